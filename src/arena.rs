@@ -0,0 +1,188 @@
+//! Container with node values in a slab, for frequent inserts and removals.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{Arena, NodeIndex};
+//!
+//! let mut arena = Arena::new();
+//! for i in 0..7 {
+//!   arena.insert(NodeIndex(i), i * 10);
+//! }
+//! let evicted = arena.remove_subtree(NodeIndex(1));
+//! assert_eq!(evicted.len(), 3);
+//! assert_eq!(arena.len(), 4);
+//! assert_eq!(arena.get(NodeIndex(4)), Some(&40));
+//! ```
+use super::*;
+
+use std::collections::BTreeMap;
+use std::iter;
+use std::ops::Range;
+
+/// Node values stored in a slab, with an ordered map from node to slot.
+///
+/// Slots are reused after a removal, so the slab doesn't grow past the
+/// largest number of values stored at once. Because the map is ordered by
+/// node, every node in a subtree, or any range of nodes, is removed with a
+/// single range split of the map.
+#[derive(Debug, Clone)]
+pub struct Arena<T> {
+  slots: Vec<Option<T>>,
+  free: Vec<usize>,
+  nodes: BTreeMap<NodeIndex, usize>,
+}
+
+impl<T> Default for Arena<T> {
+  fn default() -> Self {
+    Self {
+      slots: Vec::new(),
+      free: Vec::new(),
+      nodes: BTreeMap::new(),
+    }
+  }
+}
+
+impl<T> Arena<T> {
+  /// Create an empty arena.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Get the number of nodes that have a value.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.nodes.len()
+  }
+
+  /// Check if no node has a value.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.nodes.is_empty()
+  }
+
+  /// Get the number of slots in the slab, used or free.
+  #[inline]
+  pub fn slots(&self) -> usize {
+    self.slots.len()
+  }
+
+  /// Get the value of `node`.
+  pub fn get(&self, node: NodeIndex) -> Option<&T> {
+    let slot = *self.nodes.get(&node)?;
+    self.slots[slot].as_ref()
+  }
+
+  /// Get a mutable reference to the value of `node`.
+  pub fn get_mut(&mut self, node: NodeIndex) -> Option<&mut T> {
+    let slot = *self.nodes.get(&node)?;
+    self.slots[slot].as_mut()
+  }
+
+  /// Check if `node` has a value.
+  #[inline]
+  pub fn contains(&self, node: NodeIndex) -> bool {
+    self.nodes.contains_key(&node)
+  }
+
+  /// Set the value of `node`, reusing a free slot if there is one. Returns
+  /// the previous value.
+  pub fn insert(&mut self, node: NodeIndex, value: T) -> Option<T> {
+    if let Some(&slot) = self.nodes.get(&node) {
+      return self.slots[slot].replace(value);
+    }
+    let slot = match self.free.pop() {
+      Some(slot) => {
+        self.slots[slot] = Some(value);
+        slot
+      }
+      None => {
+        self.slots.push(Some(value));
+        self.slots.len() - 1
+      }
+    };
+    self.nodes.insert(node, slot);
+    None
+  }
+
+  /// Remove the value of `node`, returning it.
+  pub fn remove(&mut self, node: NodeIndex) -> Option<T> {
+    let slot = self.nodes.remove(&node)?;
+    self.free_slot(slot)
+  }
+
+  /// Remove the values of every node in `range`, returning them in
+  /// increasing index order.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::{Arena, NodeIndex};
+  ///
+  /// let mut arena: Arena<_> = (0..8).map(|i| (NodeIndex(i), i)).collect();
+  /// let evicted = arena.remove_range(NodeIndex(2)..NodeIndex(6));
+  /// assert_eq!(evicted.len(), 4);
+  /// assert_eq!(evicted[0], (NodeIndex(2), 2));
+  /// assert_eq!(arena.len(), 4);
+  /// ```
+  pub fn remove_range(
+    &mut self,
+    range: Range<NodeIndex>,
+  ) -> Vec<(NodeIndex, T)> {
+    if range.start >= range.end {
+      return Vec::new();
+    }
+    let mut removed = self.nodes.split_off(&range.start);
+    let mut after = removed.split_off(&range.end);
+    self.nodes.append(&mut after);
+    removed
+      .into_iter()
+      .filter_map(|(node, slot)| {
+        self.free_slot(slot).map(|value| (node, value))
+      })
+      .collect()
+  }
+
+  /// Remove the values of every node in the subtree rooted at `root`,
+  /// including `root`, returning them in increasing index order.
+  pub fn remove_subtree(&mut self, root: NodeIndex) -> Vec<(NodeIndex, T)> {
+    let (left, right) = root.spans();
+    self.remove_range(left..NodeIndex(right.0.saturating_add(1)))
+  }
+
+  /// Remove every value. The slab keeps its slots.
+  pub fn clear(&mut self) {
+    self.nodes.clear();
+    self.free = (0..self.slots.len()).rev().collect();
+    for slot in &mut self.slots {
+      *slot = None;
+    }
+  }
+
+  /// Returns an iterator over the nodes that have a value, with their
+  /// value, in increasing index order.
+  pub fn iter(&self) -> impl iter::Iterator<Item = (NodeIndex, &T)> {
+    self.nodes.iter().filter_map(move |(&node, &slot)| {
+      self.slots[slot].as_ref().map(|value| (node, value))
+    })
+  }
+
+  fn free_slot(&mut self, slot: usize) -> Option<T> {
+    self.free.push(slot);
+    self.slots[slot].take()
+  }
+}
+
+impl<T> iter::FromIterator<(NodeIndex, T)> for Arena<T> {
+  fn from_iter<I: IntoIterator<Item = (NodeIndex, T)>>(iter: I) -> Self {
+    let mut arena = Self::new();
+    arena.extend(iter);
+    arena
+  }
+}
+
+impl<T> iter::Extend<(NodeIndex, T)> for Arena<T> {
+  fn extend<I: IntoIterator<Item = (NodeIndex, T)>>(&mut self, iter: I) {
+    for (node, value) in iter {
+      self.insert(node, value);
+    }
+  }
+}
@@ -0,0 +1,143 @@
+//! A `TreeIndex` that can be shared between threads.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::AtomicTreeIndex;
+//! use std::sync::Arc;
+//! use std::thread;
+//!
+//! let tree = Arc::new(AtomicTreeIndex::new(4));
+//! let handles: Vec<_> = (0..4)
+//!   .map(|leaf| {
+//!     let tree = tree.clone();
+//!     thread::spawn(move || tree.set(leaf * 2))
+//!   })
+//!   .collect();
+//! for handle in handles {
+//!   assert!(handle.join().unwrap());
+//! }
+//! assert!(tree.get(3));
+//! ```
+use super::*;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const WORD_BITS: usize = usize::BITS as usize;
+
+/// Presence of the nodes of a tree with a fixed number of leaves, that can
+/// be updated from several threads at once without locking.
+///
+/// Like `TreeIndex`, setting a node also sets every parent whose children
+/// are both present. Bits are stored in atomic words and only ever set.
+///
+/// ## Memory ordering
+/// Setting a node and then checking its sibling are both sequentially
+/// consistent. When two threads set sibling nodes at the same time, this
+/// guarantees at least one of them sees both nodes and sets the parent. A
+/// node that's been read as present, with `get()`, happens after every write
+/// made before it was set.
+#[derive(Debug)]
+pub struct AtomicTreeIndex {
+  words: Vec<AtomicUsize>,
+  leaves: usize,
+}
+
+impl AtomicTreeIndex {
+  /// Create an empty tree index for a tree with `leaves` leaves.
+  pub fn new(leaves: usize) -> Self {
+    let len = (2 * leaves).div_ceil(WORD_BITS);
+    Self {
+      words: (0..len).map(|_| AtomicUsize::new(0)).collect(),
+      leaves,
+    }
+  }
+
+  /// Get the number of leaves in the tree.
+  #[inline]
+  pub fn tree_len(&self) -> usize {
+    self.leaves
+  }
+
+  /// Check if a node is present. Nodes outside the tree are never present.
+  #[inline]
+  pub fn get(&self, index: usize) -> bool {
+    match self.words.get(index / WORD_BITS) {
+      Some(word) => word.load(Ordering::SeqCst) & mask(index) != 0,
+      None => false,
+    }
+  }
+
+  /// Mark a node as present. Every parent whose children are now both
+  /// present is marked as well. Returns `false` if the node was already
+  /// present.
+  ///
+  /// ## Panics
+  /// If the node isn't in the tree.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let tree = flat_tree::AtomicTreeIndex::new(2);
+  /// assert!(tree.set(0));
+  /// assert!(!tree.set(0));
+  /// assert!(tree.set(2));
+  /// assert!(tree.get(1));
+  /// ```
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "trace", skip(self), ret)
+  )]
+  pub fn set(&self, index: usize) -> bool {
+    assert!(
+      in_tree(index, self.leaves),
+      "Node {} isn't in a tree with {} leaves",
+      index,
+      self.leaves
+    );
+    if !self.set_bit(index) {
+      return false;
+    }
+    // Both children of a node in the tree being present means the parent is
+    // in the tree too.
+    let mut index = index;
+    while self.get(sibling(index)) {
+      index = parent(index);
+      if !self.set_bit(index) {
+        break;
+      }
+      #[cfg(feature = "tracing")]
+      tracing::trace!(parent = index, "completed parent");
+    }
+    true
+  }
+
+  /// Copy the current state into a `TreeIndex`, for example to compute
+  /// proofs. Nodes set while copying may or may not be included.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let tree = flat_tree::AtomicTreeIndex::new(3);
+  /// tree.set(0);
+  /// tree.set(2);
+  /// assert_eq!(tree.to_tree_index().blocks(), 2);
+  /// ```
+  pub fn to_tree_index(&self) -> TreeIndex {
+    let mut bits: Vec<u8> = Vec::new();
+    for index in 0..self.words.len() * WORD_BITS {
+      if self.get(index) {
+        bits.grow(index + 1);
+        Bitfield::set(&mut bits, index, true);
+      }
+    }
+    TreeIndex::from_bitfield(bits)
+  }
+
+  fn set_bit(&self, index: usize) -> bool {
+    let word = &self.words[index / WORD_BITS];
+    word.fetch_or(mask(index), Ordering::SeqCst) & mask(index) == 0
+  }
+}
+
+#[inline]
+fn mask(index: usize) -> usize {
+  1 << (index % WORD_BITS)
+}
@@ -0,0 +1,258 @@
+//! Explore a flat tree in the terminal.
+//!
+//! Draws a tree with a given number of leaves, with a cursor that moves
+//! with the arrow keys. Nodes from a node set (`--nodes 0,2,5`) or from a
+//! `TreeIndex` bitfield file (`--bitfield FILE`) are marked with a `*`.
+//!
+//! `--once` prints a single frame instead, which is handy for bug reports.
+extern crate crossterm;
+extern crate flat_tree;
+
+use crossterm::cursor::{Hide, MoveTo, MoveToNextLine, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::style::Print;
+use crossterm::terminal::{
+  self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use crossterm::{execute, queue};
+use flat_tree::{BoundedCursor, NodeSet, TreeIndex};
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::process;
+
+const USAGE: &str = "\
+Usage: flat-tree-tui [options]
+
+Options:
+  --leaves <n>               number of leaves to draw (default 16)
+  --at <i>                   node to start at (default 0)
+  --nodes <i>,<i>,...        mark the nodes of a node set
+  --bitfield <file>          mark the nodes present in a TreeIndex bitfield
+  --once                     print a single frame and exit
+
+Keys:
+  up                         parent
+  down                       left child
+  left, right                previous and next node at the same depth
+  s                          sibling
+  l, r                       left and right child
+  q, esc                     quit";
+
+/// Shown under each frame.
+const KEYS: &str = "arrows move, s sibling, l/r children, q quits";
+
+/// The largest tree drawn, so a frame fits a wide terminal.
+const MAX_LEAVES: usize = 256;
+
+/// The tree being explored, and the nodes marked on it.
+struct Explorer {
+  cursor: BoundedCursor,
+  nodes: NodeSet,
+  tree: Option<TreeIndex>,
+}
+
+impl Explorer {
+  fn is_marked(&self, i: usize) -> bool {
+    self.nodes.contains(i) || self.tree.as_ref().is_some_and(|t| t.get(i))
+  }
+
+  /// Handle a key press. Returns `false` to quit.
+  fn key(&mut self, code: KeyCode) -> bool {
+    let cursor = &mut self.cursor;
+    match code {
+      KeyCode::Up => {
+        cursor.parent();
+      }
+      KeyCode::Down | KeyCode::Char('l') => {
+        cursor.left_child();
+      }
+      KeyCode::Char('r') => {
+        cursor.right_child();
+      }
+      KeyCode::Left => {
+        cursor.prev();
+      }
+      KeyCode::Right => {
+        cursor.next();
+      }
+      KeyCode::Char('s') => {
+        cursor.sibling();
+      }
+      KeyCode::Char('q') | KeyCode::Esc => return false,
+      _ => {}
+    }
+    true
+  }
+
+  /// Draw the tree, one line per depth with the roots at the top, then a
+  /// line describing the node under the cursor.
+  fn frame(&self) -> Vec<String> {
+    let leaves = self.cursor.tree_len();
+    let last = 2 * leaves - 2;
+    let width = last.to_string().len() + 3;
+    let top = (usize::BITS - 1 - leaves.leading_zeros()) as usize;
+
+    let mut lines = Vec::new();
+    for depth in (0..=top).rev() {
+      let mut line = String::new();
+      let mut offset = 0;
+      loop {
+        let i = flat_tree::index(depth, offset);
+        if flat_tree::right_span(i) > last {
+          break;
+        }
+        let column = i * width;
+        line.extend((line.len()..column).map(|_| ' '));
+        line.push_str(&self.cell(i, width));
+        offset += 1;
+      }
+      lines.push(line.trim_end().to_string());
+    }
+
+    let i = self.cursor.index();
+    let (left, right) = flat_tree::spans(i);
+    lines.push(String::new());
+    lines.push(format!(
+      "index {}  depth {}  offset {}  spans {}..={}  marked {}",
+      i,
+      self.cursor.depth(),
+      self.cursor.offset(),
+      left,
+      right,
+      if self.is_marked(i) { "yes" } else { "no" }
+    ));
+    lines
+  }
+
+  /// Draw node `i` as `[i]` under the cursor, followed by `*` if marked.
+  fn cell(&self, i: usize, width: usize) -> String {
+    let cell = if i == self.cursor.index() {
+      format!("[{}]", i)
+    } else {
+      format!("{}", i)
+    };
+    let mark = if self.is_marked(i) { "*" } else { "" };
+    format!("{:>w$}", format!("{}{}", cell, mark), w = width - 1)
+  }
+}
+
+/// Restores the terminal when dropped, including on panic.
+struct Screen;
+
+impl Screen {
+  fn enter() -> io::Result<Self> {
+    terminal::enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+    Ok(Screen)
+  }
+
+  fn draw(&self, lines: &[String]) -> io::Result<()> {
+    let mut out = io::stdout();
+    queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+    for line in lines {
+      queue!(out, Print(line), MoveToNextLine(1))?;
+    }
+    queue!(out, MoveToNextLine(1), Print(KEYS))?;
+    out.flush()
+  }
+}
+
+impl Drop for Screen {
+  fn drop(&mut self) {
+    let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+  }
+}
+
+fn main() {
+  let (explorer, once) = match parse(env::args().skip(1).collect()) {
+    Ok(parsed) => parsed,
+    Err(message) => {
+      eprintln!("error: {}\n\n{}", message, USAGE);
+      process::exit(2);
+    }
+  };
+
+  if once {
+    for line in explorer.frame() {
+      println!("{}", line);
+    }
+    return;
+  }
+  if let Err(err) = explore(explorer) {
+    eprintln!("error: {}", err);
+    process::exit(1);
+  }
+}
+
+fn explore(mut explorer: Explorer) -> io::Result<()> {
+  let screen = Screen::enter()?;
+  loop {
+    screen.draw(&explorer.frame())?;
+    if let Event::Key(key) = event::read()? {
+      if key.kind == KeyEventKind::Press && !explorer.key(key.code) {
+        return Ok(());
+      }
+    }
+  }
+}
+
+fn parse(args: Vec<String>) -> Result<(Explorer, bool), String> {
+  let mut leaves = 16;
+  let mut at = 0;
+  let mut nodes = NodeSet::new();
+  let mut tree = None;
+  let mut once = false;
+
+  let mut args = args.into_iter();
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--once" => once = true,
+      "-h" | "--help" => {
+        println!("{}", USAGE);
+        process::exit(0);
+      }
+      flag => {
+        let value = args
+          .next()
+          .ok_or_else(|| format!("{} takes a value", flag))?;
+        match flag {
+          "--leaves" => leaves = number(&value)?,
+          "--at" => at = number(&value)?,
+          "--nodes" => {
+            for i in value.split(',').filter(|i| !i.trim().is_empty()) {
+              nodes.insert(number(i.trim())?);
+            }
+          }
+          "--bitfield" => {
+            let bytes = fs::read(&value)
+              .map_err(|err| format!("can't read {}: {}", value, err))?;
+            tree = Some(TreeIndex::from_bitfield(bytes));
+          }
+          _ => return Err(format!("unknown option {:?}", flag)),
+        }
+      }
+    }
+  }
+
+  if leaves == 0 || leaves > MAX_LEAVES {
+    return Err(format!("--leaves must be 1 to {}", MAX_LEAVES));
+  }
+  if flat_tree::right_span(at) > 2 * leaves - 2 {
+    return Err(format!("node {} isn't in a tree of {} leaves", at, leaves));
+  }
+  let cursor = flat_tree::Iterator::new(at).with_tree_len(leaves);
+  Ok((
+    Explorer {
+      cursor,
+      nodes,
+      tree,
+    },
+    once,
+  ))
+}
+
+fn number(arg: &str) -> Result<usize, String> {
+  arg.parse().map_err(|_| format!("not a number: {:?}", arg))
+}
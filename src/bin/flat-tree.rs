@@ -0,0 +1,245 @@
+//! Inspect flat-tree indices from the command line.
+//!
+//! ```text
+//! $ flat-tree parent 42
+//! 41
+//! $ flat-tree --json spans 23
+//! [16,30]
+//! $ flat-tree path 0 parent parent left
+//! 0 1 3 1
+//! ```
+extern crate flat_tree;
+
+use std::env;
+use std::fmt;
+use std::process;
+
+const USAGE: &str = "\
+Usage: flat-tree [--json] <command> [args]
+
+Commands:
+  index <depth> <offset>     index of the node at depth and offset
+  depth <i>                  depth of node i
+  offset <i>                 offset of node i
+  parent <i>                 parent of node i
+  sibling <i>                sibling of node i
+  uncle <i>                  sibling of the parent of node i
+  children <i>               children of node i
+  spans <i>                  left and right most nodes spanned by node i
+  count <i>                  number of nodes spanned by node i
+  full-roots <i>             full roots of the tree before leaf index i
+  roots --leaves <n>         full roots of a tree with n leaves
+  path <i> <op>...           nodes visited applying each op, starting at i
+
+Path ops: parent, sibling, uncle, left, right, left_child, right_child,
+left_span, right_span.
+
+Options:
+  --json                     print the result as JSON";
+
+/// The largest depth of a node the commands accept, so every move from it
+/// fits in a `usize`.
+const MAX_DEPTH: usize = flat_tree::Iterator::MAX_DEPTH;
+
+/// The result of a command.
+enum Output {
+  Index(usize),
+  Pair(usize, usize),
+  List(Vec<usize>),
+  Nothing,
+}
+
+impl Output {
+  fn json(&self) -> String {
+    match self {
+      Output::Index(i) => i.to_string(),
+      Output::Pair(a, b) => format!("[{},{}]", a, b),
+      Output::List(nodes) => {
+        let nodes: Vec<_> = nodes.iter().map(usize::to_string).collect();
+        format!("[{}]", nodes.join(","))
+      }
+      Output::Nothing => "null".to_string(),
+    }
+  }
+}
+
+impl fmt::Display for Output {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Output::Index(i) => write!(f, "{}", i),
+      Output::Pair(a, b) => write!(f, "{} {}", a, b),
+      Output::List(nodes) => {
+        let nodes: Vec<_> = nodes.iter().map(usize::to_string).collect();
+        f.write_str(&nodes.join(" "))
+      }
+      Output::Nothing => f.write_str("none"),
+    }
+  }
+}
+
+/// Why a command failed.
+enum Error {
+  Usage(String),
+  Failed(String),
+}
+
+fn main() {
+  let mut args: Vec<String> = env::args().skip(1).collect();
+  let json = match args.iter().position(|arg| arg == "--json") {
+    Some(pos) => {
+      args.remove(pos);
+      true
+    }
+    None => false,
+  };
+
+  match run(&args) {
+    Ok(output) if json => println!("{}", output.json()),
+    Ok(output) => println!("{}", output),
+    Err(Error::Usage(message)) => {
+      eprintln!("error: {}\n\n{}", message, USAGE);
+      process::exit(2);
+    }
+    Err(Error::Failed(message)) => {
+      eprintln!("error: {}", message);
+      process::exit(1);
+    }
+  }
+}
+
+fn run(args: &[String]) -> Result<Output, Error> {
+  let (command, args) = match args.split_first() {
+    Some((command, args)) => (command.as_str(), args),
+    None => return Err(Error::Usage("missing command".to_string())),
+  };
+  if command == "help" || command == "--help" || command == "-h" {
+    println!("{}", USAGE);
+    process::exit(0);
+  }
+
+  let output = match command {
+    "index" => {
+      let (depth, offset) = two_numbers(command, args)?;
+      if depth > MAX_DEPTH {
+        return Err(Error::Failed(format!(
+          "depth {} is larger than the maximum depth {}",
+          depth, MAX_DEPTH
+        )));
+      }
+      if offset > usize::MAX >> (depth + 1) {
+        return Err(Error::Failed(format!(
+          "offset {} doesn't fit at depth {}",
+          offset, depth
+        )));
+      }
+      Output::Index(flat_tree::index(depth, offset))
+    }
+    "depth" => Output::Index(flat_tree::depth(one_number(command, args)?)),
+    "offset" => Output::Index(flat_tree::offset(one_node(command, args)?)),
+    "parent" => Output::Index(apply(flat_tree::Op::Parent, command, args)?),
+    "sibling" => Output::Index(apply(flat_tree::Op::Sibling, command, args)?),
+    "uncle" => Output::Index(apply(flat_tree::Op::Uncle, command, args)?),
+    "children" => match flat_tree::children(one_node(command, args)?) {
+      Some((left, right)) => Output::Pair(left, right),
+      None => Output::Nothing,
+    },
+    "spans" => {
+      let (left, right) = flat_tree::spans(one_node(command, args)?);
+      Output::Pair(left, right)
+    }
+    "count" => Output::Index(flat_tree::count(one_node(command, args)?)),
+    "full-roots" => {
+      let i = one_number(command, args)?;
+      if i % 2 == 1 {
+        return Err(Error::Failed(format!(
+          "full-roots needs a leaf index, got {}",
+          i
+        )));
+      }
+      Output::List(roots(i))
+    }
+    "roots" => match args {
+      [flag, leaves] if flag == "--leaves" => {
+        let leaves = number(leaves)?;
+        match leaves.checked_mul(2) {
+          Some(i) => Output::List(roots(i)),
+          None => {
+            return Err(Error::Failed(format!("too many leaves: {}", leaves)))
+          }
+        }
+      }
+      _ => return Err(Error::Usage("roots takes --leaves <n>".to_string())),
+    },
+    "path" => {
+      let (start, ops) = match args.split_first() {
+        Some((start, ops)) => (number(start)?, ops),
+        None => return Err(Error::Usage("path takes <i> <op>...".to_string())),
+      };
+      let mut nodes = vec![start];
+      for op in ops {
+        let op = match op.as_str() {
+          "left" => flat_tree::Op::LeftChild,
+          "right" => flat_tree::Op::RightChild,
+          op => op.parse().map_err(|err| Error::Usage(format!("{}", err)))?,
+        };
+        let i = op
+          .apply(nodes[nodes.len() - 1])
+          .map_err(|err| Error::Failed(format!("{}", err)))?;
+        nodes.push(i);
+      }
+      Output::List(nodes)
+    }
+    _ => return Err(Error::Usage(format!("unknown command {:?}", command))),
+  };
+  Ok(output)
+}
+
+fn apply(
+  op: flat_tree::Op,
+  command: &str,
+  args: &[String],
+) -> Result<usize, Error> {
+  op.apply(one_number(command, args)?)
+    .map_err(|err| Error::Failed(format!("{}", err)))
+}
+
+fn roots(i: usize) -> Vec<usize> {
+  let mut nodes = Vec::new();
+  flat_tree::full_roots(i, &mut nodes);
+  nodes
+}
+
+fn number(arg: &str) -> Result<usize, Error> {
+  arg
+    .parse()
+    .map_err(|_| Error::Usage(format!("not an index: {:?}", arg)))
+}
+
+fn one_number(command: &str, args: &[String]) -> Result<usize, Error> {
+  match args {
+    [i] => number(i),
+    _ => Err(Error::Usage(format!("{} takes one index", command))),
+  }
+}
+
+/// Parse a single node index that's not deeper than `MAX_DEPTH`.
+fn one_node(command: &str, args: &[String]) -> Result<usize, Error> {
+  let i = one_number(command, args)?;
+  if flat_tree::depth(i) > MAX_DEPTH {
+    return Err(Error::Failed(format!(
+      "node {} is deeper than the maximum depth {}",
+      i, MAX_DEPTH
+    )));
+  }
+  Ok(i)
+}
+
+fn two_numbers(
+  command: &str,
+  args: &[String],
+) -> Result<(usize, usize), Error> {
+  match args {
+    [a, b] => Ok((number(a)?, number(b)?)),
+    _ => Err(Error::Usage(format!("{} takes two numbers", command))),
+  }
+}
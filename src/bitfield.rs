@@ -0,0 +1,110 @@
+//! Storage for the bits of a `TreeIndex`.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{Bitfield, TreeIndex};
+//!
+//! let mut buf = [0; 4];
+//! let mut tree = TreeIndex::from_bitfield(&mut buf[..]);
+//! tree.set(0);
+//! tree.set(2);
+//! assert!(tree.get(1));
+//! assert_eq!(buf[0], 0b1110_0000);
+//! ```
+
+/// A fixed or growable sequence of bits.
+///
+/// Bits are numbered from the most significant bit of the first byte, so
+/// byte backed implementations share the same layout.
+pub trait Bitfield {
+  /// Get a bit. Bits at or past `len()` are unset.
+  fn get(&self, index: usize) -> bool;
+
+  /// Set a bit, returning `false` if it already had that value.
+  ///
+  /// ## Panics
+  /// May panic if `index` is at or past `len()`.
+  fn set(&mut self, index: usize, value: bool) -> bool;
+
+  /// Get the number of bits.
+  fn len(&self) -> usize;
+
+  /// Check if there are no bits.
+  fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Grow the bitfield so it holds at least `len` bits. Returns `false` if
+  /// it can't grow that large.
+  fn grow(&mut self, len: usize) -> bool;
+}
+
+impl Bitfield for Vec<u8> {
+  #[inline]
+  fn get(&self, index: usize) -> bool {
+    get_bit(self, index)
+  }
+
+  #[inline]
+  fn set(&mut self, index: usize, value: bool) -> bool {
+    set_bit(self, index, value)
+  }
+
+  #[inline]
+  fn len(&self) -> usize {
+    Vec::len(self) * 8
+  }
+
+  fn grow(&mut self, len: usize) -> bool {
+    let bytes = len.div_ceil(8);
+    if bytes > Vec::len(self) {
+      self.resize(bytes, 0);
+    }
+    true
+  }
+}
+
+impl Bitfield for &mut [u8] {
+  #[inline]
+  fn get(&self, index: usize) -> bool {
+    get_bit(self, index)
+  }
+
+  #[inline]
+  fn set(&mut self, index: usize, value: bool) -> bool {
+    set_bit(self, index, value)
+  }
+
+  #[inline]
+  fn len(&self) -> usize {
+    <[u8]>::len(self) * 8
+  }
+
+  fn grow(&mut self, len: usize) -> bool {
+    len <= Bitfield::len(self)
+  }
+}
+
+#[inline]
+pub(crate) fn get_bit(bytes: &[u8], index: usize) -> bool {
+  match bytes.get(index / 8) {
+    Some(byte) => byte & mask(index) != 0,
+    None => false,
+  }
+}
+
+#[inline]
+pub(crate) fn set_bit(bytes: &mut [u8], index: usize, value: bool) -> bool {
+  let byte = &mut bytes[index / 8];
+  if (*byte & mask(index) != 0) == value {
+    return false;
+  }
+  *byte ^= mask(index);
+  true
+}
+
+/// The bit for `index` in its byte.
+#[inline]
+fn mask(index: usize) -> u8 {
+  128 >> (index % 8)
+}
@@ -0,0 +1,87 @@
+//! Convert between nodes and byte ranges of data split in blocks of the
+//! same size, one block per leaf.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{byte_range_of, nodes_covering_bytes};
+//!
+//! // 1 KiB blocks.
+//! assert_eq!(byte_range_of(1, 1024), 0..2048);
+//! let nodes = nodes_covering_bytes(1000..5000, 1024, 8);
+//! assert_eq!(nodes.as_slice(), [3, 8]);
+//! ```
+use super::*;
+
+use frontier::cover;
+use std::convert::TryFrom;
+use std::ops::Range;
+
+/// Returns the bytes spanned by node `i`, with blocks of `block_size`
+/// bytes. The last block of the data may be shorter, which isn't taken
+/// into account.
+///
+/// ## Panics
+/// If the depth of `i` is larger than `Iterator::MAX_DEPTH`, or the end of
+/// the range doesn't fit in a `u64`.
+///
+/// ## Examples
+/// ```rust
+/// assert_eq!(flat_tree::byte_range_of(4, 100), 200..300);
+/// assert_eq!(flat_tree::byte_range_of(3, 100), 0..400);
+/// ```
+pub fn byte_range_of(i: usize, block_size: u64) -> Range<u64> {
+  let depth = depth(i);
+  assert!(
+    depth <= Iterator::MAX_DEPTH,
+    "Depth {} is larger than the maximum cursor depth {}",
+    depth,
+    Iterator::MAX_DEPTH
+  );
+  let (left, right) = spans_with_depth(i, depth);
+  let offset = |block: usize| {
+    (block as u64).checked_mul(block_size).unwrap_or_else(|| {
+      panic!(
+        "Block {} of {} bytes is past the end of a u64",
+        block, block_size
+      )
+    })
+  };
+  offset(left / 2)..offset(right / 2 + 1)
+}
+
+/// Returns the largest nodes covering every block that bytes `bytes` are
+/// in, from left to right, in a tree with `leaves` leaves of `block_size`
+/// bytes. Bytes past the last leaf are ignored.
+///
+/// ## Panics
+/// Panics if `block_size` is zero.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::nodes_covering_bytes;
+///
+/// let nodes = nodes_covering_bytes(0..4096, 1024, 8);
+/// assert_eq!(nodes.as_slice(), [3]);
+/// let nodes = nodes_covering_bytes(1500..1501, 1024, 8);
+/// assert_eq!(nodes.as_slice(), [2]);
+/// let nodes = nodes_covering_bytes(3000..9000, 1024, 3);
+/// assert_eq!(nodes.as_slice(), [4]);
+/// ```
+pub fn nodes_covering_bytes(
+  bytes: Range<u64>,
+  block_size: u64,
+  leaves: usize,
+) -> NodeSet {
+  assert!(block_size > 0, "The block size must be at least 1");
+  if bytes.start >= bytes.end {
+    return NodeSet::new();
+  }
+  // Blocks that don't fit in a `usize` are past the last leaf.
+  let start = match usize::try_from(bytes.start / block_size) {
+    Ok(start) => start,
+    Err(_) => return NodeSet::new(),
+  };
+  let end = usize::try_from((bytes.end - 1) / block_size)
+    .map_or(leaves, |last| last.saturating_add(1).min(leaves));
+  cover(start, end)
+}
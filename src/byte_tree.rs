@@ -0,0 +1,196 @@
+//! Map byte offsets to leaves, when leaves have different sizes.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::ByteTree;
+//!
+//! let tree: ByteTree = vec![10, 5, 20].into_iter().collect();
+//! assert_eq!(tree.seek(12), Some((1, 2)));
+//! assert_eq!(tree.byte_range(2), Some(15..35));
+//! ```
+use super::*;
+
+use std::iter;
+use std::ops::Range;
+
+/// Byte lengths of leaves, stored as a flat-tree where every parent holds
+/// the sum of the leaves it spans. This makes looking up a leaf by byte
+/// offset, and the byte range of a leaf, `O(log n)`.
+///
+/// Leaves are referred to by their number, not their flat-tree index, so
+/// leaf `n` is node `2 * n`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ByteTree {
+  nodes: Vec<u64>,
+}
+
+impl ByteTree {
+  /// Create an empty tree.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Get the number of leaves.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.nodes.len().div_ceil(2)
+  }
+
+  /// Check if there are no leaves.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.nodes.is_empty()
+  }
+
+  /// Get the sum of the lengths of all leaves.
+  pub fn byte_len(&self) -> u64 {
+    let mut roots = Vec::new();
+    full_roots(2 * self.len(), &mut roots);
+    roots.iter().map(|&root| self.nodes[root]).sum()
+  }
+
+  /// Append a leaf that's `len` bytes long.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut tree = flat_tree::ByteTree::new();
+  /// tree.push(3);
+  /// tree.push(4);
+  /// assert_eq!(tree.byte_len(), 7);
+  /// assert_eq!(tree.leaf_len(1), Some(4));
+  /// ```
+  pub fn push(&mut self, len: u64) {
+    let leaf = 2 * self.len();
+    if leaf > 0 {
+      // The new parent before the leaf spans complete leaves on its left.
+      let parent = leaf - 1;
+      let left = left_child(parent).expect("Odd nodes have children");
+      self.nodes.push(self.nodes[left]);
+    }
+    self.nodes.push(0);
+    self.add(leaf, len);
+  }
+
+  /// Get the length of leaf `leaf`, or `None` if there's no such leaf.
+  #[inline]
+  pub fn leaf_len(&self, leaf: usize) -> Option<u64> {
+    self.nodes.get(2 * leaf).cloned()
+  }
+
+  /// Change the length of leaf `leaf` to `len`.
+  ///
+  /// ## Panics
+  /// If there's no such leaf.
+  pub fn set_len(&mut self, leaf: usize, len: u64) {
+    let old = self.leaf_len(leaf).unwrap_or_else(|| {
+      panic!("Leaf {} isn't in a tree with {} leaves", leaf, self.len())
+    });
+    if len >= old {
+      self.add(2 * leaf, len - old);
+    } else {
+      self.sub(2 * leaf, old - len);
+    }
+  }
+
+  /// Get the range of bytes that leaf `leaf` covers, or `None` if there's no
+  /// such leaf.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let tree: flat_tree::ByteTree = vec![1, 2, 3, 4, 5].into_iter().collect();
+  /// assert_eq!(tree.byte_range(0), Some(0..1));
+  /// assert_eq!(tree.byte_range(4), Some(10..15));
+  /// assert_eq!(tree.byte_range(5), None);
+  /// ```
+  pub fn byte_range(&self, leaf: usize) -> Option<Range<u64>> {
+    let len = self.leaf_len(leaf)?;
+    let mut start = 0;
+    let mut iter = Iterator::new(2 * leaf);
+    while left_span(iter.index()) > 0 {
+      if iter.is_right() {
+        start += self.nodes[iter.peek_sibling()];
+      }
+      iter.parent();
+    }
+    Some(start..start + len)
+  }
+
+  /// Find the leaf containing byte `offset`. Returns the leaf and the
+  /// offset within it, or `None` if `offset` is past the end. Empty leaves
+  /// are never returned.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let tree: flat_tree::ByteTree = vec![4, 0, 4].into_iter().collect();
+  /// assert_eq!(tree.seek(3), Some((0, 3)));
+  /// assert_eq!(tree.seek(4), Some((2, 0)));
+  /// assert_eq!(tree.seek(8), None);
+  /// ```
+  pub fn seek(&self, mut offset: u64) -> Option<(usize, u64)> {
+    let mut roots = Vec::new();
+    full_roots(2 * self.len(), &mut roots);
+    for root in roots {
+      if offset >= self.nodes[root] {
+        offset -= self.nodes[root];
+        continue;
+      }
+      let mut node = root;
+      while let Some((left, right)) = children(node) {
+        if offset < self.nodes[left] {
+          node = left;
+        } else {
+          offset -= self.nodes[left];
+          node = right;
+        }
+      }
+      return Some((node / 2, offset));
+    }
+    None
+  }
+
+  /// Add `delta` to `leaf` and the ancestors of it that are stored.
+  fn add(&mut self, leaf: usize, delta: u64) {
+    for node in self.stored_path(leaf) {
+      self.nodes[node] += delta;
+    }
+  }
+
+  /// Subtract `delta` from `leaf` and the ancestors of it that are stored.
+  fn sub(&mut self, leaf: usize, delta: u64) {
+    for node in self.stored_path(leaf) {
+      self.nodes[node] -= delta;
+    }
+  }
+
+  /// Returns `leaf` and its ancestors that are stored, which are the ones
+  /// left of the end of the tree. Once a node spans the whole tree, all its
+  /// ancestors are right of the end.
+  fn stored_path(&self, leaf: usize) -> Vec<usize> {
+    let last = self.nodes.len() - 1;
+    let mut path = vec![leaf];
+    let mut iter = Iterator::new(leaf);
+    while left_span(iter.index()) > 0 || right_span(iter.index()) < last {
+      let parent = iter.parent();
+      if parent <= last {
+        path.push(parent);
+      }
+    }
+    path
+  }
+}
+
+impl iter::FromIterator<u64> for ByteTree {
+  fn from_iter<I: IntoIterator<Item = u64>>(iter: I) -> Self {
+    let mut tree = Self::new();
+    tree.extend(iter);
+    tree
+  }
+}
+
+impl iter::Extend<u64> for ByteTree {
+  fn extend<I: IntoIterator<Item = u64>>(&mut self, iter: I) {
+    for len in iter {
+      self.push(len);
+    }
+  }
+}
@@ -0,0 +1,123 @@
+//! Map data chunks to leaves, when each leaf is a group of chunks, as with
+//! bao and BLAKE3 trees that only keep parents above a chunk group.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::ChunkGroups;
+//!
+//! // Groups of 16 chunks.
+//! let groups = ChunkGroups::new(4);
+//! assert_eq!(groups.leaf(35), 4);
+//! assert_eq!(groups.chunks(1), 0..32);
+//! assert_eq!(groups.leaves(33), 3);
+//! assert_eq!(groups.roots(33), [1, 4]);
+//! ```
+use super::*;
+
+use frontier::cover;
+use std::ops::Range;
+
+/// Groups of `2^group_log` chunks, where each group is a leaf of the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChunkGroups {
+  group_log: u32,
+}
+
+impl ChunkGroups {
+  /// Create groups of `2^group_log` chunks. A `group_log` of `0` makes
+  /// every chunk a leaf.
+  ///
+  /// ## Panics
+  /// Panics if a group has more chunks than fit in a `u64`.
+  pub fn new(group_log: u32) -> Self {
+    assert!(
+      group_log < u64::BITS,
+      "A group of 2^{} chunks is too large",
+      group_log
+    );
+    Self { group_log }
+  }
+
+  /// Get the number of chunks in a group, as a power of two.
+  #[inline]
+  pub fn group_log(&self) -> u32 {
+    self.group_log
+  }
+
+  /// Get the number of chunks in a group.
+  #[inline]
+  pub fn group_len(&self) -> u64 {
+    1 << self.group_log
+  }
+
+  /// Get the leaf of the group that chunk `chunk` is in.
+  #[inline]
+  pub fn leaf(&self, chunk: u64) -> usize {
+    2 * (chunk >> self.group_log) as usize
+  }
+
+  /// Get the chunks spanned by node `i`, which always start and end on a
+  /// group boundary. The last group of the data may have fewer chunks.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let groups = flat_tree::ChunkGroups::new(2);
+  /// assert_eq!(groups.chunks(4), 8..12);
+  /// assert_eq!(groups.chunks(3), 0..16);
+  /// ```
+  pub fn chunks(&self, i: usize) -> Range<u64> {
+    let (left, right) = spans(i);
+    let start = (left / 2) as u64;
+    let end = (right / 2) as u64 + 1;
+    start << self.group_log..end << self.group_log
+  }
+
+  /// Get the number of leaves of the tree over `chunks` chunks, including
+  /// a last group that isn't full.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let groups = flat_tree::ChunkGroups::new(2);
+  /// assert_eq!(groups.leaves(0), 0);
+  /// assert_eq!(groups.leaves(8), 2);
+  /// assert_eq!(groups.leaves(9), 3);
+  /// ```
+  pub fn leaves(&self, chunks: u64) -> usize {
+    chunks.div_ceil(self.group_len()) as usize
+  }
+
+  /// Get the roots of the tree over `chunks` chunks, from left to right,
+  /// see `full_roots()`. A last group that isn't full is a root of its
+  /// own, or part of the last root.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let groups = flat_tree::ChunkGroups::new(2);
+  /// assert_eq!(groups.roots(16), [3]);
+  /// assert_eq!(groups.roots(17), [3, 8]);
+  /// ```
+  pub fn roots(&self, chunks: u64) -> Vec<usize> {
+    let mut roots = Vec::new();
+    full_roots(2 * self.leaves(chunks), &mut roots);
+    roots
+  }
+
+  /// Get the largest nodes covering every group that chunks `chunks` are
+  /// in, from left to right. The nodes span the groups of the first and
+  /// last chunk, and nothing past them.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let groups = flat_tree::ChunkGroups::new(2);
+  /// assert_eq!(groups.covering(4..16).as_slice(), [2, 5]);
+  /// assert_eq!(groups.covering(5..6).as_slice(), [2]);
+  /// ```
+  pub fn covering(&self, chunks: Range<u64>) -> NodeSet {
+    if chunks.start >= chunks.end {
+      return NodeSet::new();
+    }
+    let start = (chunks.start >> self.group_log) as usize;
+    let end = ((chunks.end - 1) >> self.group_log) as usize + 1;
+    cover(start, end)
+  }
+}
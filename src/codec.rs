@@ -0,0 +1,158 @@
+//! Binary serialization of `TreeIndex` state.
+//!
+//! ## Usage
+//! ```rust
+//! let mut tree = flat_tree::TreeIndex::new();
+//! tree.set(0);
+//! tree.set(2);
+//! let bytes = tree.to_bytes();
+//! assert_eq!(&bytes[..6], b"FTIX\x01\x00");
+//! assert_eq!(flat_tree::TreeIndex::from_bytes(&bytes), Ok(tree));
+//! ```
+use super::*;
+
+use std::error;
+use std::fmt;
+
+const MAGIC: [u8; 4] = *b"FTIX";
+const VERSION: u8 = 1;
+
+const ENCODING_RAW: u8 = 0;
+const ENCODING_RLE: u8 = 1;
+const HEADER_LEN: usize = 14;
+
+/// Error returned when a `TreeIndex`, or a list of nodes, can't be
+/// deserialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+  /// The input doesn't start with the magic bytes.
+  InvalidMagic,
+  /// The format version isn't supported.
+  UnsupportedVersion(u8),
+  /// The bitfield encoding isn't supported.
+  UnknownEncoding(u8),
+  /// The input ends before the bitfield does.
+  Truncated,
+  /// A length in the input doesn't fit in memory.
+  Overflow,
+  /// A list of nodes isn't strictly increasing.
+  NotSorted,
+}
+
+impl fmt::Display for DecodeError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      DecodeError::InvalidMagic => f.write_str("invalid magic bytes"),
+      DecodeError::UnsupportedVersion(version) => {
+        write!(f, "unsupported format version {}", version)
+      }
+      DecodeError::UnknownEncoding(encoding) => {
+        write!(f, "unknown bitfield encoding {}", encoding)
+      }
+      DecodeError::Truncated => f.write_str("unexpected end of input"),
+      DecodeError::Overflow => f.write_str("length too large"),
+      DecodeError::NotSorted => f.write_str("nodes not strictly increasing"),
+    }
+  }
+}
+
+impl error::Error for DecodeError {}
+
+impl<B: Bitfield> TreeIndex<B> {
+  /// Serialize the state of the tree index, so it can be stored or sent to
+  /// another peer.
+  ///
+  /// ## Format
+  /// All integers are little endian.
+  ///
+  /// | Offset | Size | Field                                          |
+  /// |--------|------|------------------------------------------------|
+  /// | 0      | 4    | Magic, the ASCII bytes `FTIX`                  |
+  /// | 4      | 1    | Format version, currently `1`                  |
+  /// | 5      | 1    | Encoding of the bitfield, `0` for raw          |
+  /// |        |      | or `1` for run-length encoded                  |
+  /// | 6      | 8    | Length of the encoded bitfield in bytes, `n`   |
+  /// | 14     | `n`  | The encoded bitfield                           |
+  ///
+  /// A raw bitfield is one bit per node, the bit for node `i` being bit
+  /// `7 - i % 8` of byte `i / 8`. Trailing zero bytes are left out, and nodes
+  /// past the end of the bitfield are absent. Bytes after the bitfield are
+  /// ignored, so they can be used by later versions.
+  ///
+  /// A run-length encoded bitfield is a raw bitfield encoded as described
+  /// in the `rle` module, and is written by `to_compressed_bytes()`.
+  pub fn to_bytes(&self) -> Vec<u8> {
+    with_header(ENCODING_RAW, &self.to_raw_bitfield())
+  }
+
+  /// Serialize the state of the tree index like `to_bytes()`, with the
+  /// bitfield run-length encoded.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut tree = flat_tree::TreeIndex::new();
+  /// for leaf in 0..1024 {
+  ///   tree.set(leaf * 2);
+  /// }
+  /// assert_eq!(tree.to_bytes().len(), 270);
+  /// assert_eq!(tree.to_compressed_bytes().len(), 18);
+  /// ```
+  pub fn to_compressed_bytes(&self) -> Vec<u8> {
+    with_header(ENCODING_RLE, &rle::encode(&self.to_raw_bitfield()))
+  }
+
+  /// Get the bitfield as bytes, without trailing zero bytes.
+  fn to_raw_bitfield(&self) -> Vec<u8> {
+    let mut bits = Vec::new();
+    for index in 0..self.len() {
+      if self.get(index) {
+        bits.grow(index + 1);
+        Bitfield::set(&mut bits, index, true);
+      }
+    }
+    bits
+  }
+}
+
+impl TreeIndex {
+  /// Deserialize the state of a tree index written by `to_bytes()`.
+  ///
+  /// ## Errors
+  /// If the input isn't a serialized tree index, or uses a version or
+  /// encoding that isn't supported.
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+    if bytes.len() < MAGIC.len() || bytes[..MAGIC.len()] != MAGIC {
+      return Err(DecodeError::InvalidMagic);
+    }
+    if bytes.len() < HEADER_LEN {
+      return Err(DecodeError::Truncated);
+    }
+    if bytes[4] != VERSION {
+      return Err(DecodeError::UnsupportedVersion(bytes[4]));
+    }
+    let mut len = [0; 8];
+    len.copy_from_slice(&bytes[6..HEADER_LEN]);
+    let len = u64::from_le_bytes(len);
+    let body = &bytes[HEADER_LEN..];
+    if len > body.len() as u64 {
+      return Err(DecodeError::Truncated);
+    }
+    let body = &body[..len as usize];
+
+    match bytes[5] {
+      ENCODING_RAW => Ok(TreeIndex::from_bitfield(body.to_vec())),
+      ENCODING_RLE => Ok(TreeIndex::from_bitfield(rle::decode(body)?)),
+      encoding => Err(DecodeError::UnknownEncoding(encoding)),
+    }
+  }
+}
+
+fn with_header(encoding: u8, bitfield: &[u8]) -> Vec<u8> {
+  let mut bytes = Vec::with_capacity(HEADER_LEN + bitfield.len());
+  bytes.extend_from_slice(&MAGIC);
+  bytes.push(VERSION);
+  bytes.push(encoding);
+  bytes.extend_from_slice(&(bitfield.len() as u64).to_le_bytes());
+  bytes.extend_from_slice(bitfield);
+  bytes
+}
@@ -0,0 +1,225 @@
+//! ## Usage
+//! ```rust
+//! use flat_tree::Coords;
+//!
+//! let coords: Vec<Coords> = flat_tree::Iterator::new(1)
+//!   .with_coords()
+//!   .take(2)
+//!   .collect();
+//! assert_eq!(coords[0], Coords { index: 5, depth: 1, offset: 1 });
+//! assert_eq!(coords[1], Coords { index: 9, depth: 1, offset: 2 });
+//! ```
+use super::*;
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::{Arbitrary, Unstructured};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+use std::iter;
+
+/// A flat-tree index together with its depth and offset.
+///
+/// With the `serde` feature the coordinates are serialized as their
+/// `index`, `depth` and `offset`. Deserializing rejects an index deeper
+/// than `Iterator::MAX_DEPTH`, and a depth or offset that doesn't match the
+/// index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(try_from = "State", into = "State")
+)]
+pub struct Coords {
+  /// The flat-tree index.
+  pub index: usize,
+  /// The depth of the node.
+  pub depth: usize,
+  /// The offset of the node.
+  pub offset: usize,
+}
+
+impl Coords {
+  /// Get the coordinates of an index.
+  ///
+  /// ## Panics
+  /// If the depth of `index` is larger than `Iterator::MAX_DEPTH`, see
+  /// `try_new()`.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::Coords;
+  ///
+  /// assert_eq!(Coords::new(23), Coords { index: 23, depth: 3, offset: 1 });
+  /// ```
+  pub fn new(index: usize) -> Self {
+    let depth = (!index).trailing_zeros() as usize;
+    assert!(
+      depth <= Iterator::MAX_DEPTH,
+      "Depth {} is larger than the maximum cursor depth {}",
+      depth,
+      Iterator::MAX_DEPTH
+    );
+    Self {
+      index,
+      depth,
+      offset: offset_with_depth(index, depth),
+    }
+  }
+
+  /// Get the coordinates of an index, or `None` if its depth is larger than
+  /// `Iterator::MAX_DEPTH`.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::Coords;
+  ///
+  /// assert_eq!(Coords::try_new(5), Some(Coords::new(5)));
+  /// assert_eq!(Coords::try_new(usize::MAX), None);
+  /// ```
+  pub fn try_new(index: usize) -> Option<Self> {
+    if (!index).trailing_zeros() as usize > Iterator::MAX_DEPTH {
+      return None;
+    }
+    Some(Self::new(index))
+  }
+}
+
+impl From<usize> for Coords {
+  fn from(index: usize) -> Self {
+    Coords::new(index)
+  }
+}
+
+impl From<Coords> for usize {
+  fn from(coords: Coords) -> Self {
+    coords.index
+  }
+}
+
+// Only the index is generated, and the depth and offset are derived from it.
+// As for the cursor, an index deeper than `Iterator::MAX_DEPTH` is clamped
+// to that depth.
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for Coords {
+  fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+    let mut index = usize::arbitrary(u)?;
+    if index.trailing_ones() as usize > Iterator::MAX_DEPTH {
+      // Unset the bit that makes the index too deep.
+      index -= 1 << Iterator::MAX_DEPTH;
+    }
+    Ok(Coords::new(index))
+  }
+
+  fn size_hint(depth: usize) -> (usize, Option<usize>) {
+    usize::size_hint(depth)
+  }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Coords {
+  fn format(&self, f: defmt::Formatter) {
+    defmt::write!(
+      f,
+      "Coords {{ index: {=usize}, depth: {=usize}, offset: {=usize} }}",
+      self.index,
+      self.depth,
+      self.offset
+    )
+  }
+}
+
+/// Serialized form of `Coords`.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct State {
+  index: usize,
+  depth: usize,
+  offset: usize,
+}
+
+#[cfg(feature = "serde")]
+impl From<Coords> for State {
+  fn from(coords: Coords) -> Self {
+    Self {
+      index: coords.index,
+      depth: coords.depth,
+      offset: coords.offset,
+    }
+  }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<State> for Coords {
+  type Error = String;
+
+  fn try_from(state: State) -> Result<Self, Self::Error> {
+    let coords = match Coords::try_new(state.index) {
+      Some(coords) => coords,
+      None => {
+        return Err(format!(
+          "invalid coords: index {} is deeper than the maximum depth {}",
+          state.index,
+          Iterator::MAX_DEPTH
+        ))
+      }
+    };
+    if coords.depth != state.depth || coords.offset != state.offset {
+      return Err(format!(
+        "invalid coords: index {} has depth {} and offset {}, got depth {} \
+         and offset {}",
+        coords.index, coords.depth, coords.offset, state.depth, state.offset
+      ));
+    }
+    Ok(coords)
+  }
+}
+
+/// Adapter yielding the `Coords` of every index of an iterator. The depth is
+/// derived from the bits of each index in constant time.
+#[derive(Debug, Clone)]
+pub struct WithCoords<I> {
+  iter: I,
+}
+
+impl<I> WithCoords<I> {
+  pub(crate) fn new(iter: I) -> Self {
+    Self { iter }
+  }
+
+  /// Unwrap the underlying iterator.
+  pub fn into_inner(self) -> I {
+    self.iter
+  }
+}
+
+impl<I: iter::Iterator<Item = usize>> iter::Iterator for WithCoords<I> {
+  type Item = Coords;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next().map(Coords::new)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<I: iter::DoubleEndedIterator<Item = usize>> iter::DoubleEndedIterator
+  for WithCoords<I>
+{
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.iter.next_back().map(Coords::new)
+  }
+}
+
+impl<I: iter::ExactSizeIterator<Item = usize>> iter::ExactSizeIterator
+  for WithCoords<I>
+{
+}
+
+impl<I: iter::FusedIterator<Item = usize>> iter::FusedIterator
+  for WithCoords<I>
+{
+}
@@ -0,0 +1,112 @@
+//! Compute which nodes the proofs of a Certificate Transparency log are
+//! made of, as specified by RFC 6962.
+//!
+//! A log with `size` leaves is the left-balanced tree of `to_preorder()`.
+//! Its nodes are flat-tree nodes, but a node whose subtree goes past the
+//! last leaf stands for the hash of the leaves it does have, so its value
+//! changes as the log grows.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::ct;
+//!
+//! // The example from RFC 6962, section 2.1.3: the proof for leaf `d` in
+//! // a log of 7 leaves is `[c, g, l]`.
+//! assert_eq!(ct::inclusion_proof(3, 7), Some(vec![4, 1, 11]));
+//!
+//! // And the proof that the log of 3 leaves is a prefix of it is
+//! // `[c, d, g, l]`.
+//! assert_eq!(ct::consistency_proof(3, 7), Some(vec![4, 6, 1, 11]));
+//! ```
+use super::*;
+
+use numbering::{balanced_right, balanced_root};
+
+/// Returns the nodes of the inclusion proof of leaf number `leaf` in a log
+/// with `size` leaves, in RFC 6962 order: from the sibling of the leaf up to
+/// the child of the root.
+///
+/// Returns `None` if the leaf isn't in the log.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::ct;
+///
+/// assert_eq!(ct::inclusion_proof(4, 7), Some(vec![10, 12, 3]));
+/// assert_eq!(ct::inclusion_proof(0, 1), Some(vec![]));
+/// assert_eq!(ct::inclusion_proof(7, 7), None);
+/// ```
+pub fn inclusion_proof(leaf: usize, size: usize) -> Option<Vec<usize>> {
+  if leaf >= size {
+    return None;
+  }
+  let last = 2 * size - 2;
+  let target = 2 * leaf;
+  let mut node = balanced_root(last);
+  let mut proof = Vec::new();
+  while node != target {
+    let (left, right) = children(node)?;
+    let right = balanced_right(right, last);
+    if target < node {
+      proof.push(right);
+      node = left;
+    } else {
+      proof.push(left);
+      node = right;
+    }
+  }
+  proof.reverse();
+  Some(proof)
+}
+
+/// Returns the nodes of the consistency proof between a log with
+/// `old_size` leaves and the same log grown to `new_size` leaves, in
+/// RFC 6962 order.
+///
+/// Returns `None` if `old_size` is zero or larger than `new_size`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::ct;
+///
+/// assert_eq!(ct::consistency_proof(4, 7), Some(vec![11]));
+/// assert_eq!(ct::consistency_proof(6, 7), Some(vec![9, 12, 3]));
+/// assert_eq!(ct::consistency_proof(7, 7), Some(vec![]));
+/// assert_eq!(ct::consistency_proof(8, 7), None);
+/// ```
+pub fn consistency_proof(
+  old_size: usize,
+  new_size: usize,
+) -> Option<Vec<usize>> {
+  if old_size == 0 || old_size > new_size {
+    return None;
+  }
+  let last = 2 * new_size - 2;
+  let mut node = balanced_root(last);
+  let (mut old, mut new) = (old_size, new_size);
+  // The old root is only left out of the proof if it's a node of the new
+  // log, which is when the old log is a left subtree of it.
+  let mut old_root_known = true;
+  let mut proof = Vec::new();
+  while old != new {
+    let (left, right) = children(node)?;
+    let right = balanced_right(right, last);
+    let split = 1 << (depth(node) - 1);
+    if old <= split {
+      proof.push(right);
+      node = left;
+      new = split;
+    } else {
+      proof.push(left);
+      node = right;
+      old -= split;
+      new -= split;
+      old_root_known = false;
+    }
+  }
+  if !old_root_known {
+    proof.push(node);
+  }
+  proof.reverse();
+  Some(proof)
+}
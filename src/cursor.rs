@@ -0,0 +1,256 @@
+//! ## Usage
+//! ```rust
+//! let mut cursor = flat_tree::Cursor::new(0);
+//! assert_eq!(cursor.parent(), 1);
+//! assert_eq!(cursor.parent(), 3);
+//! assert_eq!(cursor.undo(), Some(1));
+//! assert_eq!(cursor.redo(), Some(3));
+//! assert_eq!(cursor.path(), [0, 1, 3]);
+//! ```
+use super::*;
+
+use std::fmt;
+use std::iter;
+
+/// A movement made by a `Cursor`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+  /// See `Iterator::seek()`.
+  Seek,
+  /// See `Iterator::next()`.
+  Next,
+  /// See `Iterator::prev()`.
+  Prev,
+  /// See `Iterator::sibling()`.
+  Sibling,
+  /// See `Iterator::parent()`.
+  Parent,
+  /// See `Iterator::left_child()`.
+  LeftChild,
+  /// See `Iterator::right_child()`.
+  RightChild,
+  /// See `Iterator::left_span()`.
+  LeftSpan,
+  /// See `Iterator::right_span()`.
+  RightSpan,
+  /// See `Iterator::next_tree()`.
+  NextTree,
+  /// See `Iterator::prev_tree()`.
+  PrevTree,
+}
+
+impl Move {
+  /// The name of the movement, as shown by `Display`.
+  fn name(self) -> &'static str {
+    match self {
+      Move::Seek => "seek",
+      Move::Next => "next",
+      Move::Prev => "prev",
+      Move::Sibling => "sibling",
+      Move::Parent => "parent",
+      Move::LeftChild => "left_child",
+      Move::RightChild => "right_child",
+      Move::LeftSpan => "left_span",
+      Move::RightSpan => "right_span",
+      Move::NextTree => "next_tree",
+      Move::PrevTree => "prev_tree",
+    }
+  }
+}
+
+impl fmt::Display for Move {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str(self.name())
+  }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Move {
+  fn format(&self, f: defmt::Formatter) {
+    defmt::write!(f, "{=str}", self.name())
+  }
+}
+
+/// A single recorded movement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Record {
+  /// The movement made.
+  pub op: Move,
+  /// The index before the movement.
+  pub from: usize,
+  /// The index after the movement.
+  pub to: usize,
+}
+
+impl fmt::Display for Record {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{} -> {} ({})", self.from, self.to, self.op)
+  }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Record {
+  fn format(&self, f: defmt::Formatter) {
+    defmt::write!(f, "{=usize} -> {=usize} ({})", self.from, self.to, self.op)
+  }
+}
+
+/// Cursor over a flat-tree that records every movement, and can undo and redo
+/// them.
+#[derive(Debug)]
+pub struct Cursor {
+  iter: Iterator,
+  start: usize,
+  steps: Vec<Record>,
+  undone: Vec<Record>,
+}
+
+impl Cursor {
+  /// Create a new cursor.
+  pub fn new(index: usize) -> Self {
+    Self {
+      iter: Iterator::new(index),
+      start: index,
+      steps: Vec::new(),
+      undone: Vec::new(),
+    }
+  }
+
+  /// Get the current index.
+  #[inline]
+  pub fn index(&self) -> usize {
+    self.iter.index()
+  }
+
+  /// Get a reference to the underlying cursor.
+  #[inline]
+  pub fn get_ref(&self) -> &Iterator {
+    &self.iter
+  }
+
+  /// Get the steps taken, excluding the ones that were undone.
+  #[inline]
+  pub fn history(&self) -> &[Record] {
+    &self.steps
+  }
+
+  /// Get every index visited, starting with the initial one and excluding
+  /// the steps that were undone.
+  pub fn path(&self) -> Vec<usize> {
+    iter::once(self.start)
+      .chain(self.steps.iter().map(|step| step.to))
+      .collect()
+  }
+
+  /// Undo the last step. Returns the index moved back to, or `None` if there
+  /// is nothing to undo.
+  pub fn undo(&mut self) -> Option<usize> {
+    let step = self.steps.pop()?;
+    self.iter.seek(step.from);
+    self.undone.push(step);
+    Some(step.from)
+  }
+
+  /// Redo the last undone step. Returns the index moved to, or `None` if
+  /// there is nothing to redo.
+  pub fn redo(&mut self) -> Option<usize> {
+    let step = self.undone.pop()?;
+    self.iter.seek(step.to);
+    self.steps.push(step);
+    Some(step.to)
+  }
+
+  /// Seek to a position.
+  pub fn seek(&mut self, index: usize) {
+    self.record(Move::Seek, |iter| {
+      iter.seek(index);
+      index
+    });
+  }
+
+  /// Move to the previous node at the same depth.
+  pub fn prev(&mut self) -> usize {
+    self.record(Move::Prev, Iterator::prev)
+  }
+
+  /// Move to the sibling.
+  pub fn sibling(&mut self) -> usize {
+    self.record(Move::Sibling, Iterator::sibling)
+  }
+
+  /// Move to the parent.
+  pub fn parent(&mut self) -> usize {
+    self.record(Move::Parent, Iterator::parent)
+  }
+
+  /// Move to the left child.
+  pub fn left_child(&mut self) -> usize {
+    self.record(Move::LeftChild, Iterator::left_child)
+  }
+
+  /// Move to the right child.
+  pub fn right_child(&mut self) -> usize {
+    self.record(Move::RightChild, Iterator::right_child)
+  }
+
+  /// Move to the left most node spanned.
+  pub fn left_span(&mut self) -> usize {
+    self.record(Move::LeftSpan, Iterator::left_span)
+  }
+
+  /// Move to the right most node spanned.
+  pub fn right_span(&mut self) -> usize {
+    self.record(Move::RightSpan, Iterator::right_span)
+  }
+
+  /// Move to the first leaf after the tree spanned.
+  pub fn next_tree(&mut self) -> usize {
+    self.record(Move::NextTree, Iterator::next_tree)
+  }
+
+  /// Move to the last leaf before the tree spanned.
+  pub fn prev_tree(&mut self) -> usize {
+    self.record(Move::PrevTree, Iterator::prev_tree)
+  }
+
+  fn record<F>(&mut self, op: Move, f: F) -> usize
+  where
+    F: FnOnce(&mut Iterator) -> usize,
+  {
+    let from = self.iter.index();
+    let to = f(&mut self.iter);
+    self.steps.push(Record { op, from, to });
+    self.undone.clear();
+    to
+  }
+}
+
+// The recorded steps are left out, they can be logged one by one.
+#[cfg(feature = "defmt")]
+impl defmt::Format for Cursor {
+  fn format(&self, f: defmt::Formatter) {
+    defmt::write!(
+      f,
+      "Cursor {{ index: {=usize}, start: {=usize}, steps: {=usize} }}",
+      self.iter.index(),
+      self.start,
+      self.steps.len()
+    )
+  }
+}
+
+impl iter::Iterator for Cursor {
+  type Item = usize;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let from = self.iter.index();
+    let to = self.iter.next()?;
+    self.steps.push(Record {
+      op: Move::Next,
+      from,
+      to,
+    });
+    self.undone.clear();
+    Some(to)
+  }
+}
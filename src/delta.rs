@@ -0,0 +1,248 @@
+//! Compact encoding of sorted lists of node indices, such as the nodes of a
+//! proof or a list of wanted or present nodes.
+//!
+//! Each index is stored as the difference from the one before it, the
+//! first one as the difference from `0`. The differences are written as
+//! unsigned LEB128 varints:
+//!
+//! - A header `delta << 1` is a single difference of `delta`.
+//! - A header `delta << 1 | 1` is followed by a varint `n`, and is a run of
+//!   `n + 3` differences of `delta`, such as a sequence of adjacent leaves.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::delta;
+//!
+//! let nodes = [1, 4, 6, 8, 10, 12, 100];
+//! let encoded = delta::encode(&nodes);
+//! assert_eq!(encoded, [0b10, 0b110, 0b101, 1, 176, 1]);
+//! assert_eq!(delta::decode(&encoded).unwrap(), nodes);
+//! ```
+use super::*;
+
+/// The shortest run of equal differences that's encoded as a run.
+const MIN_RUN: usize = 3;
+
+/// The most node indices `decode()` decodes.
+pub const MAX_DECODED_LEN: usize = 1 << 24;
+
+/// Encode a list of node indices.
+///
+/// ## Panics
+/// If `nodes` isn't strictly increasing.
+///
+/// ## Examples
+/// ```rust
+/// let leaves: Vec<usize> = (0..1000).map(|leaf| leaf * 2).collect();
+/// assert_eq!(flat_tree::delta::encode(&leaves), [0, 0b101, 0xe4, 0x07]);
+/// ```
+pub fn encode(nodes: &[usize]) -> Vec<u8> {
+  let mut deltas = Vec::with_capacity(nodes.len());
+  let mut previous = 0;
+  for (i, &node) in nodes.iter().enumerate() {
+    assert!(
+      i == 0 || node > previous,
+      "Node indices must be strictly increasing, got {} after {}",
+      node,
+      previous
+    );
+    deltas.push(node - previous);
+    previous = node;
+  }
+
+  let mut out = Vec::new();
+  let mut i = 0;
+  while i < deltas.len() {
+    let delta = deltas[i] as u128;
+    let run = deltas[i..].iter().take_while(|&&d| d == deltas[i]).count();
+    if run >= MIN_RUN {
+      write_varint(&mut out, delta << 1 | 1);
+      write_varint(&mut out, (run - MIN_RUN) as u128);
+      i += run;
+    } else {
+      write_varint(&mut out, delta << 1);
+      i += 1;
+    }
+  }
+  out
+}
+
+/// Decode a list of at most `MAX_DECODED_LEN` node indices, see
+/// `decode_max()`.
+///
+/// ## Errors
+/// If the input ends in the middle of a varint, doesn't decode to a
+/// strictly increasing list of indices, or has more than
+/// `MAX_DECODED_LEN` of them.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::{delta, DecodeError};
+///
+/// assert_eq!(delta::decode(&[0b1000, 0]), Err(DecodeError::NotSorted));
+/// assert_eq!(delta::decode(&[0b101]), Err(DecodeError::Truncated));
+/// ```
+pub fn decode(encoded: &[u8]) -> Result<Vec<usize>, DecodeError> {
+  decode_max(encoded, MAX_DECODED_LEN)
+}
+
+/// Decode a list of at most `max_len` node indices. A few bytes of input
+/// can describe a long run, so input from other peers should be decoded
+/// with a limit.
+///
+/// ## Errors
+/// As `decode()`, or if there are more than `max_len` indices.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::{delta, DecodeError};
+///
+/// assert_eq!(delta::decode_max(&[0b11, 0], 3), Ok(vec![1, 2, 3]));
+/// assert_eq!(delta::decode_max(&[0b11, 1], 3), Err(DecodeError::Overflow));
+/// ```
+pub fn decode_max(
+  encoded: &[u8],
+  max_len: usize,
+) -> Result<Vec<usize>, DecodeError> {
+  let mut nodes = Vec::new();
+  let mut decoder = decoder(encoded);
+  while let Some(node) = decoder.next() {
+    let node = node?;
+    if nodes.len() == max_len {
+      return Err(DecodeError::Overflow);
+    }
+    nodes.push(node);
+    // Check the rest of a run before decoding it.
+    if decoder.remaining > (max_len - nodes.len()) as u128 {
+      return Err(DecodeError::Overflow);
+    }
+  }
+  Ok(nodes)
+}
+
+/// Returns an iterator decoding the node indices one at a time, see
+/// `decode()`. Unlike `decode()` it doesn't allocate, so a small input
+/// with a very long run can be decoded lazily. A run is rejected up front
+/// if its last index doesn't fit in a `usize`.
+///
+/// The iterator stops after the first error.
+///
+/// ## Examples
+/// ```rust
+/// let mut nodes = flat_tree::delta::decoder(&[0b11, 0xff, 0xff, 0x0f]);
+/// assert_eq!(nodes.next(), Some(Ok(1)));
+/// assert_eq!(nodes.next(), Some(Ok(2)));
+/// ```
+pub fn decoder(encoded: &[u8]) -> Decoder<'_> {
+  Decoder {
+    encoded,
+    previous: None,
+    delta: 0,
+    remaining: 0,
+    failed: false,
+  }
+}
+
+/// Iterator decoding node indices, created by `decoder()`.
+#[derive(Debug, Clone)]
+pub struct Decoder<'a> {
+  encoded: &'a [u8],
+  previous: Option<usize>,
+  delta: usize,
+  remaining: u128,
+  failed: bool,
+}
+
+impl<'a> Decoder<'a> {
+  fn next_delta(&mut self) -> Option<Result<usize, DecodeError>> {
+    if self.remaining > 0 {
+      self.remaining -= 1;
+      return Some(Ok(self.delta));
+    }
+    if self.encoded.is_empty() {
+      return None;
+    }
+    let header = match read_varint(&mut self.encoded) {
+      Ok(header) => header,
+      Err(err) => return Some(Err(err)),
+    };
+    let delta = match to_usize(header >> 1) {
+      Ok(delta) => delta,
+      Err(err) => return Some(Err(err)),
+    };
+    if header & 1 == 1 {
+      let run = match read_varint(&mut self.encoded) {
+        Ok(run) => run,
+        Err(err) => return Some(Err(err)),
+      };
+      // The last index of the run is `previous + delta * (run + MIN_RUN)`.
+      let last = run
+        .checked_add(MIN_RUN as u128)
+        .and_then(|len| len.checked_mul(delta as u128))
+        .and_then(|len| len.checked_add(self.previous.unwrap_or(0) as u128));
+      match last {
+        Some(last) if last <= usize::MAX as u128 => {}
+        _ => return Some(Err(DecodeError::Overflow)),
+      }
+      self.remaining = run + MIN_RUN as u128 - 1;
+      self.delta = delta;
+    }
+    Some(Ok(delta))
+  }
+}
+
+impl<'a> std::iter::Iterator for Decoder<'a> {
+  type Item = Result<usize, DecodeError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.failed {
+      return None;
+    }
+    let node = self.next_delta()?.and_then(|delta| match self.previous {
+      None => Ok(delta),
+      Some(_) if delta == 0 => Err(DecodeError::NotSorted),
+      Some(previous) => {
+        previous.checked_add(delta).ok_or(DecodeError::Overflow)
+      }
+    });
+    match node {
+      Ok(node) => self.previous = Some(node),
+      Err(_) => self.failed = true,
+    }
+    Some(node)
+  }
+}
+
+impl<'a> std::iter::FusedIterator for Decoder<'a> {}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u128) {
+  while value >= 0x80 {
+    out.push(value as u8 | 0x80);
+    value >>= 7;
+  }
+  out.push(value as u8);
+}
+
+fn read_varint(encoded: &mut &[u8]) -> Result<u128, DecodeError> {
+  let mut value = 0u128;
+  for (i, &byte) in encoded.iter().enumerate() {
+    let bits = u128::from(byte & 0x7f);
+    // Reject bits shifted out of the value, rather than dropping them.
+    if i * 7 >= 128 || (bits << (i * 7)) >> (i * 7) != bits {
+      return Err(DecodeError::Overflow);
+    }
+    value |= bits << (i * 7);
+    if byte & 0x80 == 0 {
+      *encoded = &encoded[i + 1..];
+      return Ok(value);
+    }
+  }
+  Err(DecodeError::Truncated)
+}
+
+fn to_usize(value: u128) -> Result<usize, DecodeError> {
+  if value > usize::MAX as u128 {
+    return Err(DecodeError::Overflow);
+  }
+  Ok(value as usize)
+}
@@ -0,0 +1,299 @@
+//! Container with a value per node, stored in a `Vec` in flat-tree order.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{DenseTree, Order};
+//!
+//! let mut tree = DenseTree::new();
+//! tree.set(0, "a");
+//! tree.set(2, "b");
+//! tree.set(1, "ab");
+//! assert_eq!(tree.get(1), Some(&"ab"));
+//!
+//! let values: Vec<_> = tree.subtree(1, Order::PreOrder).collect();
+//! assert_eq!(values, [(1, &"ab"), (0, &"a"), (2, &"b")]);
+//! ```
+use super::*;
+
+use std::iter;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Number of nodes in a page, as a power of two.
+const PAGE_BITS: usize = 6;
+const PAGE_LEN: usize = 1 << PAGE_BITS;
+
+type Page<T> = Arc<Vec<Option<T>>>;
+
+/// A value for every node of a flat-tree, stored in a `Vec` indexed by
+/// node. Nodes don't need to have a value, and the `Vec` grows on demand
+/// when setting a node past its end.
+///
+/// The `Vec` is split in fixed-size pages that are shared with snapshots,
+/// and copied on the first write after a snapshot. See `snapshot()`.
+///
+/// Best suited to trees where most nodes have a value, see `SparseTree`
+/// otherwise.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct DenseTree<T> {
+  pages: Vec<Page<T>>,
+  len: usize,
+}
+
+impl<T> Default for DenseTree<T> {
+  fn default() -> Self {
+    Self {
+      pages: Vec::new(),
+      len: 0,
+    }
+  }
+}
+
+// Cloning only shares the pages, so it doesn't need `T: Clone`.
+impl<T> Clone for DenseTree<T> {
+  fn clone(&self) -> Self {
+    Self {
+      pages: self.pages.clone(),
+      len: self.len,
+    }
+  }
+}
+
+impl<T> DenseTree<T> {
+  /// Create an empty tree.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Create an empty tree with room for `nodes` nodes before growing.
+  pub fn with_capacity(nodes: usize) -> Self {
+    Self {
+      pages: Vec::with_capacity(nodes.div_ceil(PAGE_LEN)),
+      len: 0,
+    }
+  }
+
+  /// Get the number of nodes the tree has room for. Every node at or past
+  /// this index has no value.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Get the number of leaves the tree has room for.
+  #[inline]
+  pub fn leaves(&self) -> usize {
+    self.len.div_ceil(2)
+  }
+
+  /// Check if the tree has room for no nodes.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Get the value of node `i`.
+  #[inline]
+  pub fn get(&self, i: usize) -> Option<&T> {
+    self
+      .pages
+      .get(i >> PAGE_BITS)
+      .and_then(|page| page[i & (PAGE_LEN - 1)].as_ref())
+  }
+
+  /// Check if node `i` has a value.
+  #[inline]
+  pub fn contains(&self, i: usize) -> bool {
+    self.get(i).is_some()
+  }
+
+  /// Remove every value. The tree doesn't shrink.
+  pub fn clear(&mut self) {
+    for page in &mut self.pages {
+      *page = empty_page();
+    }
+  }
+
+  /// Get a frozen view of the tree as it is now. Taking a snapshot is
+  /// cheap, as it shares its pages with the tree. The tree can keep
+  /// changing, which copies a shared page the first time it's written to.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut tree = flat_tree::DenseTree::new();
+  /// tree.set(0, 'a');
+  /// let snapshot = tree.snapshot();
+  /// tree.set(0, 'b');
+  /// tree.set(2, 'c');
+  /// assert_eq!(snapshot.get(0), Some(&'a'));
+  /// assert_eq!(snapshot.len(), 1);
+  /// assert_eq!(tree.get(0), Some(&'b'));
+  /// ```
+  pub fn snapshot(&self) -> DenseSnapshot<T> {
+    DenseSnapshot { tree: self.clone() }
+  }
+
+  /// Returns an iterator over the nodes that have a value, in increasing
+  /// index order.
+  pub fn iter(&self) -> impl iter::Iterator<Item = (usize, &T)> {
+    self
+      .pages
+      .iter()
+      .flat_map(|page| page.iter())
+      .enumerate()
+      .filter_map(|(i, value)| value.as_ref().map(|value| (i, value)))
+  }
+
+  /// Returns an iterator over the nodes in the subtree rooted at `root`,
+  /// including `root`, that have a value. Every node in the subtree is
+  /// visited, see `subtree_nodes()`.
+  pub fn subtree(
+    &self,
+    root: usize,
+    order: Order,
+  ) -> impl iter::Iterator<Item = (usize, &T)> {
+    subtree_nodes(root, order)
+      .filter_map(move |i| self.get(i).map(|value| (i, value)))
+  }
+
+  /// Returns an iterator over the ancestors of node `i` that have a value,
+  /// from its parent upwards.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut tree = flat_tree::DenseTree::new();
+  /// tree.set(1, 'b');
+  /// tree.set(7, 'd');
+  /// let values: Vec<_> = tree.ancestors(0).collect();
+  /// assert_eq!(values, [(1, &'b'), (7, &'d')]);
+  /// ```
+  pub fn ancestors(&self, i: usize) -> impl iter::Iterator<Item = (usize, &T)> {
+    // Past the end, only ancestors that are right children can have a
+    // parent before the end.
+    let len = self.len();
+    ancestors(i)
+      .take_while(move |&parent| parent < len || left_span(parent) > 0)
+      .filter_map(move |i| self.get(i).map(|value| (i, value)))
+  }
+}
+
+impl<T: Clone> DenseTree<T> {
+  /// Get a mutable reference to the value of node `i`.
+  pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+    if !self.contains(i) {
+      return None;
+    }
+    self.slot_mut(i).as_mut()
+  }
+
+  /// Set the value of node `i`, growing the tree if needed. Returns the
+  /// previous value.
+  pub fn set(&mut self, i: usize, value: T) -> Option<T> {
+    while self.pages.len() <= i >> PAGE_BITS {
+      self.pages.push(empty_page());
+    }
+    self.len = self.len.max(i + 1);
+    self.slot_mut(i).replace(value)
+  }
+
+  /// Append a leaf with value `value`, after the last node the tree has
+  /// room for. Returns the parents the leaf completes, from the bottom up,
+  /// see `completed_parents()`. Their values are removed, as they're out
+  /// of date, and are left for the caller to set in that order.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut tree = flat_tree::DenseTree::new();
+  /// assert_eq!(tree.push_leaf("a").count(), 0);
+  /// for parent in tree.push_leaf("b") {
+  ///   assert_eq!(parent, 1);
+  ///   tree.set(parent, "ab");
+  /// }
+  /// assert_eq!(tree.leaves(), 2);
+  /// assert_eq!(tree.get(2), Some(&"b"));
+  /// ```
+  pub fn push_leaf(&mut self, value: T) -> Ancestors {
+    let leaf = 2 * self.leaves();
+    self.set(leaf, value);
+    let parents = completed_parents(leaf);
+    for parent in parents {
+      self.remove(parent);
+    }
+    parents
+  }
+
+  /// Remove the value of node `i`, returning it. The tree doesn't shrink.
+  pub fn remove(&mut self, i: usize) -> Option<T> {
+    if !self.contains(i) {
+      return None;
+    }
+    self.slot_mut(i).take()
+  }
+
+  /// Remove every value and return the nodes that had a value, in
+  /// increasing index order.
+  pub fn take(&mut self) -> Vec<(usize, T)> {
+    self.len = 0;
+    self
+      .pages
+      .drain(..)
+      .flat_map(|page| {
+        Arc::try_unwrap(page).unwrap_or_else(|page| page.as_ref().clone())
+      })
+      .enumerate()
+      .filter_map(|(i, value)| value.map(|value| (i, value)))
+      .collect()
+  }
+
+  /// Get the slot of node `i`, which must be in a page, copying the page
+  /// first if it's shared.
+  fn slot_mut(&mut self, i: usize) -> &mut Option<T> {
+    let page = Arc::make_mut(&mut self.pages[i >> PAGE_BITS]);
+    &mut page[i & (PAGE_LEN - 1)]
+  }
+}
+
+impl<T: Clone> iter::FromIterator<(usize, T)> for DenseTree<T> {
+  fn from_iter<I: IntoIterator<Item = (usize, T)>>(iter: I) -> Self {
+    let mut tree = Self::new();
+    tree.extend(iter);
+    tree
+  }
+}
+
+impl<T: Clone> iter::Extend<(usize, T)> for DenseTree<T> {
+  fn extend<I: IntoIterator<Item = (usize, T)>>(&mut self, iter: I) {
+    for (i, value) in iter {
+      self.set(i, value);
+    }
+  }
+}
+
+fn empty_page<T>() -> Page<T> {
+  Arc::new((0..PAGE_LEN).map(|_| None).collect())
+}
+
+/// A frozen view of a `DenseTree`, that doesn't change when the tree does.
+///
+/// Created by `DenseTree::snapshot()`. It derefs to a `DenseTree`, so every
+/// method that doesn't change the tree can be used.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct DenseSnapshot<T> {
+  tree: DenseTree<T>,
+}
+
+impl<T> Clone for DenseSnapshot<T> {
+  fn clone(&self) -> Self {
+    Self {
+      tree: self.tree.clone(),
+    }
+  }
+}
+
+impl<T> Deref for DenseSnapshot<T> {
+  type Target = DenseTree<T>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.tree
+  }
+}
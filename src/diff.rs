@@ -0,0 +1,84 @@
+//! Compare two node sets, or two snapshots of a tree, and draw what changed.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{render_diff, NodeSet, RenderOptions, TreeDiff};
+//!
+//! let before: NodeSet = vec![0, 2].into_iter().collect();
+//! let after: NodeSet = vec![0, 1, 2].into_iter().collect();
+//! let diff = TreeDiff::new(&before, &after);
+//! assert_eq!(diff.added.as_slice(), [1]);
+//! assert_eq!(
+//!   render_diff(2, &diff, RenderOptions::new()),
+//!   "┌────1*───┐\n0#        2#\n\n* added\n+ removed\n# common\n"
+//! );
+//! ```
+use super::*;
+
+/// The nodes added, removed and kept between two node sets, see
+/// `TreeDiff::new()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct TreeDiff {
+  /// The nodes only in the second set.
+  pub added: NodeSet,
+  /// The nodes only in the first set.
+  pub removed: NodeSet,
+  /// The nodes in both sets.
+  pub common: NodeSet,
+}
+
+impl TreeDiff {
+  /// Compare the nodes in `before` to the nodes in `after`.
+  pub fn new(before: &NodeSet, after: &NodeSet) -> Self {
+    let mut diff = Self::default();
+    for i in before {
+      if after.contains(i) {
+        diff.common.insert(i);
+      } else {
+        diff.removed.insert(i);
+      }
+    }
+    diff.added = after.iter().filter(|&i| !before.contains(i)).collect();
+    diff
+  }
+
+  /// Compare the nodes present in two snapshots of a tree with `tree_len`
+  /// leaves. Only nodes whose leaves are all in the tree are compared.
+  pub fn between<B: Bitfield, C: Bitfield>(
+    before: &TreeIndex<B>,
+    after: &TreeIndex<C>,
+    tree_len: usize,
+  ) -> Self {
+    let last = (2 * tree_len).saturating_sub(2);
+    let nodes = (0..=last).filter(|&i| tree_len > 0 && right_span(i) <= last);
+    let before: NodeSet = nodes.clone().filter(|&i| before.get(i)).collect();
+    let after: NodeSet = nodes.filter(|&i| after.get(i)).collect();
+    Self::new(&before, &after)
+  }
+
+  /// Check if nothing was added or removed.
+  pub fn is_empty(&self) -> bool {
+    self.added.is_empty() && self.removed.is_empty()
+  }
+
+  /// Get the added, removed and common nodes as highlighted sets, in that
+  /// order, to draw the diff with `to_dot()`, `to_mermaid()` or `to_svg()`.
+  pub fn highlights(&self) -> Highlights<'_> {
+    Highlights::new()
+      .set("added", &self.added)
+      .set("removed", &self.removed)
+      .set("common", &self.common)
+  }
+}
+
+/// Draw a tree with `leaves` leaves like `render_unicode()`, with the nodes
+/// of `diff` marked as added, removed or common, see
+/// `TreeDiff::highlights()`. Any highlights in `opts` are replaced.
+pub fn render_diff(
+  leaves: usize,
+  diff: &TreeDiff,
+  opts: RenderOptions,
+) -> String {
+  let highlights = diff.highlights();
+  render_unicode(leaves, opts.highlights(&highlights))
+}
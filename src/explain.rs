@@ -0,0 +1,139 @@
+//! A breakdown of a node and how each of its relatives follows from the
+//! bits of its index.
+//!
+//! ## Usage
+//! ```rust
+//! let explanation = flat_tree::explain(11);
+//! assert_eq!(explanation.parent, 7);
+//! println!("{}", explanation);
+//! ```
+use super::*;
+
+use std::fmt;
+
+/// The position and relatives of a node, see `explain()`. The `Display`
+/// impl shows how each value is derived from the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Explanation {
+  /// The flat-tree index.
+  pub index: usize,
+  /// The depth of the node, the number of trailing one bits of the index.
+  pub depth: usize,
+  /// The offset of the node, the bits of the index above its depth.
+  pub offset: usize,
+  /// The parent of the node.
+  pub parent: usize,
+  /// The sibling of the node.
+  pub sibling: usize,
+  /// The left and right children of the node, or `None` for a leaf.
+  pub children: Option<(usize, usize)>,
+  /// The left and right most leaves the node spans.
+  pub spans: (usize, usize),
+  /// The number of leaves the node spans.
+  pub leaves: usize,
+}
+
+/// Explain node `i`: its depth, offset, parent, sibling, children, spans
+/// and leaf count, with the reasoning behind each when displayed.
+///
+/// ## Panics
+/// If the depth of `i` isn't smaller than `Iterator::MAX_DEPTH`, as its
+/// parent would be too deep.
+///
+/// ## Examples
+/// ```rust
+/// let explanation = flat_tree::explain(11);
+/// assert_eq!(explanation.depth, 2);
+/// assert_eq!(explanation.offset, 1);
+/// assert_eq!(explanation.children, Some((9, 13)));
+/// assert_eq!(
+///   explanation.to_string(),
+///   "node 11 (0b1011)
+/// depth 2: the index ends in 2 one bits
+/// offset 1: the bits above the lowest zero bit, 0b1011 >> 3
+/// parent 7: depth 3, offset 1 / 2 = 0
+/// sibling 3: depth 2, offset 1 ^ 1 = 0
+/// children 9 and 13: depth 1, offsets 2 and 3
+/// spans 8 to 14: leaves 4 to 7
+/// leaves 4: 2^2
+/// "
+/// );
+/// ```
+pub fn explain(i: usize) -> Explanation {
+  let depth = depth(i);
+  assert!(
+    depth < Iterator::MAX_DEPTH,
+    "Node {} at depth {} has no parent within the maximum cursor depth {}",
+    i,
+    depth,
+    Iterator::MAX_DEPTH
+  );
+  Explanation {
+    index: i,
+    depth,
+    offset: offset_with_depth(i, depth),
+    parent: parent_with_depth(i, depth),
+    sibling: sibling_with_depth(i, depth),
+    children: children_with_depth(i, depth),
+    spans: spans_with_depth(i, depth),
+    leaves: 1 << depth,
+  }
+}
+
+impl fmt::Display for Explanation {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let (index, depth, offset) = (self.index, self.depth, self.offset);
+    writeln!(f, "node {} ({:#b})", index, index)?;
+    if depth == 0 {
+      writeln!(f, "depth 0: the index is even")?;
+      writeln!(f, "offset {}: {:#b} >> 1", offset, index)?;
+    } else {
+      writeln!(f, "depth {}: the index ends in {} one bits", depth, depth)?;
+      writeln!(
+        f,
+        "offset {}: the bits above the lowest zero bit, {:#b} >> {}",
+        offset,
+        index,
+        depth + 1
+      )?;
+    }
+    writeln!(
+      f,
+      "parent {}: depth {}, offset {} / 2 = {}",
+      self.parent,
+      depth + 1,
+      offset,
+      offset / 2
+    )?;
+    writeln!(
+      f,
+      "sibling {}: depth {}, offset {} ^ 1 = {}",
+      self.sibling,
+      depth,
+      offset,
+      offset ^ 1
+    )?;
+    match self.children {
+      Some((left, right)) => writeln!(
+        f,
+        "children {} and {}: depth {}, offsets {} and {}",
+        left,
+        right,
+        depth - 1,
+        2 * offset,
+        2 * offset + 1
+      )?,
+      None => writeln!(f, "children: none, the node is a leaf")?,
+    }
+    let (left, right) = self.spans;
+    writeln!(
+      f,
+      "spans {} to {}: leaves {} to {}",
+      left,
+      right,
+      left / 2,
+      right / 2
+    )?;
+    writeln!(f, "leaves {}: 2^{}", self.leaves, depth)
+  }
+}
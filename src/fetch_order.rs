@@ -0,0 +1,103 @@
+//! Orders to visit or download leaves and nodes in.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{FetchOrder, TreeIndex};
+//!
+//! assert_eq!(FetchOrder::Reverse.leaves(3), [4, 2, 0]);
+//!
+//! let mut tree = TreeIndex::new();
+//! tree.set(2);
+//! let missing = tree.missing_leaves().range(0..10);
+//! assert_eq!(FetchOrder::CenterOut.arrange(missing), [6, 4, 8, 0]);
+//! ```
+
+/// Order to visit or download leaves and nodes in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FetchOrder {
+  /// In increasing index order.
+  Linear,
+  /// In decreasing index order.
+  Reverse,
+  /// In a random order, which is the same for the same seed.
+  Random(u64),
+  /// Starting in the middle, then alternating between the closest earlier
+  /// and later one.
+  CenterOut,
+}
+
+impl FetchOrder {
+  /// Returns the leaves of a tree with `leaves` leaves in this order.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::FetchOrder;
+  ///
+  /// assert_eq!(FetchOrder::Linear.leaves(3), [0, 2, 4]);
+  /// assert_eq!(FetchOrder::CenterOut.leaves(5), [4, 2, 6, 0, 8]);
+  /// ```
+  pub fn leaves(self, leaves: usize) -> Vec<usize> {
+    self.arrange((0..leaves).map(|leaf| 2 * leaf))
+  }
+
+  /// Returns `nodes` in this order, such as the nodes of a `NodeSet` or
+  /// `TreeIndex::missing_leaves()`. The order is based on the position of a
+  /// node in the sorted `nodes`.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::FetchOrder;
+  ///
+  /// let nodes = FetchOrder::Random(7).arrange(vec![1, 4, 8, 11]);
+  /// assert_eq!(nodes.len(), 4);
+  /// assert_eq!(nodes, FetchOrder::Random(7).arrange(vec![11, 8, 4, 1]));
+  /// ```
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(nodes))
+  )]
+  pub fn arrange<I: IntoIterator<Item = usize>>(self, nodes: I) -> Vec<usize> {
+    let mut nodes: Vec<usize> = nodes.into_iter().collect();
+    #[cfg(feature = "tracing")]
+    tracing::debug!(nodes = nodes.len(), "arranging nodes");
+    nodes.sort_unstable();
+    match self {
+      FetchOrder::Linear => nodes,
+      FetchOrder::Reverse => {
+        nodes.reverse();
+        nodes
+      }
+      FetchOrder::Random(seed) => {
+        shuffle(&mut nodes, seed);
+        nodes
+      }
+      FetchOrder::CenterOut => {
+        let mid = nodes.len() / 2;
+        let mut ordered = Vec::with_capacity(nodes.len());
+        for step in 0..nodes.len() {
+          let i = if step % 2 == 0 {
+            mid + step / 2
+          } else {
+            mid - step.div_ceil(2)
+          };
+          ordered.push(nodes[i]);
+        }
+        ordered
+      }
+    }
+  }
+}
+
+/// Fisher-Yates shuffle, using splitmix64 as a small deterministic random
+/// number generator.
+fn shuffle(nodes: &mut [usize], seed: u64) {
+  let mut state = seed;
+  for i in (1..nodes.len()).rev() {
+    state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^= z >> 31;
+    nodes.swap(i, (z % (i as u64 + 1)) as usize);
+  }
+}
@@ -0,0 +1,359 @@
+//! C API, behind the `ffi` feature.
+//!
+//! Every function is prefixed with `flat_tree_`. Cursors and tree indices
+//! are opaque handles, created by a `_new` function and released by the
+//! matching `_free` function. Functions that return several nodes write
+//! them to a caller provided buffer, and return how many there are, so a
+//! caller can retry with a larger buffer. The header can be generated with
+//! cbindgen.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::ffi::*;
+//!
+//! assert_eq!(flat_tree_parent(0), 1);
+//! let cursor = flat_tree_cursor_new(0);
+//! unsafe {
+//!   assert!(flat_tree_cursor_parent(cursor));
+//!   assert_eq!(flat_tree_cursor_index(cursor), 1);
+//!   flat_tree_cursor_free(cursor);
+//! }
+//! ```
+use super::*;
+
+use std::ptr;
+
+/// A cursor over a flat-tree, see `Iterator`.
+#[derive(Debug)]
+pub struct FlatTreeCursor {
+  iter: Iterator,
+}
+
+/// The presence of the nodes of a flat-tree, see `TreeIndex`.
+#[derive(Debug)]
+pub struct FlatTreeTreeIndex {
+  tree: TreeIndex,
+}
+
+/// See `index()`.
+#[no_mangle]
+pub extern "C" fn flat_tree_index(depth: usize, offset: usize) -> usize {
+  index(depth, offset)
+}
+
+/// See `depth()`.
+#[no_mangle]
+pub extern "C" fn flat_tree_depth(i: usize) -> usize {
+  depth(i)
+}
+
+/// See `offset()`.
+#[no_mangle]
+pub extern "C" fn flat_tree_offset(i: usize) -> usize {
+  offset(i)
+}
+
+/// See `parent()`.
+#[no_mangle]
+pub extern "C" fn flat_tree_parent(i: usize) -> usize {
+  parent(i)
+}
+
+/// See `sibling()`.
+#[no_mangle]
+pub extern "C" fn flat_tree_sibling(i: usize) -> usize {
+  sibling(i)
+}
+
+/// See `left_span()`.
+#[no_mangle]
+pub extern "C" fn flat_tree_left_span(i: usize) -> usize {
+  left_span(i)
+}
+
+/// See `right_span()`.
+#[no_mangle]
+pub extern "C" fn flat_tree_right_span(i: usize) -> usize {
+  right_span(i)
+}
+
+/// See `count()`.
+#[no_mangle]
+pub extern "C" fn flat_tree_count(i: usize) -> usize {
+  count(i)
+}
+
+/// See `children()`. Writes the children to `left` and `right`, and
+/// returns `false` without writing anything if `i` is a leaf.
+///
+/// ## Safety
+/// `left` and `right` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn flat_tree_children(
+  i: usize,
+  left: *mut usize,
+  right: *mut usize,
+) -> bool {
+  match children(i) {
+    Some((l, r)) => {
+      *left = l;
+      *right = r;
+      true
+    }
+    None => false,
+  }
+}
+
+/// See `full_roots()`. Writes up to `len` roots to `out`, and returns the
+/// number of roots, or `usize::MAX` if `i` isn't a leaf.
+///
+/// ## Safety
+/// `out` must be valid for writes of `len` nodes, or null if `len` is `0`.
+#[no_mangle]
+pub unsafe extern "C" fn flat_tree_full_roots(
+  i: usize,
+  out: *mut usize,
+  len: usize,
+) -> usize {
+  if !is_even(i) {
+    return usize::MAX;
+  }
+  let mut roots = Vec::new();
+  full_roots(i, &mut roots);
+  write_nodes(&roots, out, len)
+}
+
+/// Create a cursor at node `i`. Free it with `flat_tree_cursor_free()`.
+#[no_mangle]
+pub extern "C" fn flat_tree_cursor_new(i: usize) -> *mut FlatTreeCursor {
+  Box::into_raw(Box::new(FlatTreeCursor {
+    iter: Iterator::new(i),
+  }))
+}
+
+/// Free a cursor. Does nothing if `cursor` is null.
+///
+/// ## Safety
+/// `cursor` must come from `flat_tree_cursor_new()`, and not be used after.
+#[no_mangle]
+pub unsafe extern "C" fn flat_tree_cursor_free(cursor: *mut FlatTreeCursor) {
+  if !cursor.is_null() {
+    drop(Box::from_raw(cursor));
+  }
+}
+
+/// Get the node the cursor is at.
+///
+/// ## Safety
+/// `cursor` must be a live cursor.
+#[no_mangle]
+pub unsafe extern "C" fn flat_tree_cursor_index(
+  cursor: *const FlatTreeCursor,
+) -> usize {
+  (*cursor).iter.index()
+}
+
+/// Get the depth of the node the cursor is at.
+///
+/// ## Safety
+/// `cursor` must be a live cursor.
+#[no_mangle]
+pub unsafe extern "C" fn flat_tree_cursor_depth(
+  cursor: *const FlatTreeCursor,
+) -> usize {
+  (*cursor).iter.depth()
+}
+
+/// Get the offset of the node the cursor is at.
+///
+/// ## Safety
+/// `cursor` must be a live cursor.
+#[no_mangle]
+pub unsafe extern "C" fn flat_tree_cursor_offset(
+  cursor: *const FlatTreeCursor,
+) -> usize {
+  (*cursor).iter.offset()
+}
+
+/// Move the cursor to node `i`, see `Iterator::seek()`.
+///
+/// ## Safety
+/// `cursor` must be a live cursor.
+#[no_mangle]
+pub unsafe extern "C" fn flat_tree_cursor_seek(
+  cursor: *mut FlatTreeCursor,
+  i: usize,
+) {
+  (*cursor).iter.seek(i);
+}
+
+/// Define a function that moves a cursor, and returns `false` if it
+/// couldn't move.
+macro_rules! cursor_step {
+  ($(#[$doc:meta] $name:ident => $step:expr;)*) => {
+    $(
+      #[$doc]
+      ///
+      /// ## Safety
+      /// `cursor` must be a live cursor.
+      #[no_mangle]
+      pub unsafe extern "C" fn $name(cursor: *mut FlatTreeCursor) -> bool {
+        let step: fn(&mut Iterator) -> Option<usize> = $step;
+        step(&mut (*cursor).iter).is_some()
+      }
+    )*
+  };
+}
+
+cursor_step! {
+  /// Move the cursor to the next node at the same depth.
+  flat_tree_cursor_next => |iter| iter.next();
+  /// Move the cursor to the previous node at the same depth.
+  flat_tree_cursor_prev => |iter| iter.try_prev();
+  /// Move the cursor to its sibling.
+  flat_tree_cursor_sibling => |iter| Some(iter.sibling());
+  /// Move the cursor to its parent.
+  flat_tree_cursor_parent => |iter| iter.try_parent();
+  /// Move the cursor to its left child.
+  flat_tree_cursor_left_child => |iter| iter.try_left_child();
+  /// Move the cursor to its right child.
+  flat_tree_cursor_right_child => |iter| iter.try_right_child();
+  /// Move the cursor to the left most leaf it spans.
+  flat_tree_cursor_left_span => |iter| Some(iter.left_span());
+  /// Move the cursor to the right most leaf it spans.
+  flat_tree_cursor_right_span => |iter| Some(iter.right_span());
+}
+
+/// Create an empty tree index. Free it with `flat_tree_tree_index_free()`.
+#[no_mangle]
+pub extern "C" fn flat_tree_tree_index_new() -> *mut FlatTreeTreeIndex {
+  Box::into_raw(Box::new(FlatTreeTreeIndex {
+    tree: TreeIndex::new(),
+  }))
+}
+
+/// Free a tree index. Does nothing if `tree` is null.
+///
+/// ## Safety
+/// `tree` must come from `flat_tree_tree_index_new()`, and not be used
+/// after.
+#[no_mangle]
+pub unsafe extern "C" fn flat_tree_tree_index_free(
+  tree: *mut FlatTreeTreeIndex,
+) {
+  if !tree.is_null() {
+    drop(Box::from_raw(tree));
+  }
+}
+
+/// See `TreeIndex::get()`.
+///
+/// ## Safety
+/// `tree` must be a live tree index.
+#[no_mangle]
+pub unsafe extern "C" fn flat_tree_tree_index_get(
+  tree: *const FlatTreeTreeIndex,
+  i: usize,
+) -> bool {
+  (*tree).tree.get(i)
+}
+
+/// See `TreeIndex::set()`.
+///
+/// ## Safety
+/// `tree` must be a live tree index.
+#[no_mangle]
+pub unsafe extern "C" fn flat_tree_tree_index_set(
+  tree: *mut FlatTreeTreeIndex,
+  i: usize,
+) -> bool {
+  (*tree).tree.set(i)
+}
+
+/// See `TreeIndex::digest()`.
+///
+/// ## Safety
+/// `tree` must be a live tree index.
+#[no_mangle]
+pub unsafe extern "C" fn flat_tree_tree_index_digest(
+  tree: *const FlatTreeTreeIndex,
+  i: usize,
+) -> usize {
+  (*tree).tree.digest(i)
+}
+
+/// See `TreeIndex::blocks()`.
+///
+/// ## Safety
+/// `tree` must be a live tree index.
+#[no_mangle]
+pub unsafe extern "C" fn flat_tree_tree_index_blocks(
+  tree: *const FlatTreeTreeIndex,
+) -> usize {
+  (*tree).tree.blocks()
+}
+
+/// See `TreeIndex::verified_by()`.
+///
+/// ## Safety
+/// `tree` must be a live tree index.
+#[no_mangle]
+pub unsafe extern "C" fn flat_tree_tree_index_verified_by(
+  tree: *const FlatTreeTreeIndex,
+  i: usize,
+) -> usize {
+  (*tree).tree.verified_by(i)
+}
+
+/// See `TreeIndex::roots()`. Writes up to `len` roots to `out`, and returns
+/// the number of roots.
+///
+/// ## Safety
+/// `tree` must be a live tree index, and `out` valid for writes of `len`
+/// nodes, or null if `len` is `0`.
+#[no_mangle]
+pub unsafe extern "C" fn flat_tree_tree_index_roots(
+  tree: *const FlatTreeTreeIndex,
+  out: *mut usize,
+  len: usize,
+) -> usize {
+  write_nodes(&(*tree).tree.roots(), out, len)
+}
+
+/// See `TreeIndex::proof()`, with the `digest` of the remote peer, and
+/// including node `i` itself if `hash` is set. Writes up to `len` nodes of
+/// the proof to `out` and its `verified_by` to `verified_by`, and returns
+/// the number of nodes, or `usize::MAX` if `i` isn't present.
+///
+/// ## Safety
+/// `tree` must be a live tree index, `out` valid for writes of `len` nodes,
+/// or null if `len` is `0`, and `verified_by` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn flat_tree_tree_index_proof(
+  tree: *const FlatTreeTreeIndex,
+  i: usize,
+  digest: usize,
+  hash: bool,
+  out: *mut usize,
+  len: usize,
+  verified_by: *mut usize,
+) -> usize {
+  let opts = ProofOptions::new().digest(digest).hash(hash);
+  match (*tree).tree.proof(i, opts) {
+    Some(proof) => {
+      *verified_by = proof.verified_by;
+      write_nodes(&proof.nodes, out, len)
+    }
+    None => usize::MAX,
+  }
+}
+
+/// Write up to `len` of `nodes` to `out`, returning how many nodes there
+/// are.
+unsafe fn write_nodes(nodes: &[usize], out: *mut usize, len: usize) -> usize {
+  let written = nodes.len().min(len);
+  if written > 0 {
+    ptr::copy_nonoverlapping(nodes.as_ptr(), out, written);
+  }
+  nodes.len()
+}
@@ -0,0 +1,189 @@
+//! Track the roots of a tree that grows one leaf at a time.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::Frontier;
+//!
+//! let mut frontier = Frontier::new();
+//! for _ in 0..3 {
+//!   frontier.push();
+//! }
+//! assert_eq!(frontier.roots(), [1, 4]);
+//! assert_eq!(frontier.push(), 6);
+//! assert_eq!(frontier.roots(), [3]);
+//! ```
+use super::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::cmp;
+
+/// Returns an iterator over the parents that are completed by appending
+/// `leaf`, from the bottom up. These are the ancestors of `leaf` for which
+/// it's the last leaf spanned.
+///
+/// ## Panics
+/// If `leaf` isn't a leaf.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::completed_parents;
+///
+/// assert_eq!(completed_parents(0).count(), 0);
+/// assert_eq!(completed_parents(2).collect::<Vec<_>>(), [1]);
+/// assert_eq!(completed_parents(6).collect::<Vec<_>>(), [5, 3]);
+/// assert_eq!(completed_parents(8).count(), 0);
+/// ```
+pub fn completed_parents(leaf: usize) -> Ancestors {
+  assert!(
+    is_even(leaf),
+    "Only leaves complete parents, got index {}",
+    leaf
+  );
+  ancestors(leaf).to_depth((leaf / 2).trailing_ones() as usize)
+}
+
+/// How two trees differ, given their frontiers. See `Frontier::diff()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FrontierDiff {
+  /// The number of leaves both trees have.
+  pub shared_leaves: usize,
+  /// The roots of the leaves both trees have. Both trees can compute these
+  /// nodes, and they agree on every shared leaf if these nodes match.
+  pub shared: NodeSet,
+  /// The roots of the subtrees covering the leaves only the local tree has.
+  pub local: NodeSet,
+  /// The roots of the subtrees covering the leaves only the remote tree
+  /// has.
+  pub remote: NodeSet,
+}
+
+/// The full roots of a tree that grows one leaf at a time, see
+/// `full_roots()`.
+///
+/// With the `serde` feature the frontier is serialized as its number of
+/// `leaves`, as the roots follow from it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(from = "State", into = "State")
+)]
+pub struct Frontier {
+  roots: Vec<usize>,
+  leaves: usize,
+}
+
+impl Frontier {
+  /// Create a frontier for an empty tree.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Create a frontier for a tree that already has `leaves` leaves.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let frontier = flat_tree::Frontier::with_leaves(7);
+  /// assert_eq!(frontier.roots(), [3, 9, 12]);
+  /// ```
+  pub fn with_leaves(leaves: usize) -> Self {
+    let mut roots = Vec::new();
+    full_roots(2 * leaves, &mut roots);
+    Self { roots, leaves }
+  }
+
+  /// Get the number of leaves in the tree.
+  #[inline]
+  pub fn leaves(&self) -> usize {
+    self.leaves
+  }
+
+  /// Check if the tree has no leaves.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.leaves == 0
+  }
+
+  /// Get the full roots of the tree, from left to right.
+  #[inline]
+  pub fn roots(&self) -> &[usize] {
+    &self.roots
+  }
+
+  /// Append a leaf, merging the roots it completes. Returns the index of
+  /// the new leaf. The parents it completes are `completed_parents()` of
+  /// that index.
+  pub fn push(&mut self) -> usize {
+    let leaf = 2 * self.leaves;
+    let parents = completed_parents(leaf);
+    let merged = self.roots.len() - parents.len();
+    self.roots.truncate(merged);
+    self.roots.push(parents.last().unwrap_or(leaf));
+    self.leaves += 1;
+    leaf
+  }
+
+  /// Compare this tree with a `remote` one, such as a peer's copy of the
+  /// same tree at another length, or a fork of it.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::Frontier;
+  ///
+  /// let diff = Frontier::with_leaves(3).diff(&Frontier::with_leaves(8));
+  /// assert_eq!(diff.shared_leaves, 3);
+  /// assert_eq!(diff.shared.as_slice(), [1, 4]);
+  /// assert!(diff.local.is_empty());
+  /// assert_eq!(diff.remote.as_slice(), [6, 11]);
+  /// ```
+  pub fn diff(&self, remote: &Frontier) -> FrontierDiff {
+    let shared_leaves = cmp::min(self.leaves, remote.leaves);
+    let mut shared = Vec::new();
+    full_roots(2 * shared_leaves, &mut shared);
+    FrontierDiff {
+      shared_leaves,
+      shared: shared.into_iter().collect(),
+      local: cover(shared_leaves, self.leaves),
+      remote: cover(shared_leaves, remote.leaves),
+    }
+  }
+}
+
+/// Serialized form of `Frontier`.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct State {
+  leaves: usize,
+}
+
+#[cfg(feature = "serde")]
+impl From<Frontier> for State {
+  fn from(frontier: Frontier) -> Self {
+    Self {
+      leaves: frontier.leaves,
+    }
+  }
+}
+
+#[cfg(feature = "serde")]
+impl From<State> for Frontier {
+  fn from(state: State) -> Self {
+    Frontier::with_leaves(state.leaves)
+  }
+}
+
+/// Returns the largest subtrees covering leaves `start` up to, but not
+/// including, `end`.
+pub(crate) fn cover(mut start: usize, end: usize) -> NodeSet {
+  let mut nodes = NodeSet::new();
+  while start < end {
+    let mut depth = start.trailing_zeros().min(Iterator::MAX_DEPTH as u32);
+    while 1 << depth > end - start {
+      depth -= 1;
+    }
+    nodes.insert(index(depth as usize, start >> depth));
+    start += 1 << depth;
+  }
+  nodes
+}
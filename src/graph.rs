@@ -0,0 +1,223 @@
+//! Export a tree, or part of it, as a graph description for other tools.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{to_dot, GraphOptions};
+//!
+//! let dot = to_dot(2, GraphOptions::new());
+//! assert!(dot.contains("n1 -> n0;"));
+//! assert!(dot.contains("n1 -> n2;"));
+//! ```
+use super::*;
+
+use highlight::color;
+
+use std::fmt::Write;
+use std::iter;
+
+/// Options for `to_dot()` and `to_mermaid()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GraphOptions<'a> {
+  subtree: Option<usize>,
+  nodes: Option<&'a NodeSet>,
+  highlights: Option<&'a Highlights<'a>>,
+}
+
+impl<'a> GraphOptions<'a> {
+  /// Create the default options: every node of the tree.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Only include `root` and the nodes below it.
+  pub fn subtree(mut self, root: usize) -> Self {
+    self.subtree = Some(root);
+    self
+  }
+
+  /// Only include the nodes in `nodes`.
+  pub fn nodes(mut self, nodes: &'a NodeSet) -> Self {
+    self.nodes = Some(nodes);
+    self
+  }
+
+  /// Fill the nodes of each highlighted set with the set's color, and add
+  /// the names of the sets containing a node, and its note, to its label.
+  /// A node in several sets is filled with the color of the first.
+  pub fn highlights(mut self, highlights: &'a Highlights<'a>) -> Self {
+    self.highlights = Some(highlights);
+    self
+  }
+
+  /// Returns the positions of the highlighted sets containing `i`, and the
+  /// extra lines of its label.
+  fn highlight(&self, i: usize) -> (Vec<usize>, Vec<String>) {
+    let highlights = match self.highlights {
+      Some(highlights) => highlights,
+      None => return (Vec::new(), Vec::new()),
+    };
+    let sets = highlights.containing(i);
+    let mut lines = Vec::new();
+    if !sets.is_empty() {
+      let labels: Vec<&str> = highlights.labels().collect();
+      let names: Vec<&str> = sets.iter().map(|&pos| labels[pos]).collect();
+      lines.push(names.join(", "));
+    }
+    lines.extend(highlights.note(i));
+    (sets, lines)
+  }
+
+  /// Check if node `i` of a tree with `tree_len` leaves is included. A node
+  /// is in the tree if every leaf it spans is.
+  fn includes(&self, i: usize, tree_len: usize) -> bool {
+    tree_len > 0
+      && right_span(i) <= 2 * tree_len - 2
+      && self
+        .subtree
+        .is_none_or(|root| Iterator::new(root).contains(i))
+      && self.nodes.is_none_or(|nodes| nodes.contains(i))
+  }
+
+  /// Returns the included nodes, in index order.
+  fn included(&self, tree_len: usize) -> Vec<usize> {
+    let candidates: Box<dyn iter::Iterator<Item = usize>> =
+      match (self.nodes, self.subtree) {
+        (Some(nodes), _) => Box::new(nodes.iter()),
+        (None, Some(root)) => Box::new(left_span(root)..=right_span(root)),
+        (None, None) if tree_len == 0 => Box::new(iter::empty()),
+        (None, None) => Box::new(0..=2 * tree_len - 2),
+      };
+    candidates.filter(|&i| self.includes(i, tree_len)).collect()
+  }
+
+  /// Returns the edges from each included parent to its included children,
+  /// left child first.
+  fn edges(&self, nodes: &[usize], tree_len: usize) -> Vec<(usize, usize)> {
+    let mut edges = Vec::new();
+    for &i in nodes {
+      if let Some((left, right)) = children(i) {
+        for child in [left, right] {
+          if self.includes(child, tree_len) {
+            edges.push((i, child));
+          }
+        }
+      }
+    }
+    edges
+  }
+}
+
+/// Describe a tree with `tree_len` leaves as a Graphviz DOT graph. Each
+/// node is labeled with its index, depth and offset, and has an edge to
+/// each of its children.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::{to_dot, GraphOptions};
+///
+/// assert_eq!(
+///   to_dot(4, GraphOptions::new().subtree(5)),
+///   r#"digraph flat_tree {
+///   graph [ordering=out];
+///   node [shape=box];
+///   n4 [label="4\ndepth 0, offset 2"];
+///   n5 [label="5\ndepth 1, offset 1"];
+///   n6 [label="6\ndepth 0, offset 3"];
+///   n5 -> n4;
+///   n5 -> n6;
+/// }
+/// "#
+/// );
+/// ```
+pub fn to_dot(tree_len: usize, opts: GraphOptions) -> String {
+  let nodes = opts.included(tree_len);
+  let mut out = String::new();
+  out.push_str("digraph flat_tree {\n");
+  out.push_str("  graph [ordering=out];\n");
+  out.push_str("  node [shape=box];\n");
+  for &i in &nodes {
+    let (sets, lines) = opts.highlight(i);
+    write!(
+      out,
+      "  n{} [label=\"{}\\ndepth {}, offset {}",
+      i,
+      i,
+      depth(i),
+      offset(i)
+    )
+    .unwrap();
+    for line in lines {
+      write!(
+        out,
+        "\\n{}",
+        line.replace('\\', "\\\\").replace('"', "\\\"")
+      )
+      .unwrap();
+    }
+    out.push('"');
+    if let Some(&pos) = sets.first() {
+      write!(out, " style=filled fillcolor=\"{}\"", color(pos)).unwrap();
+    }
+    out.push_str("];\n");
+  }
+  for (parent, child) in opts.edges(&nodes, tree_len) {
+    writeln!(out, "  n{} -> n{};", parent, child).unwrap();
+  }
+  out.push_str("}\n");
+  out
+}
+
+/// Describe a tree with `tree_len` leaves as a Mermaid flowchart, with the
+/// same nodes and edges as `to_dot()`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::{to_mermaid, GraphOptions};
+///
+/// assert_eq!(
+///   to_mermaid(2, GraphOptions::new()),
+///   r#"flowchart TD
+///   n0["0<br/>depth 0, offset 0"]
+///   n1["1<br/>depth 1, offset 0"]
+///   n2["2<br/>depth 0, offset 1"]
+///   n1 --> n0
+///   n1 --> n2
+/// "#
+/// );
+/// ```
+pub fn to_mermaid(tree_len: usize, opts: GraphOptions) -> String {
+  let nodes = opts.included(tree_len);
+  let mut out = String::new();
+  out.push_str("flowchart TD\n");
+  let mut classes = Vec::new();
+  for &i in &nodes {
+    let (sets, lines) = opts.highlight(i);
+    write!(
+      out,
+      "  n{}[\"{}<br/>depth {}, offset {}",
+      i,
+      i,
+      depth(i),
+      offset(i)
+    )
+    .unwrap();
+    for line in lines {
+      write!(out, "<br/>{}", line.replace('"', "#quot;")).unwrap();
+    }
+    out.push_str("\"]\n");
+    if let Some(&pos) = sets.first() {
+      classes.push((i, pos));
+    }
+  }
+  for (parent, child) in opts.edges(&nodes, tree_len) {
+    writeln!(out, "  n{} --> n{}", parent, child).unwrap();
+  }
+  let sets = opts.highlights.map_or(0, |highlights| highlights.len());
+  for pos in 0..sets {
+    writeln!(out, "  classDef s{} fill:{}", pos, color(pos)).unwrap();
+  }
+  for (i, pos) in classes {
+    writeln!(out, "  class n{} s{}", i, pos).unwrap();
+  }
+  out
+}
@@ -0,0 +1,105 @@
+//! Merkle hashes over flat-tree nodes, using any `digest::Digest`.
+//!
+//! Leaves and parents are hashed with a different prefix byte, so a leaf
+//! can't be mistaken for a parent:
+//!
+//! - A leaf is hashed as `0x00 || data`.
+//! - A parent is hashed as `0x01 || left || right`, where `left` and `right`
+//!   are the hashes of its left and right child.
+//!
+//! ## Usage
+//! ```rust
+//! extern crate sha2;
+//! # extern crate flat_tree;
+//!
+//! use flat_tree::{merkle_root, node_hash, parent_hash};
+//! use sha2::Sha256;
+//!
+//! let leaves = [b"a", b"b"];
+//! let root = merkle_root::<Sha256, _>(&leaves).unwrap();
+//! let left = node_hash::<Sha256, _>(0, &leaves);
+//! let right = node_hash::<Sha256, _>(2, &leaves);
+//! assert_eq!(root, parent_hash::<Sha256>(&left, &right));
+//! assert_eq!(root, node_hash::<Sha256, _>(1, &leaves));
+//! ```
+use super::*;
+
+use digest::{Digest, Output};
+
+const LEAF_PREFIX: u8 = 0x00;
+const PARENT_PREFIX: u8 = 0x01;
+
+/// Returns the hash of a leaf with `data`.
+pub fn leaf_hash<D: Digest>(data: &[u8]) -> Output<D> {
+  D::new()
+    .chain_update([LEAF_PREFIX])
+    .chain_update(data)
+    .finalize()
+}
+
+/// Returns the hash of a parent whose children have hashes `left` and
+/// `right`.
+pub fn parent_hash<D: Digest>(left: &[u8], right: &[u8]) -> Output<D> {
+  D::new()
+    .chain_update([PARENT_PREFIX])
+    .chain_update(left)
+    .chain_update(right)
+    .finalize()
+}
+
+/// Returns the hash of node `index`, where the data of the `n`th leaf is
+/// `leaves[n]`.
+///
+/// ## Panics
+/// If the node spans leaves past the end of `leaves`.
+pub fn node_hash<D: Digest, L: AsRef<[u8]>>(
+  index: usize,
+  leaves: &[L],
+) -> Output<D> {
+  assert!(
+    in_tree(index, leaves.len()),
+    "Node {} isn't in a tree with {} leaves",
+    index,
+    leaves.len()
+  );
+  fold_subtree(
+    index,
+    |leaf| leaf_hash::<D>(leaves[leaf / 2].as_ref()),
+    |_, left, right| parent_hash::<D>(&left, &right),
+  )
+}
+
+/// Returns the merkle root of a tree with `leaves` as the data of its
+/// leaves, or `None` if there are no leaves.
+///
+/// When the number of leaves isn't a power of two the tree is a forest, see
+/// `full_roots()`. Its roots are then combined from right to left, like
+/// parents, which gives the same root as RFC 6962.
+///
+/// ## Examples
+/// ```rust
+/// extern crate sha2;
+/// # extern crate flat_tree;
+///
+/// use flat_tree::{merkle_root, node_hash, parent_hash};
+/// use sha2::Sha256;
+///
+/// let leaves = [b"a", b"b", b"c"];
+/// let root = merkle_root::<Sha256, _>(&leaves).unwrap();
+/// let left = node_hash::<Sha256, _>(1, &leaves);
+/// let right = node_hash::<Sha256, _>(4, &leaves);
+/// assert_eq!(root, parent_hash::<Sha256>(&left, &right));
+///
+/// assert_eq!(merkle_root::<Sha256, &[u8]>(&[]), None);
+/// ```
+pub fn merkle_root<D: Digest, L: AsRef<[u8]>>(
+  leaves: &[L],
+) -> Option<Output<D>> {
+  let mut roots = Vec::new();
+  full_roots(2 * leaves.len(), &mut roots);
+  roots
+    .into_iter()
+    .rev()
+    .map(|root| node_hash::<D, L>(root, leaves))
+    .reduce(|right, left| parent_hash::<D>(&left, &right))
+}
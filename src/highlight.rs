@@ -0,0 +1,111 @@
+//! Labeled node sets and notes to show on a drawn tree.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{render_unicode, Highlights, NodeSet, Orientation};
+//! use flat_tree::RenderOptions;
+//!
+//! let have: NodeSet = vec![0, 1].into_iter().collect();
+//! let proof: NodeSet = vec![5].into_iter().collect();
+//! let highlights = Highlights::new()
+//!   .set("have", &have)
+//!   .set("proof", &proof)
+//!   .annotate(|i| if i == 3 { Some("root".to_string()) } else { None });
+//!
+//! let opts = RenderOptions::new()
+//!   .orientation(Orientation::LeftRight)
+//!   .highlights(&highlights);
+//! assert_eq!(
+//!   render_unicode(4, opts),
+//!   "3  root\n├── 1*\n│   ├── 0*\n│   └── 2\n└── 5+\n    ├── 4\n    └── 6\n\
+//!    \n* have\n+ proof\n"
+//! );
+//! ```
+use super::*;
+
+use std::fmt;
+use std::iter;
+
+/// The markers of the sets in text, in order.
+const MARKERS: [char; 8] = ['*', '+', '#', '@', '%', '&', '~', '^'];
+
+/// The colors of the sets in images, in order.
+const COLORS: [&str; 8] = [
+  "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2",
+  "#17becf",
+];
+
+/// Labeled node sets, such as the nodes a peer has, wants, or needs for a
+/// proof, and a note for each node, to show on a drawn tree.
+///
+/// Each set gets its own style: a marker after the index in text, see
+/// `render_unicode()`, or a color in `to_dot()`, `to_mermaid()` and
+/// `to_svg()`. The styles repeat after eight sets.
+#[derive(Default)]
+pub struct Highlights<'a> {
+  sets: Vec<(String, &'a NodeSet)>,
+  annotate: Option<Box<dyn Fn(usize) -> Option<String> + 'a>>,
+}
+
+impl<'a> Highlights<'a> {
+  /// Create highlights with no sets and no notes.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Add a set of nodes, shown with the next style.
+  pub fn set(mut self, label: &str, nodes: &'a NodeSet) -> Self {
+    self.sets.push((label.to_string(), nodes));
+    self
+  }
+
+  /// Add a note to the nodes `annotate` returns one for.
+  pub fn annotate<F>(mut self, annotate: F) -> Self
+  where
+    F: Fn(usize) -> Option<String> + 'a,
+  {
+    self.annotate = Some(Box::new(annotate));
+    self
+  }
+
+  /// Get the labels of the sets, in the order they were added.
+  pub fn labels(&self) -> impl iter::Iterator<Item = &str> {
+    self.sets.iter().map(|(label, _)| label.as_str())
+  }
+
+  /// Get the number of sets.
+  pub(crate) fn len(&self) -> usize {
+    self.sets.len()
+  }
+
+  /// Returns the positions of the sets containing `i`.
+  pub(crate) fn containing(&self, i: usize) -> Vec<usize> {
+    (0..self.sets.len())
+      .filter(|&pos| self.sets[pos].1.contains(i))
+      .collect()
+  }
+
+  /// Get the note for node `i`.
+  pub(crate) fn note(&self, i: usize) -> Option<String> {
+    self.annotate.as_ref().and_then(|annotate| annotate(i))
+  }
+}
+
+impl<'a> fmt::Debug for Highlights<'a> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Highlights")
+      .field("sets", &self.sets)
+      .field("annotate", &self.annotate.is_some())
+      .finish()
+  }
+}
+
+/// The marker of the set at `pos`.
+pub(crate) fn marker(pos: usize) -> char {
+  MARKERS[pos % MARKERS.len()]
+}
+
+/// The color of the set at `pos`.
+pub(crate) fn color(pos: usize) -> &'static str {
+  COLORS[pos % COLORS.len()]
+}
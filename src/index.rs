@@ -0,0 +1,105 @@
+//! Integer types that can be used as flat-tree indices.
+use std::convert;
+use std::fmt;
+use std::hash;
+use std::ops;
+
+/// An unsigned integer type that can be used as a flat-tree index.
+///
+/// Implemented for `u32`, `u64`, `u128` and `usize`, so for example 64-bit
+/// trees can be traversed on 32-bit targets.
+pub trait FlatTreeIndex:
+  Copy
+  + Ord
+  + hash::Hash
+  + fmt::Debug
+  + fmt::Display
+  + Default
+  + convert::TryInto<usize>
+  + ops::Add<Output = Self>
+  + ops::Sub<Output = Self>
+  + ops::Mul<Output = Self>
+  + ops::Div<Output = Self>
+  + ops::AddAssign
+  + ops::SubAssign
+  + ops::MulAssign
+  + ops::DivAssign
+{
+  /// The value `0`.
+  const ZERO: Self;
+  /// The value `1`.
+  const ONE: Self;
+  /// The value `2`.
+  const TWO: Self;
+  /// The size of the type in bits.
+  const BITS: u32;
+
+  /// Returns `2` to the power of `exp`.
+  fn pow2(exp: u32) -> Self;
+  /// Checked integer addition.
+  fn checked_add(self, rhs: Self) -> Option<Self>;
+  /// Checked integer multiplication.
+  fn checked_mul(self, rhs: Self) -> Option<Self>;
+  /// Saturating integer addition.
+  fn saturating_add(self, rhs: Self) -> Self;
+  /// Returns the number of trailing zeros in the binary representation.
+  fn trailing_zeros(self) -> u32;
+  /// Returns the number of trailing ones in the binary representation.
+  fn trailing_ones(self) -> u32;
+  /// Returns the number of leading zeros in the binary representation.
+  fn leading_zeros(self) -> u32;
+
+  /// Check if the value is odd.
+  #[inline]
+  fn is_odd(self) -> bool {
+    self.trailing_ones() > 0
+  }
+}
+
+macro_rules! impl_flat_tree_index {
+  ($($ty:ty),*) => {$(
+    impl FlatTreeIndex for $ty {
+      const ZERO: Self = 0;
+      const ONE: Self = 1;
+      const TWO: Self = 2;
+      const BITS: u32 = <$ty>::BITS;
+
+      #[inline]
+      fn pow2(exp: u32) -> Self {
+        1 << exp
+      }
+
+      #[inline]
+      fn checked_add(self, rhs: Self) -> Option<Self> {
+        <$ty>::checked_add(self, rhs)
+      }
+
+      #[inline]
+      fn checked_mul(self, rhs: Self) -> Option<Self> {
+        <$ty>::checked_mul(self, rhs)
+      }
+
+      #[inline]
+      fn saturating_add(self, rhs: Self) -> Self {
+        <$ty>::saturating_add(self, rhs)
+      }
+
+      #[inline]
+      fn trailing_zeros(self) -> u32 {
+        <$ty>::trailing_zeros(self)
+      }
+
+      #[inline]
+      fn trailing_ones(self) -> u32 {
+        <$ty>::trailing_ones(self)
+      }
+
+      #[inline]
+      fn leading_zeros(self) -> u32 {
+        <$ty>::leading_zeros(self)
+      }
+    }
+  )*};
+}
+
+impl_flat_tree_index!(u32, u64, u128, usize);
@@ -6,158 +6,922 @@
 //! assert_eq!(iter.next(), Some(6));
 //! assert_eq!(iter.parent(), 5);
 //! ```
+//!
+//! The cursor can use any `FlatTreeIndex` as its index type, for example to
+//! traverse 64-bit trees on 32-bit targets:
+//! ```rust
+//! let mut iter = flat_tree::GenericIterator::new(u64::MAX - 1);
+//! assert_eq!(iter.parent(), u64::MAX - 2);
+//! assert_eq!(iter.next(), None);
+//! ```
 use super::*;
 
+#[cfg(feature = "arbitrary")]
+use arbitrary::{Arbitrary, Unstructured};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+use std::fmt;
 use std::iter;
 
-/// Iterator over a flat-tree.
-#[derive(Debug)]
-pub struct Iterator {
-  index: usize,
-  offset: usize,
-  factor: usize,
+/// Iterator over a flat-tree using `usize` indices.
+pub type Iterator = GenericIterator<usize>;
+
+/// Iterator over a flat-tree, generic over the index type.
+///
+/// Use the `Iterator` alias for `usize` indices.
+///
+/// With the `serde` feature the cursor can be serialized as its `index`,
+/// `offset` and `factor`. Deserializing rejects values that aren't a valid
+/// cursor position.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(
+    try_from = "State<T>",
+    into = "State<T>",
+    bound(serialize = "T: FlatTreeIndex + Serialize")
+  ),
+  serde(bound(deserialize = "T: FlatTreeIndex + Deserialize<'de>"))
+)]
+pub struct GenericIterator<T> {
+  index: T,
+  offset: T,
+  factor: T,
 }
 
-impl Iterator {
+impl<T: FlatTreeIndex> GenericIterator<T> {
+  /// The deepest level the cursor can be positioned at, where the distance
+  /// between two nodes still fits in the index type. At this depth `parent()`
+  /// doesn't move the cursor and `try_parent()` returns `None`.
+  pub const MAX_DEPTH: usize = T::BITS as usize - 2;
+
   /// Create a new iterator.
-  pub fn new(index: usize) -> Self {
+  ///
+  /// ## Panics
+  /// If the depth of `index` is larger than `MAX_DEPTH`.
+  pub fn new(index: T) -> Self {
     let mut instance = Self {
-      index: 0,
-      offset: 0,
-      factor: 0,
+      index: T::ZERO,
+      offset: T::ZERO,
+      factor: T::ZERO,
     };
 
     instance.seek(index);
     instance
   }
 
+  /// Create a new iterator positioned at the root of a tree with `leaves`
+  /// leaves. When `leaves` isn't a power of two the tree is a forest, and the
+  /// iterator is positioned at its left most, and largest, root.
+  ///
+  /// ## Panics
+  /// If `leaves` is zero, or the root is deeper than `MAX_DEPTH`.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let iter = flat_tree::Iterator::new_at_root(4);
+  /// assert_eq!(iter.index(), 3);
+  /// assert_eq!(iter.depth(), 2);
+  ///
+  /// let iter = flat_tree::Iterator::new_at_root(6);
+  /// assert_eq!(iter.index(), 3);
+  ///
+  /// let iter = flat_tree::Iterator::new_at_root(8);
+  /// assert_eq!(iter.index(), 7);
+  /// ```
+  pub fn new_at_root(leaves: T) -> Self {
+    assert!(leaves > T::ZERO, "A tree without leaves has no root");
+    let depth = T::BITS - 1 - leaves.leading_zeros();
+    let factor: T = two_pow(depth + 1);
+    Self {
+      index: factor / T::TWO - T::ONE,
+      offset: T::ZERO,
+      factor,
+    }
+  }
+
   /// Get the current index.
   #[inline]
-  pub fn index(&self) -> usize {
+  pub fn index(&self) -> T {
     self.index
   }
 
   /// Get the current offset.
   #[inline]
-  pub fn offset(&self) -> usize {
+  pub fn offset(&self) -> T {
     self.offset
   }
 
+  /// Get the current factor, the distance between two nodes at the current
+  /// depth. This is always `2` to the power of `depth() + 1`.
+  #[inline]
+  pub fn factor(&self) -> T {
+    self.factor
+  }
+
+  /// Get the current depth, derived from the factor.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut iter = flat_tree::Iterator::new(0);
+  /// assert_eq!(iter.depth(), 0);
+  /// iter.parent();
+  /// assert_eq!(iter.depth(), 1);
+  /// iter.seek(23);
+  /// assert_eq!(iter.depth(), 3);
+  /// ```
+  #[inline]
+  pub fn depth(&self) -> usize {
+    self.factor.trailing_zeros() as usize - 1
+  }
+
   /// Seek to a position in the iterator.
-  pub fn seek(&mut self, index: usize) {
+  ///
+  /// ## Panics
+  /// If the depth of `index` is larger than `MAX_DEPTH`.
+  pub fn seek(&mut self, index: T) {
     self.index = index;
-    if is_odd(self.index) {
-      self.offset = offset(index);
-      self.factor = two_pow(depth(index) + 1);
-    } else {
-      self.offset = index / 2;
-      self.factor = 2;
-    }
+    self.factor = two_pow(index.trailing_ones() + 1);
+    self.offset = index / self.factor;
   }
 
   /// Check if the position of the iterator is currently on a left node.
   #[inline]
   pub fn is_left(&self) -> bool {
-    is_even(self.offset)
+    !self.offset.is_odd()
   }
 
   /// Check if the position of the iterator is currently on a right node.
   #[inline]
   pub fn is_right(&self) -> bool {
-    is_odd(self.offset)
+    self.offset.is_odd()
+  }
+
+  /// Check if the node at the current position spans `index`.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let iter = flat_tree::Iterator::new(3);
+  /// assert!(iter.contains(0));
+  /// assert!(iter.contains(3));
+  /// assert!(iter.contains(6));
+  /// assert!(!iter.contains(7));
+  /// ```
+  pub fn contains(&self, index: T) -> bool {
+    if index > self.index {
+      index < self.index + self.factor / T::TWO
+    } else if index < self.index {
+      index + self.factor / T::TWO > self.index
+    } else {
+      true
+    }
+  }
+
+  /// Get the parent for the current position without moving the cursor.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let iter = flat_tree::Iterator::new(5);
+  /// assert_eq!(iter.peek_parent(), 3);
+  /// assert_eq!(iter.index(), 5);
+  /// ```
+  pub fn peek_parent(&self) -> T {
+    if self.offset.is_odd() {
+      self.index - self.factor / T::TWO
+    } else {
+      self.index + self.factor / T::TWO
+    }
+  }
+
+  /// Get the sibling for the current position without moving the cursor.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let iter = flat_tree::Iterator::new(5);
+  /// assert_eq!(iter.peek_sibling(), 1);
+  /// assert_eq!(iter.index(), 5);
+  /// ```
+  pub fn peek_sibling(&self) -> T {
+    if self.is_left() {
+      self.index + self.factor
+    } else {
+      self.index - self.factor
+    }
+  }
+
+  /// Get the left_child for the current position without moving the cursor.
+  /// Returns the current index when on a leaf, like `left_child()`.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let iter = flat_tree::Iterator::new(5);
+  /// assert_eq!(iter.peek_left_child(), 4);
+  /// assert_eq!(iter.index(), 5);
+  /// ```
+  pub fn peek_left_child(&self) -> T {
+    if self.factor == T::TWO {
+      self.index
+    } else {
+      self.index - self.factor / T::TWO / T::TWO
+    }
+  }
+
+  /// Get the right_child for the current position without moving the
+  /// cursor. Returns the current index when on a leaf, like `right_child()`.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let iter = flat_tree::Iterator::new(5);
+  /// assert_eq!(iter.peek_right_child(), 6);
+  /// assert_eq!(iter.index(), 5);
+  /// ```
+  pub fn peek_right_child(&self) -> T {
+    if self.factor == T::TWO {
+      self.index
+    } else {
+      self.index + self.factor / T::TWO / T::TWO
+    }
   }
 
   /// Move the cursor and get the previous item from the current position.
-  pub fn prev(&mut self) -> usize {
-    if self.offset == 0 {
-      return self.index;
+  /// Returns the current index when already on the left most node, see
+  /// `try_prev()` to detect that case.
+  pub fn prev(&mut self) -> T {
+    self.try_prev().unwrap_or(self.index)
+  }
+
+  /// Move the cursor and get the previous item from the current position.
+  /// Returns `None`, without moving the cursor, when already on the left most
+  /// node.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut iter = flat_tree::Iterator::new(4);
+  /// assert_eq!(iter.try_prev(), Some(2));
+  /// assert_eq!(iter.try_prev(), Some(0));
+  /// assert_eq!(iter.try_prev(), None);
+  /// assert_eq!(iter.index(), 0);
+  /// ```
+  pub fn try_prev(&mut self) -> Option<T> {
+    if self.offset == T::ZERO {
+      return None;
     }
-    self.offset -= 1;
+    self.offset -= T::ONE;
     self.index -= self.factor;
-    self.index
+    Some(self.index)
   }
 
   /// Get the sibling for the current position and move the cursor.
-  pub fn sibling(&mut self) -> usize {
+  pub fn sibling(&mut self) -> T {
     if self.is_left() {
-      self.next().unwrap() // this is always safe
+      self.next().unwrap() // the sibling of a node always fits in the index
     } else {
       self.prev()
     }
   }
 
   /// Get the parent for the current position and move the cursor.
-  pub fn parent(&mut self) -> usize {
-    if is_odd(self.offset) {
-      self.index -= self.factor / 2;
-      self.offset = (self.offset - 1) / 2;
+  /// Returns the current index when already at `MAX_DEPTH`, see
+  /// `try_parent()` to detect that case.
+  pub fn parent(&mut self) -> T {
+    self.try_parent().unwrap_or(self.index)
+  }
+
+  /// Get the parent for the current position and move the cursor.
+  /// Returns `None`, without moving the cursor, when already at `MAX_DEPTH`.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut iter = flat_tree::Iterator::new(0);
+  /// assert_eq!(iter.try_parent(), Some(1));
+  /// for _ in 1..flat_tree::Iterator::MAX_DEPTH {
+  ///   assert!(iter.try_parent().is_some());
+  /// }
+  /// assert_eq!(iter.try_parent(), None);
+  /// assert_eq!(iter.depth(), flat_tree::Iterator::MAX_DEPTH);
+  /// ```
+  pub fn try_parent(&mut self) -> Option<T> {
+    let factor = self.factor.checked_mul(T::TWO)?;
+    if self.offset.is_odd() {
+      self.index -= self.factor / T::TWO;
+      self.offset = (self.offset - T::ONE) / T::TWO;
     } else {
-      self.index += self.factor / 2;
-      self.offset /= 2;
+      self.index += self.factor / T::TWO;
+      self.offset /= T::TWO;
     }
-    self.factor *= 2;
-    self.index
+    self.factor = factor;
+    Some(self.index)
   }
 
   /// Get the left_span for the current position and move the cursor.
-  pub fn left_span(&mut self) -> usize {
-    self.index = self.index - self.factor / 2 + 1;
-    self.offset = self.index / 2;
-    self.factor = 2;
+  pub fn left_span(&mut self) -> T {
+    self.index = self.index - self.factor / T::TWO + T::ONE;
+    self.offset = self.index / T::TWO;
+    self.factor = T::TWO;
     self.index
   }
 
   /// Get the right_span for the current position and move the cursor.
-  pub fn right_span(&mut self) -> usize {
-    self.index = self.index + self.factor / 2 - 1;
-    self.offset = self.index / 2;
-    self.factor = 2;
+  pub fn right_span(&mut self) -> T {
+    self.index = self.index + self.factor / T::TWO - T::ONE;
+    self.offset = self.index / T::TWO;
+    self.factor = T::TWO;
     self.index
   }
 
-  /// Get the left_child for the current position and move the cursor.
-  pub fn left_child(&mut self) -> usize {
-    if self.factor == 2 {
-      return self.index;
+  /// Move the cursor to the first leaf after the tree spanned by the current
+  /// position. Doesn't move the cursor if that leaf doesn't fit in the index
+  /// type.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut iter = flat_tree::Iterator::new(3);
+  /// assert_eq!(iter.next_tree(), 8);
+  /// assert_eq!(iter.next_tree(), 10);
+  /// ```
+  pub fn next_tree(&mut self) -> T {
+    let index = match (self.index + self.factor / T::TWO).checked_add(T::ONE) {
+      Some(index) => index,
+      None => return self.index,
+    };
+    self.index = index;
+    self.offset = self.index / T::TWO;
+    self.factor = T::TWO;
+    self.index
+  }
+
+  /// Move the cursor to the last leaf before the tree spanned by the current
+  /// position. Moves to `0` if there is no such leaf.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut iter = flat_tree::Iterator::new(11);
+  /// assert_eq!(iter.prev_tree(), 6);
+  /// assert_eq!(iter.prev_tree(), 4);
+  ///
+  /// let mut iter = flat_tree::Iterator::new(3);
+  /// assert_eq!(iter.prev_tree(), 0);
+  /// ```
+  pub fn prev_tree(&mut self) -> T {
+    if self.offset == T::ZERO {
+      self.index = T::ZERO;
+      self.factor = T::TWO;
+    } else {
+      self.index -= self.factor / T::TWO + T::ONE;
+      self.offset = self.index / T::TWO;
+      self.factor = T::TWO;
     }
-    self.factor /= 2;
-    self.index -= self.factor / 2;
-    self.offset *= 2;
     self.index
   }
 
+  /// Move the cursor up to the largest full root that starts at the current
+  /// leaf and ends before `index`. Returns `false`, without moving the cursor,
+  /// if the cursor isn't on a leaf before `index`.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut iter = flat_tree::Iterator::new(0);
+  /// assert!(iter.full_root(22));
+  /// assert_eq!(iter.index(), 7);
+  /// assert_eq!(iter.next_tree(), 16);
+  /// assert!(iter.full_root(22));
+  /// assert_eq!(iter.index(), 17);
+  /// assert_eq!(iter.next_tree(), 20);
+  /// assert!(iter.full_root(22));
+  /// assert_eq!(iter.index(), 20);
+  /// assert_eq!(iter.next_tree(), 22);
+  /// assert!(!iter.full_root(22));
+  /// ```
+  pub fn full_root(&mut self, index: T) -> bool {
+    if index <= self.index || self.index.is_odd() {
+      return false;
+    }
+    while self.depth() < Self::MAX_DEPTH
+      && index
+        > self
+          .index
+          .saturating_add(self.factor + self.factor / T::TWO)
+    {
+      self.index += self.factor / T::TWO;
+      self.factor *= T::TWO;
+      self.offset /= T::TWO;
+    }
+    true
+  }
+
+  /// Get the left_child for the current position and move the cursor.
+  pub fn left_child(&mut self) -> T {
+    self.try_left_child().unwrap_or(self.index)
+  }
+
+  /// Get the left_child for the current position and move the cursor.
+  /// Returns `None`, without moving the cursor, when on a leaf.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut iter = flat_tree::Iterator::new(3);
+  /// assert_eq!(iter.try_left_child(), Some(1));
+  /// assert_eq!(iter.try_left_child(), Some(0));
+  /// assert_eq!(iter.try_left_child(), None);
+  /// assert_eq!(iter.index(), 0);
+  /// ```
+  pub fn try_left_child(&mut self) -> Option<T> {
+    if self.factor == T::TWO {
+      return None;
+    }
+    self.factor /= T::TWO;
+    self.index -= self.factor / T::TWO;
+    self.offset *= T::TWO;
+    Some(self.index)
+  }
+
   /// Get the right_child for the current position and move the cursor.
-  pub fn right_child(&mut self) -> usize {
-    if self.factor == 2 {
-      return self.index;
+  pub fn right_child(&mut self) -> T {
+    self.try_right_child().unwrap_or(self.index)
+  }
+
+  /// Get the right_child for the current position and move the cursor.
+  /// Returns `None`, without moving the cursor, when on a leaf.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut iter = flat_tree::Iterator::new(3);
+  /// assert_eq!(iter.try_right_child(), Some(5));
+  /// assert_eq!(iter.try_right_child(), Some(6));
+  /// assert_eq!(iter.try_right_child(), None);
+  /// assert_eq!(iter.index(), 6);
+  /// ```
+  pub fn try_right_child(&mut self) -> Option<T> {
+    if self.factor == T::TWO {
+      return None;
     }
-    self.factor /= 2;
-    self.index += self.factor / 2;
-    self.offset = 2 * self.offset + 1;
-    self.index
+    self.factor /= T::TWO;
+    self.index += self.factor / T::TWO;
+    self.offset = T::TWO * self.offset + T::ONE;
+    Some(self.index)
+  }
+
+  /// Get an iterator over the leaves spanned by the current position, from
+  /// left to right. The cursor isn't moved.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let iter = flat_tree::Iterator::new(3);
+  /// assert_eq!(iter.leaves().collect::<Vec<_>>(), [0, 2, 4, 6]);
+  ///
+  /// let iter = flat_tree::Iterator::new(10);
+  /// assert_eq!(iter.leaves().collect::<Vec<_>>(), [10]);
+  /// ```
+  pub fn leaves(&self) -> Leaves<T> {
+    Leaves {
+      next: self.index + T::ONE - self.factor / T::TWO,
+      last: self.index + self.factor / T::TWO - T::ONE,
+    }
+  }
+
+  /// Wrap the iterator so it can't move outside of a tree with `leaves`
+  /// leaves.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut cursor = flat_tree::Iterator::new(4).with_tree_len(3);
+  /// assert_eq!(cursor.parent(), None);
+  /// assert_eq!(cursor.prev(), Some(2));
+  /// assert_eq!(cursor.parent(), Some(1));
+  /// assert_eq!(cursor.parent(), None);
+  /// assert_eq!(cursor.next(), None);
+  /// ```
+  pub fn with_tree_len(self, leaves: T) -> BoundedCursor<T> {
+    BoundedCursor { iter: self, leaves }
+  }
+
+  /// Wrap the iterator so it stops instead of moving past `max_index`.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let iter = flat_tree::Iterator::new(0).bounded(6);
+  /// assert_eq!(iter.collect::<Vec<_>>(), [2, 4, 6]);
+  ///
+  /// let iter = flat_tree::Iterator::new(1).bounded(12);
+  /// assert_eq!(iter.collect::<Vec<_>>(), [5, 9]);
+  /// ```
+  pub fn bounded(self, max_index: T) -> BoundedIter<T> {
+    BoundedIter {
+      iter: self,
+      max_index,
+    }
+  }
+}
+
+impl Iterator {
+  /// Yield the `Coords` of each index instead of the bare index.
+  pub fn with_coords(self) -> WithCoords<Self> {
+    WithCoords::new(self)
   }
 }
 
-impl iter::Iterator for Iterator {
-  type Item = usize;
+impl<T: FlatTreeIndex> iter::Iterator for GenericIterator<T> {
+  type Item = T;
 
   fn next(&mut self) -> Option<Self::Item> {
-    self.offset += 1;
-    self.index += self.factor;
+    self.index = self.index.checked_add(self.factor)?;
+    self.offset += T::ONE;
     Some(self.index)
   }
 }
 
-impl Default for Iterator {
+impl<T: FlatTreeIndex> Default for GenericIterator<T> {
   fn default() -> Self {
-    Self::new(0)
+    Self::new(T::ZERO)
+  }
+}
+
+/// Iterator over a flat-tree that terminates at a maximum index.
+///
+/// Created by `Iterator::bounded()`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundedIter<T = usize> {
+  iter: GenericIterator<T>,
+  max_index: T,
+}
+
+impl<T: FlatTreeIndex> fmt::Debug for BoundedIter<T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("BoundedIter")
+      .field("iter", &self.iter)
+      .field("max_index", &self.max_index)
+      .finish()
+  }
+}
+
+impl<T: FlatTreeIndex> BoundedIter<T> {
+  /// Get the maximum index the iterator moves to.
+  #[inline]
+  pub fn max_index(&self) -> T {
+    self.max_index
+  }
+
+  /// Get a reference to the underlying cursor.
+  #[inline]
+  pub fn get_ref(&self) -> &GenericIterator<T> {
+    &self.iter
+  }
+
+  /// Unwrap the underlying cursor.
+  pub fn into_inner(self) -> GenericIterator<T> {
+    self.iter
+  }
+}
+
+impl BoundedIter {
+  /// Yield the `Coords` of each index instead of the bare index.
+  pub fn with_coords(self) -> WithCoords<Self> {
+    WithCoords::new(self)
+  }
+}
+
+impl<T: FlatTreeIndex> iter::Iterator for BoundedIter<T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    match self.iter.index.checked_add(self.iter.factor) {
+      Some(index) if index <= self.max_index => self.iter.next(),
+      _ => None,
+    }
+  }
+}
+
+impl<T: FlatTreeIndex> iter::FusedIterator for BoundedIter<T> {}
+
+/// Iterator over the leaves spanned by a node.
+///
+/// Created by `Iterator::leaves()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Leaves<T = usize> {
+  next: T,
+  last: T,
+}
+
+impl Leaves {
+  /// Yield the `Coords` of each index instead of the bare index.
+  pub fn with_coords(self) -> WithCoords<Self> {
+    WithCoords::new(self)
+  }
+}
+
+impl<T: FlatTreeIndex> iter::Iterator for Leaves<T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.next > self.last {
+      return None;
+    }
+    let index = self.next;
+    if self.next == self.last {
+      // The last leaf is even, so this doesn't overflow.
+      self.next = self.last + T::ONE;
+    } else {
+      self.next += T::TWO;
+    }
+    Some(index)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    if self.next > self.last {
+      return (0, Some(0));
+    }
+    match ((self.last - self.next) / T::TWO).try_into() {
+      Ok(len) if len < usize::MAX => (len + 1, Some(len + 1)),
+      _ => (usize::MAX, None),
+    }
+  }
+}
+
+impl<T: FlatTreeIndex> iter::DoubleEndedIterator for Leaves<T> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    if self.next > self.last {
+      return None;
+    }
+    let index = self.last;
+    if self.last < T::TWO {
+      self.next = self.last + T::ONE;
+    } else {
+      self.last -= T::TWO;
+    }
+    Some(index)
+  }
+}
+
+impl iter::ExactSizeIterator for Leaves {}
+
+impl<T: FlatTreeIndex> iter::FusedIterator for Leaves<T> {}
+
+/// Cursor over a flat-tree that only moves to nodes that exist in a tree of
+/// a given length.
+///
+/// Every move returns `None`, without moving the cursor, if the target node
+/// doesn't exist. When the length isn't a power of two the tree is a forest,
+/// so `parent()` stops at the root of the tree the cursor is in.
+///
+/// Created by `Iterator::with_tree_len()`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundedCursor<T = usize> {
+  iter: GenericIterator<T>,
+  leaves: T,
+}
+
+impl<T: FlatTreeIndex> fmt::Debug for BoundedCursor<T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("BoundedCursor")
+      .field("iter", &self.iter)
+      .field("leaves", &self.leaves)
+      .finish()
+  }
+}
+
+impl<T: FlatTreeIndex> BoundedCursor<T> {
+  /// Get the number of leaves in the tree.
+  #[inline]
+  pub fn tree_len(&self) -> T {
+    self.leaves
+  }
+
+  /// Get the current index.
+  #[inline]
+  pub fn index(&self) -> T {
+    self.iter.index()
+  }
+
+  /// Get the current offset.
+  #[inline]
+  pub fn offset(&self) -> T {
+    self.iter.offset()
+  }
+
+  /// Get the current depth.
+  #[inline]
+  pub fn depth(&self) -> usize {
+    self.iter.depth()
+  }
+
+  /// Get a reference to the underlying cursor.
+  #[inline]
+  pub fn get_ref(&self) -> &GenericIterator<T> {
+    &self.iter
+  }
+
+  /// Unwrap the underlying cursor.
+  pub fn into_inner(self) -> GenericIterator<T> {
+    self.iter
+  }
+
+  /// Move the cursor to the previous node at the same depth.
+  pub fn prev(&mut self) -> Option<T> {
+    self.iter.try_prev()
+  }
+
+  /// Move the cursor to the sibling.
+  pub fn sibling(&mut self) -> Option<T> {
+    if self.iter.is_left() {
+      self.next()
+    } else {
+      self.prev()
+    }
+  }
+
+  /// Move the cursor to the parent.
+  pub fn parent(&mut self) -> Option<T> {
+    if !spans_leaf_in(self.iter.peek_parent(), self.leaves) {
+      return None;
+    }
+    self.iter.try_parent()
+  }
+
+  /// Move the cursor to the left child.
+  pub fn left_child(&mut self) -> Option<T> {
+    if !spans_leaf_in(self.iter.peek_left_child(), self.leaves) {
+      return None;
+    }
+    self.iter.try_left_child()
+  }
+
+  /// Move the cursor to the right child.
+  pub fn right_child(&mut self) -> Option<T> {
+    if !spans_leaf_in(self.iter.peek_right_child(), self.leaves) {
+      return None;
+    }
+    self.iter.try_right_child()
+  }
+}
+
+impl BoundedCursor {
+  /// Yield the `Coords` of each index instead of the bare index.
+  pub fn with_coords(self) -> WithCoords<Self> {
+    WithCoords::new(self)
+  }
+}
+
+impl<T: FlatTreeIndex> iter::Iterator for BoundedCursor<T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let index = self.iter.index.checked_add(self.iter.factor)?;
+    if !spans_leaf_in(index, self.leaves) {
+      return None;
+    }
+    self.iter.next()
+  }
+}
+
+// The factor is always a power of two, which is easier to read as one.
+impl<T: FlatTreeIndex> fmt::Debug for GenericIterator<T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Iterator")
+      .field("index", &self.index)
+      .field("depth", &self.depth())
+      .field("offset", &self.offset)
+      .field(
+        "factor",
+        &format_args!("2^{}", self.factor.trailing_zeros()),
+      )
+      .finish()
+  }
+}
+
+#[cfg(feature = "defmt")]
+impl<T: FlatTreeIndex + defmt::Format> defmt::Format for GenericIterator<T> {
+  fn format(&self, f: defmt::Formatter) {
+    defmt::write!(
+      f,
+      "Iterator {{ index: {}, depth: {=usize}, offset: {}, factor: 2^{=u32} }}",
+      self.index,
+      self.depth(),
+      self.offset,
+      self.factor.trailing_zeros()
+    )
+  }
+}
+
+// Any index is a valid position, as long as it's not too deep.
+#[cfg(feature = "arbitrary")]
+impl<'a, T> Arbitrary<'a> for GenericIterator<T>
+where
+  T: FlatTreeIndex + Arbitrary<'a>,
+{
+  fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+    let mut index = T::arbitrary(u)?;
+    if index.trailing_ones() as usize > Self::MAX_DEPTH {
+      // Unset the bit that makes the index too deep.
+      index -= T::pow2(Self::MAX_DEPTH as u32);
+    }
+    Ok(Self::new(index))
+  }
+
+  fn size_hint(depth: usize) -> (usize, Option<usize>) {
+    T::size_hint(depth)
+  }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T> Arbitrary<'a> for BoundedIter<T>
+where
+  T: FlatTreeIndex + Arbitrary<'a>,
+{
+  fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+    let iter = GenericIterator::arbitrary(u)?;
+    Ok(iter.bounded(T::arbitrary(u)?))
   }
 }
 
-fn two_pow(n: usize) -> usize {
-  if n < 31 {
-    1 << n
-  } else {
-    ((1 << 30) * (1 << (n - 30)))
+// A tree with at least one leaf, and a cursor on one of its nodes: a leaf,
+// or an ancestor of it that's in the tree.
+#[cfg(feature = "arbitrary")]
+impl<'a, T> Arbitrary<'a> for BoundedCursor<T>
+where
+  T: FlatTreeIndex + Arbitrary<'a>,
+{
+  fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+    // Keep every leaf index in range of `T`.
+    let max_leaves = T::pow2(T::BITS - 2);
+    let leaves = T::arbitrary(u)?;
+    let leaves = leaves - leaves / max_leaves * max_leaves + T::ONE;
+    let leaf = T::arbitrary(u)?;
+    let leaf = leaf - leaf / leaves * leaves;
+    let mut cursor = GenericIterator::new(leaf * T::TWO).with_tree_len(leaves);
+    for _ in 0..u.int_in_range(0..=T::BITS)? {
+      if cursor.parent().is_none() {
+        break;
+      }
+    }
+    Ok(cursor)
   }
 }
+
+/// Serialized form of `GenericIterator`.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct State<T> {
+  index: T,
+  offset: T,
+  factor: T,
+}
+
+#[cfg(feature = "serde")]
+impl<T: FlatTreeIndex> From<GenericIterator<T>> for State<T> {
+  fn from(iter: GenericIterator<T>) -> Self {
+    Self {
+      index: iter.index,
+      offset: iter.offset,
+      factor: iter.factor,
+    }
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<T: FlatTreeIndex> TryFrom<State<T>> for GenericIterator<T> {
+  type Error = String;
+
+  fn try_from(state: State<T>) -> Result<Self, Self::Error> {
+    if state.index.trailing_ones() as usize > Self::MAX_DEPTH {
+      return Err(format!(
+        "invalid cursor state: index {} is too deep",
+        state.index
+      ));
+    }
+    let iter = Self::new(state.index);
+    if iter.offset != state.offset || iter.factor != state.factor {
+      return Err(format!(
+        "invalid cursor state: index {} has offset {} and factor {}, got \
+         offset {} and factor {}",
+        iter.index, iter.offset, iter.factor, state.offset, state.factor
+      ));
+    }
+    Ok(iter)
+  }
+}
+
+/// Same as `in_tree()`, for any index type.
+fn spans_leaf_in<T: FlatTreeIndex>(index: T, leaves: T) -> bool {
+  let depth = index.trailing_ones();
+  if depth == 0 {
+    return index / T::TWO < leaves;
+  }
+  match (index / T::TWO).checked_add(T::pow2(depth - 1)) {
+    Some(last_leaf) => last_leaf < leaves,
+    None => false,
+  }
+}
+
+fn two_pow<T: FlatTreeIndex>(n: u32) -> T {
+  let max_depth = GenericIterator::<T>::MAX_DEPTH;
+  assert!(
+    n as usize <= max_depth + 1,
+    "Depth {} is larger than the maximum cursor depth {}",
+    n - 1,
+    max_depth
+  );
+  T::pow2(n)
+}
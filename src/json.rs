@@ -0,0 +1,223 @@
+//! Exchange node sets, frontiers and trees as JSON.
+//!
+//! Every document is an object with a `version`, currently `1`, and a
+//! `kind`. Indices are flat-tree indices, as JSON numbers.
+//!
+//! - `node_set`: `{"version":1,"kind":"node_set","nodes":[1,4]}`. The
+//!   nodes are sorted and unique on export, and may be in any order on
+//!   import.
+//! - `frontier`: `{"version":1,"kind":"frontier","leaves":3,"roots":[1,4]}`.
+//!   The `roots` are the full roots of a tree with `leaves` leaves. They're
+//!   only there for readers, and must match `leaves` on import.
+//! - `tree`: `{"version":1,"kind":"tree","leaves":3,"nodes":[0,1]}`. A tree
+//!   with `leaves` leaves, with the `nodes` that are present in it. Every
+//!   node must be in the tree, see `TreeDescription`.
+//!
+//! Unknown fields are ignored, so later versions can add fields.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{Document, Frontier};
+//!
+//! let json = Document::from(Frontier::with_leaves(3)).to_json();
+//! assert_eq!(
+//!   json,
+//!   r#"{"version":1,"kind":"frontier","leaves":3,"roots":[1,4]}"#
+//! );
+//!
+//! match Document::from_json(&json) {
+//!   Ok(Document::Frontier(frontier)) => assert_eq!(frontier.leaves(), 3),
+//!   _ => unreachable!(),
+//! }
+//! ```
+use super::*;
+
+use serde::{Deserialize, Serialize};
+use std::error;
+use std::fmt;
+
+/// The version written by `Document::to_json()`.
+const VERSION: u64 = 1;
+
+/// A tree with a fixed number of leaves, and the nodes present in it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct TreeDescription {
+  /// The number of leaves in the tree.
+  pub leaves: usize,
+  /// The nodes present in the tree. A node is in the tree if every leaf it
+  /// spans is.
+  pub nodes: NodeSet,
+}
+
+/// A JSON document, see the module docs for the schema.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Document {
+  /// A `node_set` document.
+  NodeSet(NodeSet),
+  /// A `frontier` document.
+  Frontier(Frontier),
+  /// A `tree` document.
+  Tree(TreeDescription),
+}
+
+impl Document {
+  /// Encode the document as JSON.
+  pub fn to_json(&self) -> String {
+    let body = match self {
+      Document::NodeSet(nodes) => Body::NodeSet {
+        nodes: nodes.as_slice().to_vec(),
+      },
+      Document::Frontier(frontier) => Body::Frontier {
+        leaves: frontier.leaves(),
+        roots: frontier.roots().to_vec(),
+      },
+      Document::Tree(tree) => Body::Tree {
+        leaves: tree.leaves,
+        nodes: tree.nodes.as_slice().to_vec(),
+      },
+    };
+    let state = State {
+      version: VERSION,
+      body,
+    };
+    serde_json::to_string(&state).expect("documents always serialize")
+  }
+
+  /// Decode a document from JSON.
+  ///
+  /// ## Errors
+  /// If `json` isn't a document of a known version, or describes nodes that
+  /// don't match its tree.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::{Document, JsonError};
+  ///
+  /// let json = r#"{"version":1,"kind":"tree","leaves":2,"nodes":[4]}"#;
+  /// assert_eq!(
+  ///   Document::from_json(json),
+  ///   Err(JsonError::NotInTree { node: 4, leaves: 2 })
+  /// );
+  /// ```
+  pub fn from_json(json: &str) -> Result<Self, JsonError> {
+    let version: Version = serde_json::from_str(json)
+      .map_err(|err| JsonError::Syntax(err.to_string()))?;
+    if version.version != VERSION {
+      return Err(JsonError::UnsupportedVersion(version.version));
+    }
+    let state: State = serde_json::from_str(json)
+      .map_err(|err| JsonError::Syntax(err.to_string()))?;
+
+    match state.body {
+      Body::NodeSet { nodes } => Ok(Document::NodeSet(nodes.into())),
+      Body::Frontier { leaves, roots } => {
+        if leaves > usize::MAX / 2 {
+          return Err(JsonError::TooManyLeaves(leaves));
+        }
+        let frontier = Frontier::with_leaves(leaves);
+        if frontier.roots() != roots.as_slice() {
+          return Err(JsonError::RootsMismatch { leaves });
+        }
+        Ok(Document::Frontier(frontier))
+      }
+      Body::Tree { leaves, nodes } => {
+        if leaves > usize::MAX / 2 {
+          return Err(JsonError::TooManyLeaves(leaves));
+        }
+        let nodes: NodeSet = nodes.into();
+        if let Some(node) = nodes
+          .iter()
+          .find(|&i| leaves == 0 || right_span(i) > 2 * leaves - 2)
+        {
+          return Err(JsonError::NotInTree { node, leaves });
+        }
+        Ok(Document::Tree(TreeDescription { leaves, nodes }))
+      }
+    }
+  }
+}
+
+impl From<NodeSet> for Document {
+  fn from(nodes: NodeSet) -> Self {
+    Document::NodeSet(nodes)
+  }
+}
+
+impl From<Frontier> for Document {
+  fn from(frontier: Frontier) -> Self {
+    Document::Frontier(frontier)
+  }
+}
+
+impl From<TreeDescription> for Document {
+  fn from(tree: TreeDescription) -> Self {
+    Document::Tree(tree)
+  }
+}
+
+/// Error returned when a JSON document can't be decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonError {
+  /// The JSON is malformed, or doesn't follow the schema.
+  Syntax(String),
+  /// The document has a version this crate can't read.
+  UnsupportedVersion(u64),
+  /// The number of leaves is too large to index.
+  TooManyLeaves(usize),
+  /// The roots of a frontier don't match its number of leaves.
+  RootsMismatch {
+    /// The number of leaves of the frontier.
+    leaves: usize,
+  },
+  /// A node of a tree isn't in it.
+  NotInTree {
+    /// The node outside of the tree.
+    node: usize,
+    /// The number of leaves of the tree.
+    leaves: usize,
+  },
+}
+
+impl fmt::Display for JsonError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      JsonError::Syntax(message) => write!(f, "invalid document: {}", message),
+      JsonError::UnsupportedVersion(version) => {
+        write!(f, "unsupported document version {}", version)
+      }
+      JsonError::TooManyLeaves(leaves) => {
+        write!(f, "too many leaves: {}", leaves)
+      }
+      JsonError::RootsMismatch { leaves } => {
+        write!(f, "roots don't match a tree of {} leaves", leaves)
+      }
+      JsonError::NotInTree { node, leaves } => {
+        write!(f, "node {} isn't in a tree of {} leaves", node, leaves)
+      }
+    }
+  }
+}
+
+impl error::Error for JsonError {}
+
+/// Just the version, checked before decoding the rest.
+#[derive(Deserialize)]
+struct Version {
+  version: u64,
+}
+
+/// Serialized form of `Document`.
+#[derive(Serialize, Deserialize)]
+struct State {
+  version: u64,
+  #[serde(flatten)]
+  body: Body,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Body {
+  NodeSet { nodes: Vec<usize> },
+  Frontier { leaves: usize, roots: Vec<usize> },
+  Tree { leaves: usize, nodes: Vec<usize> },
+}
@@ -0,0 +1,209 @@
+//! Flat-tree numbering of trees where every parent has `arity` children.
+//!
+//! Like the binary flat-tree, the subtree of a node is a range of indices,
+//! laid out as the subtree of its first child, the node itself, then the
+//! subtrees of the other children. With an arity of `2`, this is the same
+//! numbering as the rest of the crate. Indices don't depend on the size of
+//! the tree, and every index is a node.
+//!
+//! Unlike the binary numbering, the depth of an index can't be read from its
+//! bits, so converting an index takes a step per level of the tree.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::kary;
+//!
+//! // With 3 children per parent, leaves 0, 2 and 3 have parent 1.
+//! assert_eq!(kary::parent(3, 2), 1);
+//! let children: Vec<_> = kary::children(3, 1).unwrap().collect();
+//! assert_eq!(children, [0, 2, 3]);
+//! assert_eq!(kary::spans(3, 4), (0, 12));
+//! ```
+
+/// Returns the index of the node at the specified depth and offset.
+///
+/// ## Panics
+/// Panics if `arity` is less than `2`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::kary;
+///
+/// assert_eq!(kary::index(3, 0, 1), 2);
+/// assert_eq!(kary::index(3, 0, 3), 5);
+/// assert_eq!(kary::index(3, 1, 1), 6);
+/// assert_eq!(kary::index(3, 2, 0), 4);
+/// assert_eq!(kary::index(2, 1, 2), flat_tree::index(1, 2));
+/// ```
+pub fn index(arity: usize, depth: usize, offset: usize) -> usize {
+  assert_arity(arity);
+  // Every digit of the offset says which child the node's subtree is in,
+  // at each level above it.
+  let mut start = 0;
+  let mut level = depth;
+  let mut offset = offset;
+  while offset > 0 {
+    let child = offset % arity;
+    if child > 0 {
+      start += child * subtree_len(arity, level) + 1;
+    }
+    offset /= arity;
+    level += 1;
+  }
+  if depth == 0 {
+    start
+  } else {
+    start + subtree_len(arity, depth - 1)
+  }
+}
+
+/// Returns the depth of a node.
+///
+/// ## Panics
+/// Panics if `arity` is less than `2`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::kary;
+///
+/// assert_eq!(kary::depth(3, 0), 0);
+/// assert_eq!(kary::depth(3, 1), 1);
+/// assert_eq!(kary::depth(3, 4), 2);
+/// assert_eq!(kary::depth(3, 5), 0);
+/// ```
+pub fn depth(arity: usize, i: usize) -> usize {
+  locate(arity, i).0
+}
+
+/// Returns the offset of a node, from the left of its depth.
+///
+/// ## Panics
+/// Panics if `arity` is less than `2`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::kary;
+///
+/// assert_eq!(kary::offset(3, 3), 2);
+/// assert_eq!(kary::offset(3, 5), 3);
+/// assert_eq!(kary::offset(3, 10), 2);
+/// ```
+pub fn offset(arity: usize, i: usize) -> usize {
+  locate(arity, i).1
+}
+
+/// Returns the parent of a node.
+///
+/// ## Panics
+/// Panics if `arity` is less than `2`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::kary;
+///
+/// assert_eq!(kary::parent(3, 0), 1);
+/// assert_eq!(kary::parent(3, 10), 4);
+/// assert_eq!(kary::parent(4, 0), 1);
+/// ```
+pub fn parent(arity: usize, i: usize) -> usize {
+  let (depth, offset) = locate(arity, i);
+  index(arity, depth + 1, offset / arity)
+}
+
+/// Returns the children of a node, from left to right, or `None` if the
+/// node is a leaf.
+///
+/// ## Panics
+/// Panics if `arity` is less than `2`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::kary;
+///
+/// let children: Vec<_> = kary::children(3, 4).unwrap().collect();
+/// assert_eq!(children, [1, 6, 10]);
+/// assert!(kary::children(3, 0).is_none());
+/// ```
+pub fn children(arity: usize, i: usize) -> Option<impl Iterator<Item = usize>> {
+  let (depth, offset) = locate(arity, i);
+  if depth == 0 {
+    return None;
+  }
+  let first = offset * arity;
+  Some((first..first + arity).map(move |child| index(arity, depth - 1, child)))
+}
+
+/// Returns the left and right most leaves spanned by a node.
+///
+/// ## Panics
+/// Panics if `arity` is less than `2`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::kary;
+///
+/// assert_eq!(kary::spans(3, 6), (5, 8));
+/// assert_eq!(kary::spans(3, 7), (7, 7));
+/// assert_eq!(kary::spans(4, 5), (0, 20));
+/// ```
+pub fn spans(arity: usize, i: usize) -> (usize, usize) {
+  let (depth, offset) = locate(arity, i);
+  let leaves = arity.pow(depth as u32);
+  (
+    index(arity, 0, offset * leaves),
+    index(arity, 0, (offset + 1) * leaves - 1),
+  )
+}
+
+/// Returns the depth and offset of a node.
+fn locate(arity: usize, i: usize) -> (usize, usize) {
+  assert_arity(arity);
+  // Start from the first subtree at offset 0 that's large enough to hold
+  // the node, and walk down to it.
+  let mut depth = 0;
+  while subtree_len(arity, depth) <= i {
+    depth += 1;
+  }
+  let (mut start, mut offset) = (0, 0);
+  while depth > 0 {
+    let child_len = subtree_len(arity, depth - 1);
+    let root = start + child_len;
+    if i == root {
+      break;
+    }
+    let child = if i < root {
+      0
+    } else {
+      (i - start - 1) / child_len
+    };
+    if child > 0 {
+      start += child * child_len + 1;
+    }
+    offset = offset * arity + child;
+    depth -= 1;
+  }
+  (depth, offset)
+}
+
+/// Returns the number of nodes in a subtree with a root at `depth`, or
+/// `usize::MAX` if that doesn't fit in a `usize`.
+fn subtree_len(arity: usize, depth: usize) -> usize {
+  let mut len: usize = 1;
+  let mut width: usize = 1;
+  for _ in 0..depth {
+    width = match width.checked_mul(arity) {
+      Some(width) => width,
+      None => return usize::MAX,
+    };
+    len = len.saturating_add(width);
+  }
+  len
+}
+
+fn assert_arity(arity: usize) {
+  assert!(
+    arity >= 2,
+    "A parent needs at least 2 children, got {}",
+    arity
+  );
+}
@@ -0,0 +1,129 @@
+//! Map nodes to offsets in files of fixed-size records.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::Layout;
+//!
+//! // 40 byte nodes after a 32 byte header, and 4096 byte blocks.
+//! let layout = Layout::new(40, 4096).header(32);
+//! assert_eq!(layout.node_range(3), 152..192);
+//! assert_eq!(layout.data_range(4), 8192..12288);
+//! assert_eq!(layout.tree_file_len(3), 232);
+//! ```
+use super::*;
+
+use std::ops::Range;
+
+/// Layout of a store made of a tree file and a data file.
+///
+/// The tree file is an optional header, followed by a record for every node
+/// in flat-tree order, so node `i` is record `i`. The data file holds the
+/// leaves, one block per leaf, so leaf `2 * n` is block `n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Layout {
+  record_size: u64,
+  block_size: u64,
+  header: u64,
+}
+
+impl Layout {
+  /// Create a layout with `record_size` byte node records and `block_size`
+  /// byte leaf blocks, without a header.
+  pub fn new(record_size: u64, block_size: u64) -> Self {
+    Self {
+      record_size,
+      block_size,
+      header: 0,
+    }
+  }
+
+  /// Set the length of the header at the start of the tree file.
+  pub fn header(mut self, header: u64) -> Self {
+    self.header = header;
+    self
+  }
+
+  /// Get the size of a node record.
+  #[inline]
+  pub fn record_size(&self) -> u64 {
+    self.record_size
+  }
+
+  /// Get the size of a leaf block.
+  #[inline]
+  pub fn block_size(&self) -> u64 {
+    self.block_size
+  }
+
+  /// Get the length of the header of the tree file.
+  #[inline]
+  pub fn header_len(&self) -> u64 {
+    self.header
+  }
+
+  /// Returns the offset of the record of node `i` in the tree file.
+  #[inline]
+  pub fn node_offset(&self, i: usize) -> u64 {
+    self.header + i as u64 * self.record_size
+  }
+
+  /// Returns the range of bytes of the record of node `i` in the tree file.
+  pub fn node_range(&self, i: usize) -> Range<u64> {
+    let start = self.node_offset(i);
+    start..start + self.record_size
+  }
+
+  /// Returns the node whose record contains byte `offset` of the tree file,
+  /// or `None` if `offset` is in the header.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let layout = flat_tree::Layout::new(40, 4096).header(32);
+  /// assert_eq!(layout.node_at(0), None);
+  /// assert_eq!(layout.node_at(32), Some(0));
+  /// assert_eq!(layout.node_at(191), Some(3));
+  /// ```
+  pub fn node_at(&self, offset: u64) -> Option<usize> {
+    let offset = offset.checked_sub(self.header)?;
+    Some((offset / self.record_size) as usize)
+  }
+
+  /// Returns the length of the tree file of a tree with `leaves` leaves.
+  pub fn tree_file_len(&self, leaves: usize) -> u64 {
+    self.node_offset((2 * leaves).saturating_sub(1))
+  }
+
+  /// Returns the offset of the block of leaf `i` in the data file.
+  ///
+  /// ## Panics
+  /// If `i` isn't a leaf.
+  pub fn data_offset(&self, i: usize) -> u64 {
+    assert!(
+      is_even(i),
+      "Only leaves are stored as data, got index {}",
+      i
+    );
+    (i / 2) as u64 * self.block_size
+  }
+
+  /// Returns the range of bytes of the block of leaf `i` in the data file.
+  ///
+  /// ## Panics
+  /// If `i` isn't a leaf.
+  pub fn data_range(&self, i: usize) -> Range<u64> {
+    let start = self.data_offset(i);
+    start..start + self.block_size
+  }
+
+  /// Returns the leaf whose block contains byte `offset` of the data file.
+  #[inline]
+  pub fn leaf_at(&self, offset: u64) -> usize {
+    2 * (offset / self.block_size) as usize
+  }
+
+  /// Returns the length of the data file of a tree with `leaves` leaves.
+  #[inline]
+  pub fn data_file_len(&self, leaves: usize) -> u64 {
+    leaves as u64 * self.block_size
+  }
+}
@@ -2,9 +2,155 @@
 #![cfg_attr(feature = "nightly", feature(external_doc))]
 #![cfg_attr(feature = "nightly", doc(include = "../README.md"))]
 
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+#[cfg(feature = "defmt")]
+extern crate defmt;
+#[cfg(feature = "hash")]
+extern crate digest;
+#[cfg(feature = "memmap2")]
+extern crate memmap2;
+#[cfg(feature = "proptest")]
+extern crate proptest;
+// The pyo3 macros refer to `::core`, which needs declaring on edition 2015.
+#[cfg(feature = "python")]
+extern crate core;
+#[cfg(feature = "python")]
+extern crate pyo3;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "json")]
+extern crate serde_json;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+
+mod arena;
+mod atomic_tree_index;
+mod bitfield;
+mod byte_range;
+mod byte_tree;
+mod chunk_groups;
+mod codec;
+mod coords;
+pub mod ct;
+mod cursor;
+pub mod delta;
+mod dense_tree;
+mod diff;
+mod explain;
+mod fetch_order;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod frontier;
+mod graph;
+#[cfg(feature = "hash")]
+mod hash;
+mod highlight;
+mod index;
 mod iterator;
-
-pub use iterator::Iterator;
+#[cfg(feature = "json")]
+mod json;
+pub mod kary;
+mod layout;
+pub mod mirrored;
+#[cfg(feature = "memmap2")]
+mod mmap;
+mod mmr;
+mod node_index;
+mod node_set;
+mod node_store;
+mod numbering;
+mod overlay;
+mod path;
+mod planner;
+pub mod prelude;
+mod present;
+mod proof;
+#[cfg(feature = "python")]
+pub mod python;
+mod render;
+pub mod rle;
+mod sparse_tree;
+mod storage;
+#[cfg(feature = "svg")]
+mod svg;
+#[cfg(feature = "proptest")]
+pub mod testing;
+mod tile;
+mod trace;
+mod traversal;
+mod tree_builder;
+mod tree_index;
+mod walk;
+mod want;
+mod want_queue;
+mod zipper;
+
+pub use arena::Arena;
+pub use atomic_tree_index::AtomicTreeIndex;
+pub use bitfield::Bitfield;
+pub use byte_range::{byte_range_of, nodes_covering_bytes};
+pub use byte_tree::ByteTree;
+pub use chunk_groups::ChunkGroups;
+pub use codec::DecodeError;
+pub use coords::{Coords, WithCoords};
+pub use cursor::{Cursor, Move, Record};
+pub use dense_tree::{DenseSnapshot, DenseTree};
+pub use diff::{render_diff, TreeDiff};
+pub use explain::{explain, Explanation};
+pub use fetch_order::FetchOrder;
+pub use frontier::{completed_parents, Frontier, FrontierDiff};
+pub use graph::{to_dot, to_mermaid, GraphOptions};
+#[cfg(feature = "hash")]
+pub use hash::{leaf_hash, merkle_root, node_hash, parent_hash};
+pub use highlight::Highlights;
+pub use index::FlatTreeIndex;
+pub use iterator::{
+  BoundedCursor, BoundedIter, GenericIterator, Iterator, Leaves,
+};
+#[cfg(feature = "json")]
+pub use json::{Document, JsonError, TreeDescription};
+pub use layout::Layout;
+#[cfg(feature = "memmap2")]
+pub use mmap::MmapBitfield;
+pub use mmr::{is_peak, mmr_leaves, mmr_size, peaks};
+pub use node_index::NodeIndex;
+pub use node_set::NodeSet;
+pub use node_store::{NodeStore, NodeStoreMut};
+pub use numbering::{
+  from_fenwick, from_level_order, from_postorder, from_preorder, from_utreexo,
+  from_veb, to_fenwick, to_level_order, to_postorder, to_preorder, to_utreexo,
+  to_veb, utreexo_rows,
+};
+pub use overlay::Overlay;
+pub use path::{apply_ops, apply_path, Op, PathError};
+pub use planner::{Planner, Request, Strategy};
+pub use present::IndexLeaves;
+pub use proof::{
+  multiproof, needed_nodes, verify_proof, Verifier, VerifyError,
+};
+pub use render::{render_ascii, render_unicode, Orientation, RenderOptions};
+pub use sparse_tree::SparseTree;
+pub use storage::{assemble_proof, AssembleProof, MissingNode, NodeStorage};
+#[cfg(feature = "svg")]
+pub use svg::{to_svg, SvgOptions};
+pub use tile::Tile;
+pub use trace::Trace;
+pub use traversal::{
+  ancestors, audit_path, descendants, inorder, inorder_subtree, levelorder,
+  levelorder_subtree, nodes, parents_by_level, path_from_root,
+  path_to_ancestor, postorder, postorder_subtree, preorder, preorder_subtree,
+  subtree_nodes, Ancestors, AuditPath, Descendants, Nodes, NotAncestor, Order,
+  ParentsByLevel, PathFromRoot,
+};
+pub use tree_builder::TreeBuilder;
+pub use tree_index::{Proof, ProofOptions, TreeIndex};
+pub use walk::{fold_subtree, walk, Step, WalkContext};
+pub use want_queue::WantQueue;
+pub use zipper::Zipper;
+
+/// Alias for `Iterator` that doesn't clash with `std::iter::Iterator`.
+pub type FlatTreeIter = Iterator;
 
 /// Returns the flat-tree of the tree node at the specified depth and offset.
 ///
@@ -194,7 +340,9 @@ pub fn right_span_with_depth(i: usize, depth: usize) -> usize {
   if depth == 0 {
     i
   } else {
-    (offset_with_depth(i, depth) + 1) * (2 << depth) - 2
+    // Add the width last, so the right span of the last node doesn't
+    // overflow.
+    offset_with_depth(i, depth) * (2 << depth) + ((2 << depth) - 2)
   }
 }
 
@@ -291,7 +439,7 @@ pub fn count(i: usize) -> usize {
 ///
 /// let mut nodes = Vec::with_capacity(16);
 /// full_roots(0, &mut nodes);
-/// assert_eq!(nodes, []);
+/// assert_eq!(nodes, [0usize; 0]);
 ///
 /// let mut nodes = Vec::with_capacity(16);
 /// full_roots(2, &mut nodes);
@@ -316,10 +464,8 @@ pub fn count(i: usize) -> usize {
 pub fn full_roots(i: usize, nodes: &mut Vec<usize>) {
   assert!(
     is_even(i),
-    format!(
-      "You can only look up roots for depth 0 blocks, got index {}",
-      i
-    )
+    "You can only look up roots for depth 0 blocks, got index {}",
+    i
   );
   let mut tmp = i >> 1;
   let mut offset = 0;
@@ -339,11 +485,19 @@ pub fn full_roots(i: usize, nodes: &mut Vec<usize>) {
   }
 }
 
+/// Check if a node exists in a tree with `leaves` leaves, i.e. if its span
+/// lies within the first `leaves` leaves.
+#[inline]
+pub(crate) fn in_tree(i: usize, leaves: usize) -> bool {
+  right_span(i) / 2 < leaves
+}
+
 #[inline]
 pub(crate) fn is_even(num: usize) -> bool {
   (num & 1) == 0
 }
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_is_even() {
   assert_eq!(is_even(0), true);
   assert_eq!(is_even(1), false);
@@ -356,6 +510,7 @@ pub(crate) fn is_odd(num: usize) -> bool {
   (num & 1) != 0
 }
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_is_odd() {
   assert_eq!(is_odd(0), false);
   assert_eq!(is_odd(1), true);
@@ -0,0 +1,124 @@
+//! The mirror image of the flat-tree numbering, where the tree is filled
+//! from the right, so a right child has a lower index than its sibling.
+//!
+//! Nodes have the same indices, depths and offsets, counted from the right,
+//! so `index()`, `depth()`, `offset()`, `parent()`, `sibling()`, `uncle()`
+//! and `full_roots()` apply as they are. Only the functions that tell left
+//! from right are mirrored here.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::mirrored;
+//!
+//! assert_eq!(mirrored::children(1), Some((2, 0)));
+//! assert_eq!(mirrored::left_span(3), 6);
+//!
+//! // In a tree with 3 leaves, the leftmost leaf is leaf 2 from the right,
+//! // and the rightmost one is leaf 0.
+//! assert_eq!(mirrored::to_mirrored(0, 3), Some(4));
+//! assert_eq!(mirrored::to_mirrored(4, 3), Some(0));
+//! ```
+use super::*;
+
+/// Returns the left and right children of a node, where the left child has
+/// the higher index.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::mirrored;
+///
+/// assert_eq!(mirrored::children(0), None);
+/// assert_eq!(mirrored::children(3), Some((5, 1)));
+/// ```
+#[inline]
+pub fn children(i: usize) -> Option<(usize, usize)> {
+  super::children(i).map(|(low, high)| (high, low))
+}
+
+/// Returns the left child of a node, the one with the higher index.
+#[inline]
+pub fn left_child(i: usize) -> Option<usize> {
+  super::right_child(i)
+}
+
+/// Returns the right child of a node, the one with the lower index.
+#[inline]
+pub fn right_child(i: usize) -> Option<usize> {
+  super::left_child(i)
+}
+
+/// Returns the left most node in the tree that a node spans, which has the
+/// highest index.
+#[inline]
+pub fn left_span(i: usize) -> usize {
+  super::right_span(i)
+}
+
+/// Returns the right most node in the tree that a node spans, which has
+/// the lowest index.
+#[inline]
+pub fn right_span(i: usize) -> usize {
+  super::left_span(i)
+}
+
+/// Returns the left and right most nodes in the tree that a node spans.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::mirrored;
+///
+/// assert_eq!(mirrored::spans(3), (6, 0));
+/// assert_eq!(mirrored::spans(9), (10, 8));
+/// ```
+#[inline]
+pub fn spans(i: usize) -> (usize, usize) {
+  let (low, high) = super::spans(i);
+  (high, low)
+}
+
+/// Returns the index in the mirrored numbering of the node that spans the
+/// same leaves as node `i`, in a tree with `leaves` leaves.
+///
+/// Returns `None` if the node isn't in the tree, or no node spans the same
+/// leaves in the mirrored numbering, as they aren't aligned from the right.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::mirrored;
+///
+/// assert_eq!(mirrored::to_mirrored(1, 4), Some(5));
+/// assert_eq!(mirrored::to_mirrored(1, 3), None);
+/// assert_eq!(mirrored::to_mirrored(8, 4), None);
+/// ```
+pub fn to_mirrored(i: usize, leaves: usize) -> Option<usize> {
+  if !in_tree(i, leaves) {
+    return None;
+  }
+  let depth = depth(i);
+  // The last leaf the node spans is the first one from the right.
+  let first = leaves - 1 - super::right_span(i) / 2;
+  if first & ((1 << depth) - 1) != 0 {
+    return None;
+  }
+  Some(index(depth, first >> depth))
+}
+
+/// Returns the index in the flat-tree numbering of node `i` in the
+/// mirrored numbering, in a tree with `leaves` leaves. The inverse of
+/// `to_mirrored()`.
+///
+/// Returns `None` if the node isn't in the tree, or no node spans the same
+/// leaves in the flat-tree numbering.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::mirrored;
+///
+/// assert_eq!(mirrored::from_mirrored(5, 4), Some(1));
+/// assert_eq!(mirrored::from_mirrored(4, 3), Some(0));
+/// ```
+#[inline]
+pub fn from_mirrored(i: usize, leaves: usize) -> Option<usize> {
+  // Mirroring is its own inverse.
+  to_mirrored(i, leaves)
+}
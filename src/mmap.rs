@@ -0,0 +1,121 @@
+//! A `Bitfield` stored in a memory-mapped file, so the state of a
+//! `TreeIndex` persists without explicit serialization.
+//!
+//! ## Usage
+//! ```rust
+//! # fn main() -> std::io::Result<()> {
+//! use flat_tree::{MmapBitfield, TreeIndex};
+//!
+//! let path = std::env::temp_dir().join("flat-tree-mmap-usage.bitfield");
+//! # let _ = std::fs::remove_file(&path);
+//! let mut tree = TreeIndex::from_bitfield(MmapBitfield::open(&path)?);
+//! tree.set(0);
+//! tree.set(2);
+//! tree.bitfield().sync()?;
+//!
+//! let tree = TreeIndex::from_bitfield(MmapBitfield::open(&path)?);
+//! assert!(tree.get(1));
+//! # std::fs::remove_file(&path)?;
+//! # Ok(())
+//! # }
+//! ```
+use super::*;
+
+use bitfield::{get_bit, set_bit};
+use memmap2::MmapMut;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::Path;
+
+/// The number of bytes the file grows by at a time.
+const PAGE_SIZE: usize = 4096;
+
+/// A `Bitfield` backed by a memory-mapped file.
+///
+/// The file grows in multiples of 4096 bytes when a bit past its end is
+/// needed. Writes reach the file when the operating system decides, or when
+/// `flush()` or `sync()` is called.
+#[derive(Debug)]
+pub struct MmapBitfield {
+  file: File,
+  map: MmapMut,
+}
+
+impl MmapBitfield {
+  /// Open the file at `path` for reading and writing, creating it if it
+  /// doesn't exist.
+  pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+    let file = OpenOptions::new()
+      .read(true)
+      .write(true)
+      .create(true)
+      .truncate(false)
+      .open(path)?;
+    Self::from_file(file)
+  }
+
+  /// Map a file that's open for reading and writing.
+  pub fn from_file(file: File) -> io::Result<Self> {
+    let map = map(&file)?;
+    Ok(Self { file, map })
+  }
+
+  /// Get a reference to the underlying file.
+  #[inline]
+  pub fn file(&self) -> &File {
+    &self.file
+  }
+
+  /// Write the changes to the file, blocking until done.
+  pub fn flush(&self) -> io::Result<()> {
+    self.map.flush()
+  }
+
+  /// Start writing the changes to the file, without waiting for it to
+  /// complete.
+  pub fn flush_async(&self) -> io::Result<()> {
+    self.map.flush_async()
+  }
+
+  /// Write the changes to the file, along with its metadata, and wait for
+  /// them to reach the disk.
+  pub fn sync(&self) -> io::Result<()> {
+    self.map.flush()?;
+    self.file.sync_all()
+  }
+
+  fn try_grow(&mut self, len: usize) -> io::Result<()> {
+    let bytes = len.div_ceil(8).div_ceil(PAGE_SIZE) * PAGE_SIZE;
+    self.map.flush()?;
+    self.file.set_len(bytes as u64)?;
+    self.map = map(&self.file)?;
+    Ok(())
+  }
+}
+
+impl Bitfield for MmapBitfield {
+  #[inline]
+  fn get(&self, index: usize) -> bool {
+    get_bit(&self.map, index)
+  }
+
+  #[inline]
+  fn set(&mut self, index: usize, value: bool) -> bool {
+    set_bit(&mut self.map, index, value)
+  }
+
+  #[inline]
+  fn len(&self) -> usize {
+    self.map.len() * 8
+  }
+
+  fn grow(&mut self, len: usize) -> bool {
+    len <= self.len() || self.try_grow(len).is_ok()
+  }
+}
+
+fn map(file: &File) -> io::Result<MmapMut> {
+  // Safety: the mapping is only sound while no one else truncates the file,
+  // which callers opt into by handing the file to the bitfield.
+  unsafe { MmapMut::map_mut(file) }
+}
@@ -0,0 +1,112 @@
+//! Map the full roots of a flat-tree to the peaks of a Merkle Mountain
+//! Range, where nodes are numbered by their post-order position, see
+//! `to_postorder()`.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{is_peak, mmr_size, peaks};
+//!
+//! // An MMR with 3 leaves has 4 nodes, and peaks at positions 2 and 3.
+//! assert_eq!(mmr_size(3), 4);
+//! assert_eq!(peaks(3), [2, 3]);
+//! assert!(is_peak(2, 3));
+//! assert!(!is_peak(1, 3));
+//! ```
+use super::*;
+
+use std::cmp;
+
+/// Returns the MMR positions of the peaks of an MMR with `leaves` leaves,
+/// from left to right. These are the full roots of the flat-tree, see
+/// `full_roots()`.
+///
+/// ## Panics
+/// If `2 * leaves` doesn't fit in a `usize`.
+///
+/// ## Examples
+/// ```rust
+/// assert!(flat_tree::peaks(0).is_empty());
+/// assert_eq!(flat_tree::peaks(4), [6]);
+/// assert_eq!(flat_tree::peaks(7), [6, 9, 10]);
+/// ```
+pub fn peaks(leaves: usize) -> Vec<usize> {
+  let mut roots = Vec::new();
+  full_roots(double_leaves(leaves), &mut roots);
+  // Every full root is in the tree, and its position is smaller than
+  // `2 * leaves`, so it fits.
+  roots
+    .into_iter()
+    .map(|root| to_postorder(root, leaves).expect("A full root is in the tree"))
+    .collect()
+}
+
+/// Check if MMR position `pos` is a peak of an MMR with `leaves` leaves.
+///
+/// ## Examples
+/// ```rust
+/// assert!(flat_tree::is_peak(9, 7));
+/// assert!(!flat_tree::is_peak(9, 8));
+/// assert!(!flat_tree::is_peak(11, 7));
+/// ```
+pub fn is_peak(pos: usize, leaves: usize) -> bool {
+  match from_postorder(pos, leaves) {
+    Some(i) => !in_tree(parent(i), leaves),
+    None => false,
+  }
+}
+
+/// Returns the number of nodes in an MMR with `leaves` leaves, which is the
+/// MMR position of the next leaf.
+///
+/// ## Panics
+/// If `2 * leaves` doesn't fit in a `usize`.
+///
+/// ## Examples
+/// ```rust
+/// assert_eq!(flat_tree::mmr_size(4), 7);
+/// assert_eq!(flat_tree::mmr_size(5), 8);
+/// ```
+#[inline]
+pub fn mmr_size(leaves: usize) -> usize {
+  double_leaves(leaves) - leaves.count_ones() as usize
+}
+
+/// Returns the number of leaves of an MMR with `size` nodes. The inverse of
+/// `mmr_size()`.
+///
+/// Returns `None` if no MMR has `size` nodes, as the last leaf would be
+/// missing some of the parents it completes.
+///
+/// ## Examples
+/// ```rust
+/// assert_eq!(flat_tree::mmr_leaves(7), Some(4));
+/// assert_eq!(flat_tree::mmr_leaves(6), None);
+/// ```
+pub fn mmr_leaves(size: usize) -> Option<usize> {
+  // `mmr_size()` is increasing, and never smaller than the leaves. It
+  // doesn't fit in a `usize` past half of the range.
+  let (mut low, mut high) = (0, cmp::min(size, usize::MAX / 2));
+  while low < high {
+    let mid = low + (high - low) / 2;
+    if mmr_size(mid) < size {
+      low = mid + 1;
+    } else {
+      high = mid;
+    }
+  }
+  if mmr_size(low) == size {
+    Some(low)
+  } else {
+    None
+  }
+}
+
+/// Returns `2 * leaves`, the index after the last leaf of a tree.
+fn double_leaves(leaves: usize) -> usize {
+  assert!(
+    leaves <= usize::MAX / 2,
+    "An MMR with {} leaves has more nodes than fit in a usize",
+    leaves
+  );
+  2 * leaves
+}
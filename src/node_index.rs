@@ -0,0 +1,117 @@
+//! ## Usage
+//! ```rust
+//! use flat_tree::NodeIndex;
+//!
+//! let node = NodeIndex::from(5);
+//! assert_eq!(node.depth(), 1);
+//! assert_eq!(node.parent(), NodeIndex(3));
+//! assert_eq!(usize::from(node), 5);
+//! ```
+use super::*;
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::{Arbitrary, Unstructured};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A flat-tree index, as a type of its own so containers can tell a node
+/// apart from other numbers, such as leaf numbers or byte offsets.
+///
+/// With the `serde` feature a node is serialized as its bare index.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(transparent)
+)]
+pub struct NodeIndex(pub usize);
+
+impl NodeIndex {
+  /// Get the node of the leaf with number `leaf`.
+  #[inline]
+  pub fn leaf(leaf: usize) -> Self {
+    NodeIndex(2 * leaf)
+  }
+
+  /// Get the bare index.
+  #[inline]
+  pub fn index(self) -> usize {
+    self.0
+  }
+
+  /// Get the depth of the node, see `depth()`.
+  #[inline]
+  pub fn depth(self) -> usize {
+    depth(self.0)
+  }
+
+  /// Get the offset of the node, see `offset()`.
+  #[inline]
+  pub fn offset(self) -> usize {
+    offset(self.0)
+  }
+
+  /// Get the parent of the node, see `parent()`.
+  #[inline]
+  pub fn parent(self) -> Self {
+    NodeIndex(parent(self.0))
+  }
+
+  /// Get the sibling of the node, see `sibling()`.
+  #[inline]
+  pub fn sibling(self) -> Self {
+    NodeIndex(sibling(self.0))
+  }
+
+  /// Get the children of the node, see `children()`.
+  #[inline]
+  pub fn children(self) -> Option<(Self, Self)> {
+    children(self.0).map(|(left, right)| (NodeIndex(left), NodeIndex(right)))
+  }
+
+  /// Get the left and right most nodes spanned by the node, see `spans()`.
+  #[inline]
+  pub fn spans(self) -> (Self, Self) {
+    let (left, right) = spans(self.0);
+    (NodeIndex(left), NodeIndex(right))
+  }
+}
+
+impl From<usize> for NodeIndex {
+  #[inline]
+  fn from(index: usize) -> Self {
+    NodeIndex(index)
+  }
+}
+
+impl From<NodeIndex> for usize {
+  #[inline]
+  fn from(node: NodeIndex) -> Self {
+    node.0
+  }
+}
+
+impl fmt::Display for NodeIndex {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    self.0.fmt(f)
+  }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for NodeIndex {
+  fn format(&self, f: defmt::Formatter) {
+    defmt::write!(f, "{=usize}", self.0)
+  }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for NodeIndex {
+  fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+    usize::arbitrary(u).map(NodeIndex)
+  }
+
+  fn size_hint(depth: usize) -> (usize, Option<usize>) {
+    usize::size_hint(depth)
+  }
+}
@@ -0,0 +1,135 @@
+//! ## Usage
+//! ```rust
+//! let mut set = flat_tree::NodeSet::new();
+//! set.insert(5);
+//! set.insert(0);
+//! assert!(set.contains(5));
+//! assert_eq!(set.iter().collect::<Vec<_>>(), [0, 5]);
+//! ```
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::iter;
+use std::slice;
+use std::vec;
+
+/// A set of flat-tree nodes, ordered by index.
+///
+/// With the `serde` feature the set is serialized as a sequence of its
+/// nodes in increasing order. Deserializing accepts them in any order, and
+/// ignores duplicates.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(from = "Vec<usize>", into = "Vec<usize>")
+)]
+pub struct NodeSet {
+  nodes: Vec<usize>,
+}
+
+impl NodeSet {
+  /// Create an empty set.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Add a node. Returns `false` if it was already in the set.
+  pub fn insert(&mut self, index: usize) -> bool {
+    match self.nodes.binary_search(&index) {
+      Ok(_) => false,
+      Err(position) => {
+        self.nodes.insert(position, index);
+        true
+      }
+    }
+  }
+
+  /// Remove a node. Returns `false` if it wasn't in the set.
+  pub fn remove(&mut self, index: usize) -> bool {
+    match self.nodes.binary_search(&index) {
+      Ok(position) => {
+        self.nodes.remove(position);
+        true
+      }
+      Err(_) => false,
+    }
+  }
+
+  /// Check if a node is in the set.
+  #[inline]
+  pub fn contains(&self, index: usize) -> bool {
+    self.nodes.binary_search(&index).is_ok()
+  }
+
+  /// Get the number of nodes in the set.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.nodes.len()
+  }
+
+  /// Check if the set is empty.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.nodes.is_empty()
+  }
+
+  /// Get the nodes, in increasing index order.
+  #[inline]
+  pub fn as_slice(&self) -> &[usize] {
+    &self.nodes
+  }
+
+  /// Get an iterator over the nodes, in increasing index order.
+  pub fn iter(&self) -> iter::Cloned<slice::Iter<'_, usize>> {
+    self.nodes.iter().cloned()
+  }
+}
+
+impl iter::FromIterator<usize> for NodeSet {
+  fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+    let mut nodes: Vec<usize> = iter.into_iter().collect();
+    nodes.sort_unstable();
+    nodes.dedup();
+    Self { nodes }
+  }
+}
+
+impl iter::Extend<usize> for NodeSet {
+  fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+    self.nodes.extend(iter);
+    self.nodes.sort_unstable();
+    self.nodes.dedup();
+  }
+}
+
+impl IntoIterator for NodeSet {
+  type Item = usize;
+  type IntoIter = vec::IntoIter<usize>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.nodes.into_iter()
+  }
+}
+
+impl<'a> IntoIterator for &'a NodeSet {
+  type Item = usize;
+  type IntoIter = iter::Cloned<slice::Iter<'a, usize>>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+#[cfg(feature = "serde")]
+impl From<Vec<usize>> for NodeSet {
+  fn from(nodes: Vec<usize>) -> Self {
+    nodes.into_iter().collect()
+  }
+}
+
+#[cfg(feature = "serde")]
+impl From<NodeSet> for Vec<usize> {
+  fn from(set: NodeSet) -> Self {
+    set.nodes
+  }
+}
@@ -0,0 +1,205 @@
+//! Index any container of node values by `NodeIndex`.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{DenseTree, NodeIndex, NodeStore};
+//!
+//! // Sum the leaves of a subtree, whatever stores the values.
+//! fn sum<S: NodeStore<Value = u32>>(store: &S, root: NodeIndex) -> u32 {
+//!   let (left, right) = root.spans();
+//!   (left.0..=right.0)
+//!     .step_by(2)
+//!     .filter_map(|leaf| store.node(NodeIndex(leaf)))
+//!     .sum()
+//! }
+//!
+//! let values = vec![1, 0, 2, 0, 3];
+//! let tree: DenseTree<u32> = values.iter().cloned().enumerate().collect();
+//! assert_eq!(sum(&values, NodeIndex(1)), 3);
+//! assert_eq!(sum(&tree, NodeIndex(3)), 6);
+//! assert_eq!(values[NodeIndex(4)], tree[NodeIndex(4)]);
+//! ```
+use super::*;
+
+use std::ops::{Index, IndexMut};
+
+/// Storage of a value for some nodes of a flat-tree, like the synchronous
+/// counterpart of `NodeStorage`.
+pub trait NodeStore {
+  /// The value stored for a node.
+  type Value;
+
+  /// Get the value of `node`, or `None` if it doesn't have one.
+  fn node(&self, node: NodeIndex) -> Option<&Self::Value>;
+
+  /// Check if `node` has a value.
+  fn contains_node(&self, node: NodeIndex) -> bool {
+    self.node(node).is_some()
+  }
+}
+
+/// Storage of a value for some nodes of a flat-tree, that can be changed.
+pub trait NodeStoreMut: NodeStore {
+  /// Get a mutable reference to the value of `node`, or `None` if it
+  /// doesn't have one.
+  fn node_mut(&mut self, node: NodeIndex) -> Option<&mut Self::Value>;
+}
+
+impl<T> NodeStore for [T] {
+  type Value = T;
+
+  fn node(&self, node: NodeIndex) -> Option<&T> {
+    self.get(node.0)
+  }
+}
+
+impl<T> NodeStoreMut for [T] {
+  fn node_mut(&mut self, node: NodeIndex) -> Option<&mut T> {
+    self.get_mut(node.0)
+  }
+}
+
+impl<T> NodeStore for Vec<T> {
+  type Value = T;
+
+  fn node(&self, node: NodeIndex) -> Option<&T> {
+    self.get(node.0)
+  }
+}
+
+impl<T> NodeStoreMut for Vec<T> {
+  fn node_mut(&mut self, node: NodeIndex) -> Option<&mut T> {
+    self.get_mut(node.0)
+  }
+}
+
+impl<T> NodeStore for DenseTree<T> {
+  type Value = T;
+
+  fn node(&self, node: NodeIndex) -> Option<&T> {
+    self.get(node.0)
+  }
+}
+
+impl<T: Clone> NodeStoreMut for DenseTree<T> {
+  fn node_mut(&mut self, node: NodeIndex) -> Option<&mut T> {
+    self.get_mut(node.0)
+  }
+}
+
+impl<T> NodeStore for DenseSnapshot<T> {
+  type Value = T;
+
+  fn node(&self, node: NodeIndex) -> Option<&T> {
+    self.get(node.0)
+  }
+}
+
+impl<T> NodeStore for SparseTree<T> {
+  type Value = T;
+
+  fn node(&self, node: NodeIndex) -> Option<&T> {
+    self.get(node.0)
+  }
+}
+
+impl<T> NodeStoreMut for SparseTree<T> {
+  fn node_mut(&mut self, node: NodeIndex) -> Option<&mut T> {
+    self.get_mut(node.0)
+  }
+}
+
+impl<T> NodeStore for Arena<T> {
+  type Value = T;
+
+  fn node(&self, node: NodeIndex) -> Option<&T> {
+    self.get(node)
+  }
+}
+
+impl<T> NodeStoreMut for Arena<T> {
+  fn node_mut(&mut self, node: NodeIndex) -> Option<&mut T> {
+    self.get_mut(node)
+  }
+}
+
+impl<S: NodeStore + ?Sized> NodeStore for &S {
+  type Value = S::Value;
+
+  fn node(&self, node: NodeIndex) -> Option<&S::Value> {
+    (**self).node(node)
+  }
+}
+
+impl<S: NodeStore + ?Sized> NodeStore for &mut S {
+  type Value = S::Value;
+
+  fn node(&self, node: NodeIndex) -> Option<&S::Value> {
+    (**self).node(node)
+  }
+}
+
+impl<S: NodeStoreMut + ?Sized> NodeStoreMut for &mut S {
+  fn node_mut(&mut self, node: NodeIndex) -> Option<&mut S::Value> {
+    (**self).node_mut(node)
+  }
+}
+
+/// Get the value of `node` from `store`, for `Index` impls.
+fn index<S: NodeStore + ?Sized>(store: &S, node: NodeIndex) -> &S::Value {
+  store
+    .node(node)
+    .unwrap_or_else(|| panic!("Node {} doesn't have a value", node))
+}
+
+/// Get the value of `node` from `store`, for `IndexMut` impls.
+fn index_mut<S: NodeStoreMut + ?Sized>(
+  store: &mut S,
+  node: NodeIndex,
+) -> &mut S::Value {
+  store
+    .node_mut(node)
+    .unwrap_or_else(|| panic!("Node {} doesn't have a value", node))
+}
+
+macro_rules! impl_index {
+  ($($ty:ty),*) => {
+    $(
+      impl<T> Index<NodeIndex> for $ty {
+        type Output = T;
+
+        fn index(&self, node: NodeIndex) -> &T {
+          index(self, node)
+        }
+      }
+    )*
+  };
+}
+
+macro_rules! impl_index_mut {
+  ($($ty:ty),*) => {
+    $(
+      impl<T> IndexMut<NodeIndex> for $ty {
+        fn index_mut(&mut self, node: NodeIndex) -> &mut T {
+          index_mut(self, node)
+        }
+      }
+    )*
+  };
+}
+
+impl_index!(
+  [T],
+  Vec<T>,
+  DenseTree<T>,
+  DenseSnapshot<T>,
+  SparseTree<T>,
+  Arena<T>
+);
+impl_index_mut!([T], Vec<T>, SparseTree<T>, Arena<T>);
+
+impl<T: Clone> IndexMut<NodeIndex> for DenseTree<T> {
+  fn index_mut(&mut self, node: NodeIndex) -> &mut T {
+    index_mut(self, node)
+  }
+}
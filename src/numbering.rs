@@ -0,0 +1,509 @@
+//! Convert flat-tree indices to and from other ways of numbering the nodes
+//! of a binary tree.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{from_level_order, to_level_order};
+//!
+//! // A tree with 4 leaves has a height of 3.
+//! assert_eq!(to_level_order(3, 3), Some(1));
+//! assert_eq!(from_level_order(7, 3), Some(6));
+//! ```
+use super::*;
+
+/// Returns the 1-based binary heap number of node `i`, in a complete tree
+/// that has `tree_height` levels, so `2^(tree_height - 1)` leaves. The root
+/// is `1`, and the children of `k` are `2k` and `2k + 1`.
+///
+/// Returns `None` if the node isn't in the tree, or its number doesn't fit
+/// in a `usize`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::to_level_order;
+///
+/// assert_eq!(to_level_order(1, 3), Some(2));
+/// assert_eq!(to_level_order(5, 3), Some(3));
+/// assert_eq!(to_level_order(0, 3), Some(4));
+/// assert_eq!(to_level_order(8, 3), None);
+/// assert_eq!(to_level_order(7, 3), None);
+/// ```
+pub fn to_level_order(i: usize, tree_height: usize) -> Option<usize> {
+  let (level, offset) = level_and_offset(i, tree_height)?;
+  Some((1 << level) + offset)
+}
+
+/// Returns the node with 1-based binary heap number `k`, in a complete tree
+/// that has `tree_height` levels. The inverse of `to_level_order()`.
+///
+/// Returns `None` if `k` is zero or past the last node of the tree, or the
+/// node doesn't fit in a `usize`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::from_level_order;
+///
+/// assert_eq!(from_level_order(1, 3), Some(3));
+/// assert_eq!(from_level_order(3, 3), Some(5));
+/// assert_eq!(from_level_order(4, 3), Some(0));
+/// assert_eq!(from_level_order(8, 3), None);
+/// ```
+pub fn from_level_order(k: usize, tree_height: usize) -> Option<usize> {
+  if k == 0 {
+    return None;
+  }
+  let level = (usize::BITS - 1 - k.leading_zeros()) as usize;
+  from_level_and_offset(level, k - (1 << level), tree_height)
+}
+
+/// Returns the level of node `i`, counted from the root at level `0`, and
+/// its offset within the level, in a complete tree with `tree_height`
+/// levels.
+fn level_and_offset(i: usize, tree_height: usize) -> Option<(usize, usize)> {
+  let depth = depth(i);
+  if depth >= tree_height || depth + 1 >= usize::BITS as usize {
+    return None;
+  }
+  let level = tree_height - 1 - depth;
+  let offset = offset_with_depth(i, depth);
+  if level >= usize::BITS as usize - 1 || offset >> level != 0 {
+    return None;
+  }
+  Some((level, offset))
+}
+
+/// The inverse of `level_and_offset()`.
+fn from_level_and_offset(
+  level: usize,
+  offset: usize,
+  tree_height: usize,
+) -> Option<usize> {
+  if level >= tree_height {
+    return None;
+  }
+  let depth = tree_height - 1 - level;
+  if depth + 1 >= usize::BITS as usize || offset > usize::MAX >> (depth + 1) {
+    return None;
+  }
+  Some(index(depth, offset))
+}
+
+/// Returns the 0-based post-order position of node `i` in a tree with
+/// `leaves` leaves, see `postorder()`. This is the position used by Merkle
+/// Mountain Ranges, where every node comes right after the last node it
+/// spans.
+///
+/// Returns `None` if the node isn't in the tree.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::to_postorder;
+///
+/// assert_eq!(to_postorder(2, 3), Some(1));
+/// assert_eq!(to_postorder(1, 3), Some(2));
+/// assert_eq!(to_postorder(4, 3), Some(3));
+/// assert_eq!(to_postorder(3, 3), None);
+/// ```
+pub fn to_postorder(i: usize, leaves: usize) -> Option<usize> {
+  if !in_tree(i, leaves) {
+    return None;
+  }
+  // The node comes right after the last leaf it spans, and the parents
+  // below it that the leaf completes.
+  Some(leaf_postorder(right_span(i) / 2) + depth(i))
+}
+
+/// Returns the node at 0-based post-order position `p` in a tree with
+/// `leaves` leaves. The inverse of `to_postorder()`.
+///
+/// Returns `None` if `p` is past the last node of the tree.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::from_postorder;
+///
+/// assert_eq!(from_postorder(2, 4), Some(1));
+/// assert_eq!(from_postorder(6, 4), Some(3));
+/// assert_eq!(from_postorder(7, 4), None);
+/// ```
+pub fn from_postorder(p: usize, leaves: usize) -> Option<usize> {
+  // Find the last leaf at or before `p`.
+  let (mut low, mut high) = (0, leaves);
+  while low < high {
+    let mid = low + (high - low) / 2;
+    if leaf_postorder(mid) <= p {
+      low = mid + 1;
+    } else {
+      high = mid;
+    }
+  }
+  let leaf = low.checked_sub(1)?;
+  let depth = p - leaf_postorder(leaf);
+  if depth > leaf.trailing_ones() as usize {
+    return None;
+  }
+  Some(index(depth, leaf >> depth))
+}
+
+/// Returns the post-order position of leaf number `leaf`, which is the
+/// number of nodes in a tree with `leaf` leaves.
+#[inline]
+fn leaf_postorder(leaf: usize) -> usize {
+  2 * leaf - leaf.count_ones() as usize
+}
+
+/// Returns the 0-based pre-order position of node `i` in the left-balanced
+/// tree over `leaves` leaves, as used by bao and BLAKE3. Unlike a bounded
+/// tree, see `nodes()`, it has a single root, and it contains every index
+/// below `2 * leaves - 1`. A parent whose subtree isn't complete joins its
+/// complete left subtree with what's left of the right one.
+///
+/// This is the position in the bao combined encoding. The outboard
+/// encoding only stores parents, so a parent is at its pre-order position
+/// minus `left_span(i) / 2`, the number of leaves before it.
+///
+/// Returns `None` if the node isn't in the tree, or the tree's size doesn't
+/// fit in a `usize`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::to_preorder;
+///
+/// // The root of a tree with 3 leaves is 3, with children 1 and 4.
+/// assert_eq!(to_preorder(3, 3), Some(0));
+/// assert_eq!(to_preorder(1, 3), Some(1));
+/// assert_eq!(to_preorder(2, 3), Some(3));
+/// assert_eq!(to_preorder(4, 3), Some(4));
+/// assert_eq!(to_preorder(5, 3), None);
+/// ```
+pub fn to_preorder(i: usize, leaves: usize) -> Option<usize> {
+  let last = leaves.checked_mul(2)?.checked_sub(2)?;
+  if i > last {
+    return None;
+  }
+  let mut node = balanced_root(last);
+  let mut position = 0;
+  while node != i {
+    let depth = depth(node);
+    let (left, right) = children_with_depth(node, depth)?;
+    position += 1;
+    if i < node {
+      node = left;
+    } else {
+      position += (1 << depth) - 1;
+      node = balanced_right(right, last);
+    }
+  }
+  Some(position)
+}
+
+/// Returns the node at 0-based pre-order position `p` in the left-balanced
+/// tree over `leaves` leaves. The inverse of `to_preorder()`.
+///
+/// Returns `None` if `p` is past the last node of the tree, or the tree's
+/// size doesn't fit in a `usize`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::from_preorder;
+///
+/// assert_eq!(from_preorder(0, 5), Some(7));
+/// assert_eq!(from_preorder(8, 5), Some(8));
+/// assert_eq!(from_preorder(9, 5), None);
+/// ```
+pub fn from_preorder(p: usize, leaves: usize) -> Option<usize> {
+  let last = leaves.checked_mul(2)?.checked_sub(2)?;
+  if p > last {
+    return None;
+  }
+  let mut node = balanced_root(last);
+  let mut p = p;
+  while p > 0 {
+    let depth = depth(node);
+    let (left, right) = children_with_depth(node, depth)?;
+    p -= 1;
+    let left_len = (1 << depth) - 1;
+    if p < left_len {
+      node = left;
+    } else {
+      p -= left_len;
+      node = balanced_right(right, last);
+    }
+  }
+  Some(node)
+}
+
+/// Returns the root of the left-balanced tree whose last node is `last`.
+pub(crate) fn balanced_root(last: usize) -> usize {
+  let mut root = 0;
+  while 2 * root < last {
+    root = 2 * root + 1;
+  }
+  root
+}
+
+/// Returns the right child of a parent in the left-balanced tree whose last
+/// node is `last`, given the right child in the full tree.
+pub(crate) fn balanced_right(mut right: usize, last: usize) -> usize {
+  while right > last {
+    right = left_child(right).expect("The left-balanced tree has a leaf");
+  }
+  right
+}
+
+/// Returns the 1-based Fenwick tree (binary indexed tree) position that
+/// covers the same leaves as node `i`. Position `j` covers the
+/// `j & j.wrapping_neg()` leaves that end at leaf number `j - 1`, which is
+/// always a left child, so a prefix of `n` leaves is covered by the
+/// positions a Fenwick prefix query for `n` visits, the nodes of
+/// `full_roots(2 * n)`.
+///
+/// Returns `None` if the node is a right child, as no position covers the
+/// same leaves.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::to_fenwick;
+///
+/// assert_eq!(to_fenwick(0), Some(1));
+/// assert_eq!(to_fenwick(1), Some(2));
+/// assert_eq!(to_fenwick(4), Some(3));
+/// assert_eq!(to_fenwick(3), Some(4));
+/// assert_eq!(to_fenwick(2), None);
+/// ```
+pub fn to_fenwick(i: usize) -> Option<usize> {
+  let depth = depth(i);
+  let offset = offset_with_depth(i, depth);
+  if offset & 1 != 0 {
+    return None;
+  }
+  Some((offset + 1) << depth)
+}
+
+/// Returns the node that covers the same leaves as 1-based Fenwick tree
+/// position `j`. The inverse of `to_fenwick()`.
+///
+/// Returns `None` if `j` is zero, or the node doesn't fit in a `usize`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::from_fenwick;
+///
+/// assert_eq!(from_fenwick(6), Some(9));
+/// assert_eq!(from_fenwick(8), Some(7));
+/// assert_eq!(from_fenwick(0), None);
+/// assert_eq!(from_fenwick(usize::MAX), None);
+/// ```
+pub fn from_fenwick(j: usize) -> Option<usize> {
+  if j == 0 {
+    return None;
+  }
+  let depth = j.trailing_zeros() as usize;
+  let offset = (j >> depth) - 1;
+  // The node is `(2 * offset + 1) << depth` minus one, without shifting by
+  // the full width of a `usize` like `index()` would at the top depth.
+  if offset > usize::MAX >> depth >> 1 {
+    return None;
+  }
+  Some(((2 * offset + 1) << depth) - 1)
+}
+
+/// Returns the 0-based position of node `i` in the van Emde Boas layout of a
+/// complete tree with `tree_height` levels. The tree is split in a top tree
+/// with half of the levels, followed by every bottom tree from left to
+/// right, and each of them is laid out the same way, down to blocks of at
+/// most `block_height` levels, which are laid out in level order.
+///
+/// A descent from the root then only touches about `tree_height /
+/// block_height` blocks, whatever the size of a block in bytes, so it's
+/// much friendlier to caches than the in-order layout for deep trees.
+///
+/// Returns `None` if the node isn't in the tree, or the tree's size doesn't
+/// fit in a `usize`.
+///
+/// ## Panics
+/// Panics if `block_height` is zero.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::to_veb;
+///
+/// // A tree with 8 leaves has a height of 4, and a top tree of 2 levels.
+/// assert_eq!(to_veb(7, 4, 1), Some(0));
+/// assert_eq!(to_veb(11, 4, 1), Some(2));
+/// assert_eq!(to_veb(1, 4, 1), Some(3));
+/// assert_eq!(to_veb(2, 4, 1), Some(5));
+/// assert_eq!(to_veb(4, 4, 1), Some(7));
+/// assert_eq!(to_veb(15, 4, 1), None);
+/// ```
+pub fn to_veb(
+  i: usize,
+  tree_height: usize,
+  block_height: usize,
+) -> Option<usize> {
+  assert!(block_height > 0, "The block height must be at least 1");
+  if tree_height >= usize::BITS as usize {
+    return None;
+  }
+  let (level, offset) = level_and_offset(i, tree_height)?;
+  Some(veb_position(level, offset, tree_height, block_height))
+}
+
+/// Returns the node at 0-based position `p` in the van Emde Boas layout of
+/// a complete tree with `tree_height` levels. The inverse of `to_veb()`.
+///
+/// Returns `None` if `p` is past the last node of the tree.
+///
+/// ## Panics
+/// Panics if `block_height` is zero.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::from_veb;
+///
+/// assert_eq!(from_veb(0, 4, 1), Some(7));
+/// assert_eq!(from_veb(14, 4, 1), Some(14));
+/// assert_eq!(from_veb(15, 4, 1), None);
+/// ```
+pub fn from_veb(
+  p: usize,
+  tree_height: usize,
+  block_height: usize,
+) -> Option<usize> {
+  assert!(block_height > 0, "The block height must be at least 1");
+  if tree_height >= usize::BITS as usize || p >= (1 << tree_height) - 1 {
+    return None;
+  }
+  let (level, offset) = veb_level_and_offset(p, tree_height, block_height);
+  from_level_and_offset(level, offset, tree_height)
+}
+
+/// Returns the van Emde Boas position of the node at `level` and `offset`
+/// in a complete tree with `height` levels.
+fn veb_position(
+  level: usize,
+  offset: usize,
+  height: usize,
+  block_height: usize,
+) -> usize {
+  if height <= block_height {
+    return (1 << level) - 1 + offset;
+  }
+  let top_height = height / 2;
+  if level < top_height {
+    return veb_position(level, offset, top_height, block_height);
+  }
+  let bottom_height = height - top_height;
+  let level = level - top_height;
+  let bottom = offset >> level;
+  let offset = offset & ((1 << level) - 1);
+  (1 << top_height) - 1
+    + bottom * ((1 << bottom_height) - 1)
+    + veb_position(level, offset, bottom_height, block_height)
+}
+
+/// The inverse of `veb_position()`.
+fn veb_level_and_offset(
+  p: usize,
+  height: usize,
+  block_height: usize,
+) -> (usize, usize) {
+  if height <= block_height {
+    let level = (usize::BITS - 1 - (p + 1).leading_zeros()) as usize;
+    return (level, p + 1 - (1 << level));
+  }
+  let top_height = height / 2;
+  let top_len = (1 << top_height) - 1;
+  if p < top_len {
+    return veb_level_and_offset(p, top_height, block_height);
+  }
+  let bottom_height = height - top_height;
+  let bottom_len = (1 << bottom_height) - 1;
+  let (bottom, p) = ((p - top_len) / bottom_len, (p - top_len) % bottom_len);
+  let (level, offset) = veb_level_and_offset(p, bottom_height, block_height);
+  (top_height + level, (bottom << level) | offset)
+}
+
+/// Returns the number of rows above the leaves of the Utreexo forest with
+/// `leaves` leaves, which is the height of the smallest perfect tree that
+/// holds every leaf.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::utreexo_rows;
+///
+/// assert_eq!(utreexo_rows(1), 0);
+/// assert_eq!(utreexo_rows(5), 3);
+/// assert_eq!(utreexo_rows(8), 3);
+/// ```
+#[inline]
+pub fn utreexo_rows(leaves: usize) -> usize {
+  leaves.next_power_of_two().trailing_zeros() as usize
+}
+
+/// Returns the position of node `i` in the Utreexo forest with `leaves`
+/// leaves. The forest numbers rows from the leaves up, each row left to
+/// right, and a row is as wide as it would be if the forest was a perfect
+/// tree, see `utreexo_rows()`. The row of a node is its depth, and its
+/// column is its offset.
+///
+/// Positions depend on the number of leaves, as the rows move up when the
+/// forest grows a row.
+///
+/// Returns `None` if the node isn't in the forest, or the forest's size
+/// doesn't fit in a `usize`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::to_utreexo;
+///
+/// // With 6 leaves, row 1 starts at 8, and row 2 at 12.
+/// assert_eq!(to_utreexo(10, 6), Some(5));
+/// assert_eq!(to_utreexo(9, 6), Some(10));
+/// assert_eq!(to_utreexo(3, 6), Some(12));
+/// assert_eq!(to_utreexo(11, 6), None);
+/// ```
+pub fn to_utreexo(i: usize, leaves: usize) -> Option<usize> {
+  if !in_tree(i, leaves) {
+    return None;
+  }
+  let depth = depth(i);
+  let offset = offset_with_depth(i, depth);
+  Some(utreexo_row_start(depth, utreexo_rows(leaves))? + offset)
+}
+
+/// Returns the node at position `pos` in the Utreexo forest with `leaves`
+/// leaves. The inverse of `to_utreexo()`.
+///
+/// Returns `None` if there's no node at that position.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::from_utreexo;
+///
+/// assert_eq!(from_utreexo(10, 6), Some(9));
+/// assert_eq!(from_utreexo(7, 6), None);
+/// assert_eq!(from_utreexo(14, 8), Some(7));
+/// ```
+pub fn from_utreexo(pos: usize, leaves: usize) -> Option<usize> {
+  let rows = utreexo_rows(leaves);
+  let mut row = 0;
+  while row < rows && utreexo_row_start(row + 1, rows)? <= pos {
+    row += 1;
+  }
+  let node = index(row, pos - utreexo_row_start(row, rows)?);
+  if in_tree(node, leaves) {
+    Some(node)
+  } else {
+    None
+  }
+}
+
+/// Returns the position of the first node of row `row` in a Utreexo forest
+/// with `rows` rows, or `None` if it doesn't fit in a `usize`.
+fn utreexo_row_start(row: usize, rows: usize) -> Option<usize> {
+  if rows + 1 >= usize::BITS as usize {
+    return None;
+  }
+  Some((1 << (rows + 1)) - (1 << (rows + 1 - row)))
+}
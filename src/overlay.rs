@@ -0,0 +1,107 @@
+//! Pending node writes on top of a `DenseTree`, applied all at once.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{DenseTree, Overlay};
+//!
+//! let mut tree = DenseTree::new();
+//! tree.set(0, "a");
+//!
+//! let mut overlay = Overlay::new(&mut tree);
+//! overlay.set(2, "b");
+//! assert_eq!(overlay.get(2), Some(&"b"));
+//! overlay.rollback();
+//! assert_eq!(tree.get(2), None);
+//!
+//! let mut overlay = Overlay::new(&mut tree);
+//! overlay.set(2, "b");
+//! overlay.commit();
+//! assert_eq!(tree.get(2), Some(&"b"));
+//! ```
+use super::*;
+
+use std::collections::BTreeMap;
+
+/// Node writes layered over a base `DenseTree`, which isn't changed until
+/// `commit()`. Reads see the pending writes first, then the base.
+///
+/// Useful to only accept a batch of nodes once all of them verify.
+#[derive(Debug)]
+#[must_use = "pending writes are dropped unless committed"]
+pub struct Overlay<'a, T: 'a> {
+  base: &'a mut DenseTree<T>,
+  pending: BTreeMap<usize, Option<T>>,
+}
+
+impl<'a, T: Clone> Overlay<'a, T> {
+  /// Start a batch of writes on top of `base`.
+  pub fn new(base: &'a mut DenseTree<T>) -> Self {
+    Self {
+      base,
+      pending: BTreeMap::new(),
+    }
+  }
+
+  /// Get the base tree, without the pending writes.
+  #[inline]
+  pub fn base(&self) -> &DenseTree<T> {
+    self.base
+  }
+
+  /// Get the number of pending writes.
+  #[inline]
+  pub fn pending(&self) -> usize {
+    self.pending.len()
+  }
+
+  /// Get the value of node `i`, including the pending writes.
+  pub fn get(&self, i: usize) -> Option<&T> {
+    match self.pending.get(&i) {
+      Some(value) => value.as_ref(),
+      None => self.base.get(i),
+    }
+  }
+
+  /// Check if node `i` has a value, including the pending writes.
+  #[inline]
+  pub fn contains(&self, i: usize) -> bool {
+    self.get(i).is_some()
+  }
+
+  /// Set the value of node `i`. Returns the previous value.
+  pub fn set(&mut self, i: usize, value: T) -> Option<T> {
+    let previous = self.get(i).cloned();
+    self.pending.insert(i, Some(value));
+    previous
+  }
+
+  /// Remove the value of node `i`. Returns the previous value.
+  pub fn remove(&mut self, i: usize) -> Option<T> {
+    let previous = self.get(i).cloned();
+    if previous.is_some() {
+      self.pending.insert(i, None);
+    }
+    previous
+  }
+
+  /// Apply the pending writes to the base tree.
+  pub fn commit(self) {
+    for (i, value) in self.pending {
+      match value {
+        Some(value) => self.base.set(i, value),
+        None => self.base.remove(i),
+      };
+    }
+  }
+
+  /// Drop the pending writes, leaving the base tree as it was.
+  pub fn rollback(self) {}
+}
+
+impl<'a, T: Clone> NodeStore for Overlay<'a, T> {
+  type Value = T;
+
+  fn node(&self, node: NodeIndex) -> Option<&T> {
+    self.get(node.0)
+  }
+}
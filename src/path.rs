@@ -0,0 +1,165 @@
+//! Evaluate sequences of named operations against flat-tree indices.
+//!
+//! ## Usage
+//! ```rust
+//! let index = flat_tree::apply_path(0, "parent,parent,left_child,sibling");
+//! assert_eq!(index, Ok(5));
+//! ```
+use super::*;
+
+use std::error;
+use std::fmt;
+use std::str;
+
+/// A single navigation step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+  /// Move to the parent, see `parent()`.
+  Parent,
+  /// Move to the sibling, see `sibling()`.
+  Sibling,
+  /// Move to the parent's sibling, see `uncle()`.
+  Uncle,
+  /// Move to the left child, see `left_child()`.
+  LeftChild,
+  /// Move to the right child, see `right_child()`.
+  RightChild,
+  /// Move to the left most node spanned, see `left_span()`.
+  LeftSpan,
+  /// Move to the right most node spanned, see `right_span()`.
+  RightSpan,
+}
+
+impl Op {
+  /// Apply the operation to an index.
+  ///
+  /// ## Errors
+  /// If the node is deeper than `Iterator::MAX_DEPTH`, or a parent or uncle
+  /// would be, as the result doesn't fit in a `usize`.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::{Op, PathError};
+  ///
+  /// assert_eq!(Op::Uncle.apply(0), Ok(5));
+  /// let err = PathError::OutOfRange(usize::MAX);
+  /// assert_eq!(Op::Sibling.apply(usize::MAX), Err(err));
+  /// ```
+  pub fn apply(self, i: usize) -> Result<usize, PathError> {
+    let depth = depth(i);
+    let max_depth = match self {
+      Op::Parent | Op::Uncle => Iterator::MAX_DEPTH - 1,
+      _ => Iterator::MAX_DEPTH,
+    };
+    if depth > max_depth {
+      return Err(PathError::OutOfRange(i));
+    }
+    match self {
+      Op::Parent => Ok(parent(i)),
+      Op::Sibling => Ok(sibling(i)),
+      Op::Uncle => Ok(uncle(i)),
+      Op::LeftChild => left_child(i).ok_or(PathError::NoChild(i)),
+      Op::RightChild => right_child(i).ok_or(PathError::NoChild(i)),
+      Op::LeftSpan => Ok(left_span(i)),
+      Op::RightSpan => Ok(right_span(i)),
+    }
+  }
+
+  /// Get the name of the operation, as accepted by `apply_path()`.
+  pub fn name(self) -> &'static str {
+    match self {
+      Op::Parent => "parent",
+      Op::Sibling => "sibling",
+      Op::Uncle => "uncle",
+      Op::LeftChild => "left_child",
+      Op::RightChild => "right_child",
+      Op::LeftSpan => "left_span",
+      Op::RightSpan => "right_span",
+    }
+  }
+}
+
+impl str::FromStr for Op {
+  type Err = PathError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "parent" => Ok(Op::Parent),
+      "sibling" => Ok(Op::Sibling),
+      "uncle" => Ok(Op::Uncle),
+      "left_child" => Ok(Op::LeftChild),
+      "right_child" => Ok(Op::RightChild),
+      "left_span" => Ok(Op::LeftSpan),
+      "right_span" => Ok(Op::RightSpan),
+      _ => Err(PathError::UnknownOp(s.to_string())),
+    }
+  }
+}
+
+impl fmt::Display for Op {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str(self.name())
+  }
+}
+
+/// Error returned when a path can't be evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+  /// The operation name isn't known.
+  UnknownOp(String),
+  /// A child was requested for a leaf node.
+  NoChild(usize),
+  /// The node, or the one moved to, is deeper than `Iterator::MAX_DEPTH`.
+  OutOfRange(usize),
+}
+
+impl fmt::Display for PathError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      PathError::UnknownOp(name) => write!(f, "unknown operation {:?}", name),
+      PathError::NoChild(i) => write!(f, "node {} has no children", i),
+      PathError::OutOfRange(i) => {
+        write!(f, "node {} is too deep to move from", i)
+      }
+    }
+  }
+}
+
+impl error::Error for PathError {}
+
+/// Applies a sequence of operations, starting at a node.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::{apply_ops, Op, PathError};
+///
+/// assert_eq!(apply_ops(0, &[Op::Parent, Op::Parent]), Ok(3));
+/// assert_eq!(apply_ops(3, &[Op::RightChild, Op::Sibling]), Ok(1));
+/// assert_eq!(apply_ops(2, &[Op::LeftChild]), Err(PathError::NoChild(2)));
+/// ```
+pub fn apply_ops(start: usize, ops: &[Op]) -> Result<usize, PathError> {
+  ops.iter().try_fold(start, |i, op| op.apply(i))
+}
+
+/// Applies a comma separated list of operation names, starting at a node.
+/// Whitespace around the names is ignored.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::{apply_path, PathError};
+///
+/// assert_eq!(apply_path(0, ""), Ok(0));
+/// assert_eq!(apply_path(0, "parent, parent, right_span"), Ok(6));
+/// assert_eq!(apply_path(23, "left_child,left_child,left_child"), Ok(16));
+/// assert_eq!(
+///   apply_path(0, "parent,grandparent"),
+///   Err(PathError::UnknownOp("grandparent".to_string()))
+/// );
+/// ```
+pub fn apply_path(start: usize, path: &str) -> Result<usize, PathError> {
+  path
+    .split(',')
+    .map(str::trim)
+    .filter(|name| !name.is_empty())
+    .try_fold(start, |i, name| name.parse::<Op>()?.apply(i))
+}
@@ -0,0 +1,154 @@
+//! Turn a want/have diff into an ordered list of requests.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{NodeSet, Planner, TreeIndex};
+//!
+//! let local = TreeIndex::new();
+//! let mut remote = TreeIndex::new();
+//! for leaf in &[0, 2, 4, 8] {
+//!   remote.set(*leaf);
+//! }
+//!
+//! let requests = Planner::new().plan(&local, &remote, &NodeSet::new());
+//! let nodes: Vec<_> = requests.iter().map(|request| request.node).collect();
+//! assert_eq!(nodes, [1, 4, 8]);
+//! ```
+use super::*;
+
+use std::fmt;
+
+/// How a `Planner` orders requests.
+#[derive(Clone, Copy)]
+pub enum Strategy<'a> {
+  /// Request the nodes covering the earliest leaves first. The priority of a
+  /// request is the first leaf it covers.
+  PrefixFirst,
+  /// Request the nodes held by the fewest peers first. The hook returns the
+  /// number of peers that have a node, which is used as the priority.
+  /// Nodes held by the same number of peers are requested in prefix order.
+  RarestFirst(&'a dyn Fn(usize) -> usize),
+}
+
+impl<'a> fmt::Debug for Strategy<'a> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Strategy::PrefixFirst => f.write_str("PrefixFirst"),
+      Strategy::RarestFirst(_) => f.write_str("RarestFirst(..)"),
+    }
+  }
+}
+
+/// A node to request from a remote peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Request {
+  /// The node to request, which covers its whole subtree.
+  pub node: usize,
+  /// The priority of the request, lower is more urgent. What it means
+  /// depends on the `Strategy`.
+  pub priority: usize,
+}
+
+/// Plans which nodes to request from a remote peer, and in which order.
+///
+/// The nodes come from `TreeIndex::want()`.
+#[derive(Debug, Clone, Copy)]
+pub struct Planner<'a> {
+  strategy: Strategy<'a>,
+  max_outstanding: usize,
+}
+
+impl<'a> Default for Planner<'a> {
+  fn default() -> Self {
+    Self {
+      strategy: Strategy::PrefixFirst,
+      max_outstanding: usize::MAX,
+    }
+  }
+}
+
+impl<'a> Planner<'a> {
+  /// Create a planner that requests every wanted node, in prefix order.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Set how requests are ordered.
+  pub fn strategy(mut self, strategy: Strategy<'a>) -> Self {
+    self.strategy = strategy;
+    self
+  }
+
+  /// Limit the number of requests in flight, including the ones already
+  /// sent.
+  pub fn max_outstanding(mut self, max_outstanding: usize) -> Self {
+    self.max_outstanding = max_outstanding;
+    self
+  }
+
+  /// Plan the requests to send to a remote peer, most urgent first. Nodes
+  /// in `in_flight` have already been requested, so are skipped and count
+  /// towards `max_outstanding()`.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::{NodeSet, Planner, Strategy, TreeIndex};
+  ///
+  /// let local = TreeIndex::new();
+  /// let mut remote = TreeIndex::new();
+  /// for leaf in &[0, 4, 8, 12] {
+  ///   remote.set(*leaf);
+  /// }
+  /// let peers = |node| if node == 8 { 1 } else { 3 };
+  /// let in_flight: NodeSet = vec![4].into_iter().collect();
+  ///
+  /// let requests = Planner::new()
+  ///   .strategy(Strategy::RarestFirst(&peers))
+  ///   .max_outstanding(3)
+  ///   .plan(&local, &remote, &in_flight);
+  /// let nodes: Vec<_> = requests.iter().map(|request| request.node).collect();
+  /// assert_eq!(nodes, [8, 0]);
+  /// ```
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+      level = "debug",
+      skip_all,
+      fields(
+        strategy = ?self.strategy,
+        max_outstanding = self.max_outstanding,
+        in_flight = in_flight.len()
+      )
+    )
+  )]
+  pub fn plan<B: Bitfield, R: Bitfield>(
+    &self,
+    local: &TreeIndex<B>,
+    remote: &TreeIndex<R>,
+    in_flight: &NodeSet,
+  ) -> Vec<Request> {
+    let mut requests: Vec<Request> = local
+      .want(remote)
+      .iter()
+      .filter(|node| !in_flight.contains(*node))
+      .map(|node| Request {
+        node,
+        priority: match self.strategy {
+          Strategy::PrefixFirst => left_span(node) / 2,
+          Strategy::RarestFirst(peers) => peers(node),
+        },
+      })
+      .collect();
+    requests.sort_by_key(|request| (request.priority, left_span(request.node)));
+    requests.truncate(self.max_outstanding.saturating_sub(in_flight.len()));
+    #[cfg(feature = "tracing")]
+    for request in &requests {
+      tracing::debug!(
+        node = request.node,
+        priority = request.priority,
+        "planned request"
+      );
+    }
+    requests
+  }
+}
@@ -0,0 +1,18 @@
+//! Convenience re-exports.
+//!
+//! The cursor is exported as `FlatTreeIter` here, so a glob import doesn't
+//! shadow `std::iter::Iterator`.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::prelude::*;
+//!
+//! let mut iter = FlatTreeIter::new(0);
+//! assert_eq!(iter.parent(), 1);
+//! let leaves: Vec<usize> = (0..3).map(|i| i * 2).collect();
+//! assert_eq!(leaves, [0, 2, 4]);
+//! ```
+pub use super::{
+  BoundedCursor, BoundedIter, Coords, Cursor, FlatTreeIndex, FlatTreeIter,
+  GenericIterator, Nodes, Op, Order, PathError,
+};
@@ -0,0 +1,105 @@
+//! Enumerate the leaves of a `TreeIndex` that are present or missing.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::TreeIndex;
+//!
+//! let mut tree = TreeIndex::new();
+//! tree.set(2);
+//! tree.set(6);
+//!
+//! let present: Vec<_> = tree.present_leaves().collect();
+//! assert_eq!(present, [2, 6]);
+//! let missing: Vec<_> = tree.missing_leaves().range(0..6).collect();
+//! assert_eq!(missing, [0, 4]);
+//! ```
+use super::*;
+
+use std::iter;
+use std::ops::Range;
+
+impl<B: Bitfield> TreeIndex<B> {
+  /// Returns an iterator over the leaves that are present, in increasing
+  /// index order.
+  pub fn present_leaves(&self) -> IndexLeaves<'_, B> {
+    IndexLeaves::new(self, true)
+  }
+
+  /// Returns an iterator over the leaves that are missing, in increasing
+  /// index order. By default it stops at `len()`, past which every leaf is
+  /// missing, use `IndexLeaves::range()` to go further.
+  pub fn missing_leaves(&self) -> IndexLeaves<'_, B> {
+    IndexLeaves::new(self, false)
+  }
+}
+
+/// Iterator over the leaves of a `TreeIndex` that are either present or
+/// missing.
+///
+/// Created by `TreeIndex::present_leaves()` and
+/// `TreeIndex::missing_leaves()`.
+#[derive(Debug)]
+pub struct IndexLeaves<'a, B: 'a> {
+  tree: &'a TreeIndex<B>,
+  present: bool,
+  next: usize,
+  end: usize,
+}
+
+impl<'a, B: Bitfield> IndexLeaves<'a, B> {
+  fn new(tree: &'a TreeIndex<B>, present: bool) -> Self {
+    Self {
+      tree,
+      present,
+      next: 0,
+      end: tree.len(),
+    }
+  }
+
+  /// Only yield the leaves in `range`, which is a range of indices, not of
+  /// leaf numbers.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let tree = flat_tree::TreeIndex::new();
+  /// let missing: Vec<_> = tree.missing_leaves().range(3..9).collect();
+  /// assert_eq!(missing, [4, 6, 8]);
+  /// ```
+  pub fn range(mut self, range: Range<usize>) -> Self {
+    self.next = range.start + range.start % 2;
+    self.end = range.end;
+    self
+  }
+}
+
+impl<'a, B> Clone for IndexLeaves<'a, B> {
+  fn clone(&self) -> Self {
+    Self {
+      tree: self.tree,
+      present: self.present,
+      next: self.next,
+      end: self.end,
+    }
+  }
+}
+
+impl<'a, B: Bitfield> iter::Iterator for IndexLeaves<'a, B> {
+  type Item = usize;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while self.next < self.end {
+      let leaf = self.next;
+      self.next += 2;
+      if self.tree.get(leaf) == self.present {
+        return Some(leaf);
+      }
+    }
+    None
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, Some(self.end.saturating_sub(self.next).div_ceil(2)))
+  }
+}
+
+impl<'a, B: Bitfield> iter::FusedIterator for IndexLeaves<'a, B> {}
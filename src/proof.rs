@@ -0,0 +1,260 @@
+//! Compute which nodes a merkle proof is made of.
+//!
+//! ## Usage
+//! ```rust
+//! let proof = flat_tree::multiproof(&[0, 2], 4);
+//! assert_eq!(proof.as_slice(), [5]);
+//! ```
+use super::*;
+
+use std::error;
+use std::fmt;
+
+/// Returns the nodes needed, in addition to `nodes`, to verify all of
+/// `nodes` at once against the roots of a tree with `leaves` leaves.
+///
+/// This is the union of the audit paths of `nodes`, see `audit_path()`,
+/// without the nodes that can be computed from `nodes` and the rest of the
+/// proof. Nodes that aren't in the tree are ignored.
+///
+/// ## Examples
+/// ```rust
+/// let proof = flat_tree::multiproof(&[0, 6], 8);
+/// assert_eq!(proof.as_slice(), [2, 4, 11]);
+///
+/// let proof = flat_tree::multiproof(&[2, 4], 6);
+/// assert_eq!(proof.as_slice(), [0, 6]);
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+pub fn multiproof(nodes: &[usize], leaves: usize) -> NodeSet {
+  let mut computed = NodeSet::new();
+  for &node in nodes {
+    if in_tree(node, leaves) {
+      computed.insert(node);
+      computed.extend(ancestors(node).within(leaves));
+    }
+  }
+
+  let mut proof = NodeSet::new();
+  for &node in nodes {
+    for sibling in audit_path(node, leaves) {
+      if !computed.contains(sibling) {
+        proof.insert(sibling);
+      }
+    }
+  }
+  #[cfg(feature = "tracing")]
+  tracing::debug!(proof = ?proof.as_slice(), "proof nodes");
+  proof
+}
+
+/// Returns the nodes needed to verify `node` against a node that's already
+/// trusted, such as a root or subtree verified earlier. This is the audit
+/// path of `node` up to the nearest trusted ancestor, from the bottom up,
+/// without the siblings that are trusted themselves.
+///
+/// Returns `None` if no ancestor of `node`, or `node` itself, is trusted.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::{needed_nodes, NodeSet};
+///
+/// let trusted: NodeSet = vec![3, 9].into_iter().collect();
+/// assert_eq!(needed_nodes(0, &trusted), Some(vec![2, 5]));
+/// assert_eq!(needed_nodes(10, &trusted), Some(vec![8]));
+/// assert_eq!(needed_nodes(12, &trusted), None);
+/// ```
+#[cfg_attr(
+  feature = "tracing",
+  tracing::instrument(
+    level = "debug",
+    skip(trusted),
+    fields(trusted = trusted.len()),
+    ret
+  )
+)]
+pub fn needed_nodes(node: usize, trusted: &NodeSet) -> Option<Vec<usize>> {
+  let max_depth = trusted.iter().map(depth).max()?;
+  let mut nodes = Vec::new();
+  let mut iter = Iterator::new(node);
+  while !trusted.contains(iter.index()) {
+    if iter.depth() >= max_depth {
+      return None;
+    }
+    let sibling = iter.peek_sibling();
+    if !trusted.contains(sibling) {
+      nodes.push(sibling);
+    }
+    iter.try_parent()?;
+  }
+  Some(nodes)
+}
+
+/// Error returned when a proof doesn't verify.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyError {
+  /// A proof node isn't the sibling of the node computed so far.
+  NotSibling {
+    /// The sibling that was expected.
+    expected: usize,
+    /// The node given instead.
+    got: usize,
+  },
+  /// The proof ends at a different node than the expected root.
+  WrongRoot {
+    /// The expected root.
+    expected: usize,
+    /// The node the proof ends at.
+    got: usize,
+  },
+}
+
+impl fmt::Display for VerifyError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      VerifyError::NotSibling { expected, got } => {
+        write!(f, "expected sibling {}, got node {}", expected, got)
+      }
+      VerifyError::WrongRoot { expected, got } => {
+        write!(f, "expected proof to end at {}, ended at {}", expected, got)
+      }
+    }
+  }
+}
+
+impl error::Error for VerifyError {}
+
+/// Hash agnostic proof verifier, that keeps track of which node the values
+/// being combined belong to.
+///
+/// Start with a node and its value, then push the siblings on its audit
+/// path from the bottom up. Each push combines the values of two siblings,
+/// in left to right order, into the value of their parent.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::Verifier;
+///
+/// let combine = |parent: usize, left: &String, right: &String| {
+///   format!("{}({},{})", parent, left, right)
+/// };
+/// let mut verifier = Verifier::new(2, "b".to_string());
+/// assert_eq!(verifier.push(0, "a".to_string(), combine), Ok(1));
+/// assert_eq!(verifier.push(5, "cd".to_string(), combine), Ok(3));
+/// assert_eq!(verifier.finish(3).unwrap(), "3(1(a,b),cd)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verifier<T> {
+  index: usize,
+  value: T,
+}
+
+impl<T> Verifier<T> {
+  /// Start verifying `node`, which has value `value`.
+  pub fn new(node: usize, value: T) -> Self {
+    Self { index: node, value }
+  }
+
+  /// Get the node the current value belongs to.
+  #[inline]
+  pub fn index(&self) -> usize {
+    self.index
+  }
+
+  /// Get the current value.
+  #[inline]
+  pub fn value(&self) -> &T {
+    &self.value
+  }
+
+  /// Combine the current value with the value of the sibling of the current
+  /// node. `combine` is called with the index of the parent, and the values
+  /// of its left and right child. Returns the index of the parent, which is
+  /// the new current node.
+  ///
+  /// ## Errors
+  /// If `sibling` isn't the sibling of the current node. The verifier isn't
+  /// changed in that case.
+  pub fn push<F>(
+    &mut self,
+    sibling: usize,
+    value: T,
+    combine: F,
+  ) -> Result<usize, VerifyError>
+  where
+    F: FnOnce(usize, &T, &T) -> T,
+  {
+    let expected = super::sibling(self.index);
+    if sibling != expected {
+      return Err(VerifyError::NotSibling {
+        expected,
+        got: sibling,
+      });
+    }
+    let parent = parent(self.index);
+    self.value = if sibling < self.index {
+      combine(parent, &value, &self.value)
+    } else {
+      combine(parent, &self.value, &value)
+    };
+    self.index = parent;
+    Ok(parent)
+  }
+
+  /// Finish verifying, returning the computed value of `root`.
+  ///
+  /// ## Errors
+  /// If the current node isn't `root`.
+  pub fn finish(self, root: usize) -> Result<T, VerifyError> {
+    if self.index != root {
+      return Err(VerifyError::WrongRoot {
+        expected: root,
+        got: self.index,
+      });
+    }
+    Ok(self.value)
+  }
+}
+
+/// Verify a proof made of the siblings on the audit path of `node`, from
+/// the bottom up, with their values. Returns the computed value of `root`.
+/// See `Verifier` for how `combine` is called.
+///
+/// ## Errors
+/// If a proof node isn't the sibling of the node computed so far, or the
+/// proof doesn't end at `root`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::{audit_path, verify_proof, VerifyError};
+///
+/// let sum = |_: usize, left: &u32, right: &u32| left + right;
+/// let proof = audit_path(0, 8).map(|node| (node, 1));
+/// assert_eq!(verify_proof(0, 1, proof, 7, sum), Ok(4));
+///
+/// let proof = audit_path(0, 8).map(|node| (node, 1));
+/// assert_eq!(
+///   verify_proof(0, 1, proof, 15, sum),
+///   Err(VerifyError::WrongRoot {
+///     expected: 15,
+///     got: 7
+///   })
+/// );
+/// ```
+pub fn verify_proof<T, I, F>(
+  node: usize,
+  value: T,
+  proof: I,
+  root: usize,
+  mut combine: F,
+) -> Result<T, VerifyError>
+where
+  I: IntoIterator<Item = (usize, T)>,
+  F: FnMut(usize, &T, &T) -> T,
+{
+  let mut verifier = Verifier::new(node, value);
+  for (sibling, value) in proof {
+    verifier.push(sibling, value, &mut combine)?;
+  }
+  verifier.finish(root)
+}
@@ -0,0 +1,339 @@
+//! Python bindings, behind the `python` feature.
+//!
+//! The `flat_tree` Python module has the index functions, an `Iterator`
+//! class and a `TreeIndex` class. The functions that take a list of nodes,
+//! such as `parents()`, run without holding the GIL, which is much faster
+//! than calling the single node functions in a loop.
+//!
+//! Build the extension with
+//! `cargo rustc --release --features python --crate-type cdylib`, and
+//! rename the library to `flat_tree.so`, or `flat_tree.pyd` on Windows.
+//!
+//! ```python
+//! import flat_tree
+//!
+//! assert flat_tree.parent(0) == 1
+//! assert flat_tree.parents([0, 2, 5]) == [1, 1, 3]
+//!
+//! tree = flat_tree.TreeIndex()
+//! tree.set_many([0, 2])
+//! assert tree.get(1)
+//! ```
+use super::*;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// See `index()`.
+#[pyfunction(name = "index")]
+fn py_index(depth: usize, offset: usize) -> usize {
+  index(depth, offset)
+}
+
+/// See `depth()`.
+#[pyfunction(name = "depth")]
+fn py_depth(i: usize) -> usize {
+  depth(i)
+}
+
+/// See `offset()`.
+#[pyfunction(name = "offset")]
+fn py_offset(i: usize) -> usize {
+  offset(i)
+}
+
+/// See `parent()`.
+#[pyfunction(name = "parent")]
+fn py_parent(i: usize) -> usize {
+  parent(i)
+}
+
+/// See `sibling()`.
+#[pyfunction(name = "sibling")]
+fn py_sibling(i: usize) -> usize {
+  sibling(i)
+}
+
+/// See `uncle()`.
+#[pyfunction(name = "uncle")]
+fn py_uncle(i: usize) -> usize {
+  uncle(i)
+}
+
+/// See `children()`.
+#[pyfunction(name = "children")]
+fn py_children(i: usize) -> Option<(usize, usize)> {
+  children(i)
+}
+
+/// See `left_span()`.
+#[pyfunction(name = "left_span")]
+fn py_left_span(i: usize) -> usize {
+  left_span(i)
+}
+
+/// See `right_span()`.
+#[pyfunction(name = "right_span")]
+fn py_right_span(i: usize) -> usize {
+  right_span(i)
+}
+
+/// See `spans()`.
+#[pyfunction(name = "spans")]
+fn py_spans(i: usize) -> (usize, usize) {
+  spans(i)
+}
+
+/// See `count()`.
+#[pyfunction(name = "count")]
+fn py_count(i: usize) -> usize {
+  count(i)
+}
+
+/// See `full_roots()`. Raises `ValueError` if `i` isn't a leaf.
+#[pyfunction(name = "full_roots")]
+fn py_full_roots(i: usize) -> PyResult<Vec<usize>> {
+  if !is_even(i) {
+    return Err(PyValueError::new_err(format!(
+      "You can only look up roots for depth 0 blocks, got index {}",
+      i
+    )));
+  }
+  let mut roots = Vec::new();
+  full_roots(i, &mut roots);
+  Ok(roots)
+}
+
+/// Define a function applying a single node function to a list of nodes,
+/// without holding the GIL.
+macro_rules! batch {
+  ($($(#[$doc:meta])* $name:ident => $fn:ident;)*) => {
+    $(
+      $(#[$doc])*
+      #[pyfunction]
+      fn $name(py: Python, nodes: Vec<usize>) -> Vec<usize> {
+        py.detach(|| nodes.into_iter().map($fn).collect())
+      }
+    )*
+  };
+}
+
+batch! {
+  /// The parent of every node, see `parent()`.
+  parents => parent;
+  /// The sibling of every node, see `sibling()`.
+  siblings => sibling;
+  /// The depth of every node, see `depth()`.
+  depths => depth;
+  /// The offset of every node, see `offset()`.
+  offsets => offset;
+  /// The left span of every node, see `left_span()`.
+  left_spans => left_span;
+  /// The right span of every node, see `right_span()`.
+  right_spans => right_span;
+}
+
+/// The index of every pair of depth and offset, see `index()`.
+#[pyfunction]
+fn indices(py: Python, nodes: Vec<(usize, usize)>) -> Vec<usize> {
+  py.detach(|| {
+    nodes
+      .into_iter()
+      .map(|(depth, offset)| index(depth, offset))
+      .collect()
+  })
+}
+
+/// A cursor over a flat-tree, see `Iterator`.
+#[pyclass(name = "Iterator")]
+#[derive(Debug, Clone)]
+pub struct PyIterator {
+  iter: Iterator,
+}
+
+#[pymethods]
+impl PyIterator {
+  #[new]
+  #[pyo3(signature = (index = 0))]
+  fn new(index: usize) -> Self {
+    Self {
+      iter: Iterator::new(index),
+    }
+  }
+
+  fn index(&self) -> usize {
+    self.iter.index()
+  }
+
+  fn offset(&self) -> usize {
+    self.iter.offset()
+  }
+
+  fn factor(&self) -> usize {
+    self.iter.factor()
+  }
+
+  fn depth(&self) -> usize {
+    self.iter.depth()
+  }
+
+  fn seek(&mut self, index: usize) {
+    self.iter.seek(index);
+  }
+
+  fn is_left(&self) -> bool {
+    self.iter.is_left()
+  }
+
+  fn is_right(&self) -> bool {
+    self.iter.is_right()
+  }
+
+  fn contains(&self, index: usize) -> bool {
+    self.iter.contains(index)
+  }
+
+  /// Move to the next node at the same depth, or return `None` without
+  /// moving past the last index.
+  fn next(&mut self) -> Option<usize> {
+    std::iter::Iterator::next(&mut self.iter)
+  }
+
+  /// Move to the previous node at the same depth, or return `None` without
+  /// moving on the left most node.
+  fn prev(&mut self) -> Option<usize> {
+    self.iter.try_prev()
+  }
+
+  fn sibling(&mut self) -> usize {
+    self.iter.sibling()
+  }
+
+  /// Move to the parent, or return `None` without moving at `MAX_DEPTH`.
+  fn parent(&mut self) -> Option<usize> {
+    self.iter.try_parent()
+  }
+
+  /// Move to the left child, or return `None` without moving on a leaf.
+  fn left_child(&mut self) -> Option<usize> {
+    self.iter.try_left_child()
+  }
+
+  /// Move to the right child, or return `None` without moving on a leaf.
+  fn right_child(&mut self) -> Option<usize> {
+    self.iter.try_right_child()
+  }
+
+  fn left_span(&mut self) -> usize {
+    self.iter.left_span()
+  }
+
+  fn right_span(&mut self) -> usize {
+    self.iter.right_span()
+  }
+
+  fn full_root(&mut self, index: usize) -> bool {
+    self.iter.full_root(index)
+  }
+
+  fn __repr__(&self) -> String {
+    format!("Iterator({})", self.iter.index())
+  }
+}
+
+/// The presence of the nodes of a flat-tree, see `TreeIndex`.
+#[pyclass(name = "TreeIndex")]
+#[derive(Debug, Clone, Default)]
+pub struct PyTreeIndex {
+  tree: TreeIndex,
+}
+
+#[pymethods]
+impl PyTreeIndex {
+  #[new]
+  fn new() -> Self {
+    Self::default()
+  }
+
+  fn get(&self, index: usize) -> bool {
+    self.tree.get(index)
+  }
+
+  fn set(&mut self, index: usize) -> bool {
+    self.tree.set(index)
+  }
+
+  /// Set every node in `nodes`, without holding the GIL. Returns how many
+  /// weren't present yet.
+  fn set_many(&mut self, py: Python, nodes: Vec<usize>) -> usize {
+    let tree = &mut self.tree;
+    py.detach(|| nodes.into_iter().filter(|&i| tree.set(i)).count())
+  }
+
+  /// Check every node in `nodes`, without holding the GIL.
+  fn get_many(&self, py: Python, nodes: Vec<usize>) -> Vec<bool> {
+    let tree = &self.tree;
+    py.detach(|| nodes.into_iter().map(|i| tree.get(i)).collect())
+  }
+
+  fn digest(&self, index: usize) -> usize {
+    self.tree.digest(index)
+  }
+
+  fn blocks(&self) -> usize {
+    self.tree.blocks()
+  }
+
+  fn roots(&self) -> Vec<usize> {
+    self.tree.roots()
+  }
+
+  fn verified_by(&self, index: usize) -> usize {
+    self.tree.verified_by(index)
+  }
+
+  /// See `TreeIndex::proof()`. Returns the nodes and `verified_by`, or
+  /// `None` if `index` isn't present.
+  #[pyo3(signature = (index, digest = 0, hash = false))]
+  fn proof(
+    &self,
+    index: usize,
+    digest: usize,
+    hash: bool,
+  ) -> Option<(Vec<usize>, usize)> {
+    let opts = ProofOptions::new().digest(digest).hash(hash);
+    let proof = self.tree.proof(index, opts)?;
+    Some((proof.nodes, proof.verified_by))
+  }
+
+  fn __len__(&self) -> usize {
+    self.tree.len()
+  }
+}
+
+/// The `flat_tree` Python module.
+#[pymodule]
+pub fn flat_tree(m: &Bound<PyModule>) -> PyResult<()> {
+  m.add_function(wrap_pyfunction!(self::py_index, m)?)?;
+  m.add_function(wrap_pyfunction!(self::py_depth, m)?)?;
+  m.add_function(wrap_pyfunction!(self::py_offset, m)?)?;
+  m.add_function(wrap_pyfunction!(self::py_parent, m)?)?;
+  m.add_function(wrap_pyfunction!(self::py_sibling, m)?)?;
+  m.add_function(wrap_pyfunction!(self::py_uncle, m)?)?;
+  m.add_function(wrap_pyfunction!(self::py_children, m)?)?;
+  m.add_function(wrap_pyfunction!(self::py_left_span, m)?)?;
+  m.add_function(wrap_pyfunction!(self::py_right_span, m)?)?;
+  m.add_function(wrap_pyfunction!(self::py_spans, m)?)?;
+  m.add_function(wrap_pyfunction!(self::py_count, m)?)?;
+  m.add_function(wrap_pyfunction!(self::py_full_roots, m)?)?;
+  m.add_function(wrap_pyfunction!(self::parents, m)?)?;
+  m.add_function(wrap_pyfunction!(self::siblings, m)?)?;
+  m.add_function(wrap_pyfunction!(self::depths, m)?)?;
+  m.add_function(wrap_pyfunction!(self::offsets, m)?)?;
+  m.add_function(wrap_pyfunction!(self::left_spans, m)?)?;
+  m.add_function(wrap_pyfunction!(self::right_spans, m)?)?;
+  m.add_function(wrap_pyfunction!(self::indices, m)?)?;
+  m.add_class::<PyIterator>()?;
+  m.add_class::<PyTreeIndex>()?;
+  Ok(())
+}
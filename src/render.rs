@@ -0,0 +1,346 @@
+//! Draw flat trees as text, for logs and error messages.
+//!
+//! ## Usage
+//! ```rust
+//! assert_eq!(
+//!   flat_tree::render_ascii(4),
+//!   "      3\n  1       5\n0   2   4   6\n"
+//! );
+//! ```
+use super::*;
+
+use highlight::marker;
+
+use std::cmp;
+use std::iter;
+
+/// Draw a tree with `leaves` leaves, with the leaves on the bottom row and
+/// each parent on the row above its children:
+///
+/// ```text
+///       3
+///   1       5
+/// 0   2   4   6
+/// ```
+///
+/// Each index is drawn at a column proportional to its value, so parents
+/// sit between their children. Only nodes whose leaves are all in the tree
+/// are drawn, so a tree with a length that's not a power of two is drawn as
+/// the forest of its full roots.
+///
+/// ## Examples
+/// ```rust
+/// assert_eq!(flat_tree::render_ascii(0), "");
+/// assert_eq!(flat_tree::render_ascii(3), "  1\n0   2   4\n");
+/// ```
+pub fn render_ascii(leaves: usize) -> String {
+  if leaves == 0 {
+    return String::new();
+  }
+  let last = 2 * leaves - 2;
+  let width = cmp::max(2, last.to_string().len() + 1);
+  let top = (usize::BITS - 1 - leaves.leading_zeros()) as usize;
+
+  let mut out = String::new();
+  for depth in (0..=top).rev() {
+    let mut line = String::new();
+    for i in nodes_at(depth, last) {
+      let column = i * width;
+      line.extend((line.len()..column).map(|_| ' '));
+      line.push_str(&i.to_string());
+    }
+    out.push_str(&line);
+    out.push('\n');
+  }
+  out
+}
+
+/// Returns the nodes at `depth` whose leaves are all at or before `last`.
+fn nodes_at(depth: usize, last: usize) -> impl iter::Iterator<Item = usize> {
+  (0..)
+    .map(move |offset| index(depth, offset))
+    .take_while(move |&i| right_span(i) <= last)
+}
+
+/// The direction a tree grows in when drawn by `render_unicode()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Orientation {
+  /// The roots on the top row and the leaves on the bottom row, like
+  /// `render_ascii()`.
+  #[default]
+  TopDown,
+  /// One node per line, with the roots on the left and each node's children
+  /// indented below it, left child first.
+  LeftRight,
+}
+
+/// Options for `render_unicode()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RenderOptions<'a> {
+  orientation: Orientation,
+  max_width: Option<usize>,
+  min_depth: usize,
+  highlights: Option<&'a Highlights<'a>>,
+}
+
+impl<'a> RenderOptions<'a> {
+  /// Create the default options: draw top down, every node, with no limit
+  /// on the width.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Set the direction the tree grows in.
+  pub fn orientation(mut self, orientation: Orientation) -> Self {
+    self.orientation = orientation;
+    self
+  }
+
+  /// Limit the number of characters in a line. A top down tree elides
+  /// more of its lower levels until it fits, see `min_depth()`. A left to
+  /// right tree cuts longer lines short, ending them with `…`.
+  pub fn max_width(mut self, max_width: usize) -> Self {
+    self.max_width = Some(max_width);
+    self
+  }
+
+  /// Elide the nodes below `min_depth`. The subtrees rooted at that depth
+  /// are drawn as their root, marked with `…`.
+  pub fn min_depth(mut self, min_depth: usize) -> Self {
+    self.min_depth = min_depth;
+    self
+  }
+
+  /// Mark the nodes of each highlighted set with the set's marker after
+  /// the index, and list the markers after the tree. Notes follow the node
+  /// in a left to right tree, and are listed after a top down tree.
+  pub fn highlights(mut self, highlights: &'a Highlights<'a>) -> Self {
+    self.highlights = Some(highlights);
+    self
+  }
+
+  /// Get the index of `i` with the markers of the sets containing it.
+  fn marked(&self, i: usize) -> String {
+    let mut label = i.to_string();
+    if let Some(highlights) = self.highlights {
+      label.extend(highlights.containing(i).into_iter().map(marker));
+    }
+    label
+  }
+
+  /// Get the lines listing the notes of `nodes`, if `notes` is set, and the
+  /// markers of the sets.
+  fn footer(&self, nodes: &[usize], notes: bool) -> Vec<String> {
+    let highlights = match self.highlights {
+      Some(highlights) => highlights,
+      None => return Vec::new(),
+    };
+    let mut lines = Vec::new();
+    if notes {
+      for &i in nodes {
+        if let Some(note) = highlights.note(i) {
+          lines.push(format!("{}: {}", i, note));
+        }
+      }
+    }
+    for (pos, label) in highlights.labels().enumerate() {
+      lines.push(format!("{} {}", marker(pos), label));
+    }
+    if !lines.is_empty() {
+      lines.insert(0, String::new());
+    }
+    lines
+  }
+}
+
+/// Draw a tree with `leaves` leaves like `render_ascii()`, with box drawing
+/// characters connecting each parent to its children.
+///
+/// As with `render_ascii()`, only nodes whose leaves are all in the tree
+/// are drawn.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::{render_unicode, Orientation, RenderOptions};
+///
+/// assert_eq!(
+///   render_unicode(4, RenderOptions::new()),
+///   "  ┌───3───┐\n┌─1─┐   ┌─5─┐\n0   2   4   6\n"
+/// );
+///
+/// let opts = RenderOptions::new().orientation(Orientation::LeftRight);
+/// assert_eq!(render_unicode(2, opts), "1\n├── 0\n└── 2\n");
+///
+/// let opts = RenderOptions::new().min_depth(1);
+/// assert_eq!(render_unicode(4, opts), "┌─3─┐\n1   5\n…   …\n");
+/// ```
+pub fn render_unicode(leaves: usize, opts: RenderOptions) -> String {
+  if leaves == 0 {
+    return String::new();
+  }
+  let mut roots = Vec::new();
+  full_roots(2 * leaves, &mut roots);
+  match opts.orientation {
+    Orientation::TopDown => top_down(leaves, &roots, opts),
+    Orientation::LeftRight => left_right(&roots, opts),
+  }
+}
+
+fn top_down(leaves: usize, roots: &[usize], opts: RenderOptions) -> String {
+  let top = depth(roots[0]);
+  let sets = opts.highlights.map_or(0, |highlights| highlights.len());
+  let width = (2 * leaves - 2).to_string().len() + sets + 1;
+
+  // Elide more levels until the bottom row fits, see `bottom_len()`.
+  let mut min_depth = cmp::min(opts.min_depth, top);
+  if let Some(max_width) = opts.max_width {
+    while min_depth < top
+      && (bottom_len(leaves, min_depth) - 1) * 2 * width + width > max_width
+    {
+      min_depth += 1;
+    }
+  }
+
+  let mut bottom = Vec::new();
+  let mut columns = Vec::new();
+  for &root in roots {
+    place(root, min_depth, width, &mut bottom, &mut columns);
+  }
+  columns.sort_unstable();
+  let line_len =
+    columns.iter().map(|&(_, column)| column).max().unwrap_or(0) + width;
+  let mut lines = vec![vec![' '; line_len]; top - min_depth + 1];
+  let column = |i: usize| {
+    let pos = columns.binary_search_by_key(&i, |&(node, _)| node).unwrap();
+    columns[pos].1
+  };
+
+  for &(i, at) in &columns {
+    let bottom_row = bottom.binary_search(&i).is_ok();
+    let row = if bottom_row {
+      top - min_depth
+    } else {
+      top - depth(i)
+    };
+    let line = &mut lines[row];
+    if !bottom_row {
+      let (left, right) = children(i).unwrap();
+      let (from, to) = (column(left), column(right));
+      line[from] = '┌';
+      for c in &mut line[from + 1..to] {
+        *c = '─';
+      }
+      line[to] = '┐';
+    }
+    for (c, label) in line[at..].iter_mut().zip(opts.marked(i).chars()) {
+      *c = label;
+    }
+  }
+
+  if bottom.iter().any(|&i| depth(i) > 0) {
+    let mut line = vec![' '; line_len];
+    for &i in bottom.iter().filter(|&&i| depth(i) > 0) {
+      line[column(i)] = '…';
+    }
+    lines.push(line);
+  }
+
+  let mut out = String::new();
+  for line in lines {
+    let line: String = line.into_iter().collect();
+    out.push_str(line.trim_end());
+    out.push('\n');
+  }
+  let nodes: Vec<usize> = columns.iter().map(|&(i, _)| i).collect();
+  for line in opts.footer(&nodes, true) {
+    out.push_str(&line);
+    out.push('\n');
+  }
+  out
+}
+
+/// The number of nodes on the bottom row of a top down tree elided below
+/// `min_depth`: the nodes at that depth, and the smaller roots after them.
+fn bottom_len(leaves: usize, min_depth: usize) -> usize {
+  (leaves >> min_depth)
+    + (leaves & ((1 << min_depth) - 1)).count_ones() as usize
+}
+
+/// Assign a column to `i` and the nodes below it, down to `min_depth`. The
+/// nodes on the bottom row are spaced evenly, and each parent sits halfway
+/// between its children.
+fn place(
+  i: usize,
+  min_depth: usize,
+  width: usize,
+  bottom: &mut Vec<usize>,
+  columns: &mut Vec<(usize, usize)>,
+) -> usize {
+  let column = if depth(i) <= min_depth {
+    let column = bottom.len() * 2 * width;
+    bottom.push(i);
+    column
+  } else {
+    let (left, right) = children(i).unwrap();
+    let left = place(left, min_depth, width, bottom, columns);
+    let right = place(right, min_depth, width, bottom, columns);
+    (left + right) / 2
+  };
+  columns.push((i, column));
+  column
+}
+
+fn left_right(roots: &[usize], opts: RenderOptions) -> String {
+  let mut lines = Vec::new();
+  for &root in roots {
+    lines.push(label(root, opts));
+    branches(root, String::new(), opts, &mut lines);
+  }
+  lines.extend(opts.footer(&[], false));
+
+  let mut out = String::new();
+  for line in lines {
+    match opts.max_width {
+      Some(max_width) if line.chars().count() > max_width => {
+        let cut: String =
+          line.chars().take(max_width.saturating_sub(1)).collect();
+        out.push_str(&cut);
+        out.push('…');
+      }
+      _ => out.push_str(&line),
+    }
+    out.push('\n');
+  }
+  out
+}
+
+/// Draw the children of `i`, and their children, one per line.
+fn branches(
+  i: usize,
+  prefix: String,
+  opts: RenderOptions,
+  lines: &mut Vec<String>,
+) {
+  if depth(i) <= opts.min_depth {
+    return;
+  }
+  let (left, right) = children(i).unwrap();
+  lines.push(format!("{}├── {}", prefix, label(left, opts)));
+  branches(left, format!("{}│   ", prefix), opts, lines);
+  lines.push(format!("{}└── {}", prefix, label(right, opts)));
+  branches(right, format!("{}    ", prefix), opts, lines);
+}
+
+/// The index of `i` with its markers, marked with `…` if its children are
+/// elided, and followed by its note.
+fn label(i: usize, opts: RenderOptions) -> String {
+  let mut label = opts.marked(i);
+  if depth(i) > 0 && depth(i) <= opts.min_depth {
+    label.push_str(" …");
+  }
+  if let Some(note) = opts.highlights.and_then(|h| h.note(i)) {
+    label.push_str("  ");
+    label.push_str(&note);
+  }
+  label
+}
@@ -0,0 +1,169 @@
+//! Run-length encoding of bitfields, compatible with the `bitfield-rle`
+//! module used by hypercore.
+//!
+//! The encoding is a sequence of chunks, each starting with an unsigned
+//! LEB128 varint header:
+//!
+//! - If bit `0` of the header is set, the chunk is a run of `header >> 2`
+//!   bytes that are all `0xff` if bit `1` is set, or all `0x00` otherwise.
+//! - Otherwise the header is followed by `header >> 1` bytes copied as is.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::rle;
+//!
+//! let bits = [0xff; 64];
+//! let encoded = rle::encode(&bits);
+//! assert_eq!(encoded, [0x83, 0x02]);
+//! assert_eq!(rle::decode(&encoded).unwrap(), &bits[..]);
+//! ```
+use super::*;
+
+/// The shortest run of `0x00` or `0xff` bytes that's encoded as a run, rather
+/// than copied.
+const MIN_RUN: usize = 4;
+
+/// The largest bitfield `decode()` decodes, in bytes: a tree with `2^32`
+/// leaves.
+pub const MAX_DECODED_LEN: usize = 1 << 30;
+
+/// Encode a bitfield.
+///
+/// ## Examples
+/// ```rust
+/// let encoded = flat_tree::rle::encode(&[0, 0, 0, 0, 0, 0x0f, 0xff]);
+/// assert_eq!(encoded, [0b10101, 0b100, 0x0f, 0xff]);
+/// ```
+pub fn encode(bits: &[u8]) -> Vec<u8> {
+  let mut out = Vec::new();
+  let mut literal = 0;
+  let mut i = 0;
+  while i < bits.len() {
+    let run = run_len(&bits[i..]);
+    if run >= MIN_RUN || (run > 0 && i + run == bits.len() && literal == i) {
+      write_literal(&mut out, &bits[literal..i]);
+      let fill = if bits[i] == 0xff { 0b10 } else { 0 };
+      write_varint(&mut out, (run as u64) << 2 | fill | 1);
+      i += run;
+      literal = i;
+    } else {
+      i += 1;
+    }
+  }
+  write_literal(&mut out, &bits[literal..]);
+  out
+}
+
+/// Decode a bitfield of at most `MAX_DECODED_LEN` bytes, see
+/// `decode_max()`.
+///
+/// ## Errors
+/// If the input ends in the middle of a chunk, or the bitfield is larger
+/// than `MAX_DECODED_LEN` bytes.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::{rle, DecodeError};
+///
+/// assert_eq!(rle::decode(&[0b10101, 0b100, 0x0f, 0xff]).unwrap().len(), 7);
+/// assert_eq!(rle::decode(&[0b100, 0x0f]), Err(DecodeError::Truncated));
+/// ```
+pub fn decode(encoded: &[u8]) -> Result<Vec<u8>, DecodeError> {
+  decode_max(encoded, MAX_DECODED_LEN)
+}
+
+/// Decode a bitfield of at most `max_len` bytes. A few bytes of input can
+/// describe a huge run, so input from other peers should be decoded with a
+/// limit.
+///
+/// ## Errors
+/// If the input ends in the middle of a chunk, or the bitfield is larger
+/// than `max_len` bytes or than what fits in memory.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::{rle, DecodeError};
+///
+/// assert_eq!(rle::decode_max(&[0b1001], 2), Ok(vec![0, 0]));
+/// assert_eq!(rle::decode_max(&[0b1101], 2), Err(DecodeError::Overflow));
+/// ```
+pub fn decode_max(
+  mut encoded: &[u8],
+  max_len: usize,
+) -> Result<Vec<u8>, DecodeError> {
+  let mut bits = Vec::new();
+  while !encoded.is_empty() {
+    let header = read_varint(&mut encoded)?;
+    if header & 1 == 1 {
+      let len = to_len(header >> 2)?;
+      let fill = if header & 0b10 != 0 { 0xff } else { 0 };
+      let new_len = bits
+        .len()
+        .checked_add(len)
+        .filter(|&new_len| new_len <= max_len)
+        .ok_or(DecodeError::Overflow)?;
+      bits.try_reserve(len).map_err(|_| DecodeError::Overflow)?;
+      bits.resize(new_len, fill);
+    } else {
+      let len = to_len(header >> 1)?;
+      if len > encoded.len() {
+        return Err(DecodeError::Truncated);
+      }
+      if bits.len() + len > max_len {
+        return Err(DecodeError::Overflow);
+      }
+      bits.extend_from_slice(&encoded[..len]);
+      encoded = &encoded[len..];
+    }
+  }
+  Ok(bits)
+}
+
+/// The number of leading bytes that are all `0x00`, or all `0xff`.
+fn run_len(bits: &[u8]) -> usize {
+  match bits.first() {
+    Some(&byte) if byte == 0 || byte == 0xff => {
+      bits.iter().take_while(|&&b| b == byte).count()
+    }
+    _ => 0,
+  }
+}
+
+fn write_literal(out: &mut Vec<u8>, bytes: &[u8]) {
+  if !bytes.is_empty() {
+    write_varint(out, (bytes.len() as u64) << 1);
+    out.extend_from_slice(bytes);
+  }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+  while value >= 0x80 {
+    out.push(value as u8 | 0x80);
+    value >>= 7;
+  }
+  out.push(value as u8);
+}
+
+fn read_varint(encoded: &mut &[u8]) -> Result<u64, DecodeError> {
+  let mut value = 0u64;
+  for (i, &byte) in encoded.iter().enumerate() {
+    let bits = u64::from(byte & 0x7f);
+    // Reject bits shifted out of the value, rather than dropping them.
+    if i * 7 >= 64 || (bits << (i * 7)) >> (i * 7) != bits {
+      return Err(DecodeError::Overflow);
+    }
+    value |= bits << (i * 7);
+    if byte & 0x80 == 0 {
+      *encoded = &encoded[i + 1..];
+      return Ok(value);
+    }
+  }
+  Err(DecodeError::Truncated)
+}
+
+fn to_len(len: u64) -> Result<usize, DecodeError> {
+  if len > usize::MAX as u64 {
+    return Err(DecodeError::Overflow);
+  }
+  Ok(len as usize)
+}
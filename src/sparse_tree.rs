@@ -0,0 +1,168 @@
+//! Container with a value per node, for trees where few nodes have one.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::SparseTree;
+//!
+//! let mut tree = SparseTree::new();
+//! tree.set(1 << 40, "far");
+//! tree.set(3, "near");
+//! let nodes: Vec<_> = tree.populated_nodes().collect();
+//! assert_eq!(nodes, [3, 1 << 40]);
+//! ```
+use super::*;
+
+use std::collections::BTreeMap;
+use std::iter;
+use std::mem;
+
+/// A value for some nodes of a flat-tree, stored in a `BTreeMap`. Unlike
+/// `DenseTree`, memory use only depends on the number of nodes that have a
+/// value, not on their index.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SparseTree<T> {
+  nodes: BTreeMap<usize, T>,
+}
+
+impl<T> Default for SparseTree<T> {
+  fn default() -> Self {
+    Self {
+      nodes: BTreeMap::new(),
+    }
+  }
+}
+
+impl<T> SparseTree<T> {
+  /// Create an empty tree.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Get the number of nodes that have a value.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.nodes.len()
+  }
+
+  /// Check if no node has a value.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.nodes.is_empty()
+  }
+
+  /// Get the value of node `i`.
+  #[inline]
+  pub fn get(&self, i: usize) -> Option<&T> {
+    self.nodes.get(&i)
+  }
+
+  /// Get a mutable reference to the value of node `i`.
+  #[inline]
+  pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+    self.nodes.get_mut(&i)
+  }
+
+  /// Check if node `i` has a value.
+  #[inline]
+  pub fn contains(&self, i: usize) -> bool {
+    self.nodes.contains_key(&i)
+  }
+
+  /// Set the value of node `i`. Returns the previous value.
+  pub fn set(&mut self, i: usize, value: T) -> Option<T> {
+    self.nodes.insert(i, value)
+  }
+
+  /// Remove the value of node `i`, returning it.
+  pub fn remove(&mut self, i: usize) -> Option<T> {
+    self.nodes.remove(&i)
+  }
+
+  /// Remove every value.
+  pub fn clear(&mut self) {
+    self.nodes.clear();
+  }
+
+  /// Remove every value and return the nodes that had a value, in
+  /// increasing index order.
+  pub fn take(&mut self) -> Vec<(usize, T)> {
+    mem::take(&mut self.nodes).into_iter().collect()
+  }
+
+  /// Returns an iterator over the nodes that have a value, in increasing
+  /// index order.
+  pub fn populated_nodes(&self) -> impl iter::Iterator<Item = usize> + '_ {
+    self.nodes.keys().cloned()
+  }
+
+  /// Returns an iterator over the nodes that have a value, with their
+  /// value, in increasing index order.
+  pub fn iter(&self) -> impl iter::Iterator<Item = (usize, &T)> {
+    self.nodes.iter().map(|(&i, value)| (i, value))
+  }
+
+  /// Returns the nodes in the subtree rooted at `root`, including `root`,
+  /// that have a value, in `order`. Only the nodes that have a value are
+  /// visited.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::{Order, SparseTree};
+  ///
+  /// let tree: SparseTree<_> = vec![(0, 'a'), (3, 'c'), (4, 'b')]
+  ///   .into_iter()
+  ///   .collect();
+  /// let values: Vec<_> = tree.subtree(3, Order::PostOrder).collect();
+  /// assert_eq!(values, [(0, &'a'), (4, &'b'), (3, &'c')]);
+  /// ```
+  pub fn subtree(
+    &self,
+    root: usize,
+    order: Order,
+  ) -> impl iter::Iterator<Item = (usize, &T)> {
+    let (left, right) = spans(root);
+    let mut nodes: Vec<_> = self
+      .nodes
+      .range(left..=right)
+      .map(|(&i, value)| (i, value))
+      .collect();
+    match order {
+      Order::InOrder => {}
+      Order::PreOrder => {
+        nodes.sort_by_key(|&(i, _)| (left_span(i), depth(root) - depth(i)))
+      }
+      Order::PostOrder => {
+        nodes.sort_by_key(|&(i, _)| (right_span(i), depth(i)))
+      }
+      Order::LevelOrder => {
+        nodes.sort_by_key(|&(i, _)| (depth(root) - depth(i), i))
+      }
+    }
+    nodes.into_iter()
+  }
+
+  /// Returns an iterator over the ancestors of node `i` that have a value,
+  /// from its parent upwards.
+  pub fn ancestors(&self, i: usize) -> impl iter::Iterator<Item = (usize, &T)> {
+    // Past the last node, only ancestors that are right children can have
+    // a parent before it.
+    let last = self.nodes.keys().next_back().cloned().unwrap_or(0);
+    ancestors(i)
+      .take_while(move |&parent| parent <= last || left_span(parent) > 0)
+      .filter_map(move |i| self.get(i).map(|value| (i, value)))
+  }
+}
+
+impl<T> iter::FromIterator<(usize, T)> for SparseTree<T> {
+  fn from_iter<I: IntoIterator<Item = (usize, T)>>(iter: I) -> Self {
+    Self {
+      nodes: iter.into_iter().collect(),
+    }
+  }
+}
+
+impl<T> iter::Extend<(usize, T)> for SparseTree<T> {
+  fn extend<I: IntoIterator<Item = (usize, T)>>(&mut self, iter: I) {
+    self.nodes.extend(iter);
+  }
+}
@@ -0,0 +1,145 @@
+//! Asynchronous node storage, and assembling proofs from it.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{assemble_proof, NodeStorage, ProofOptions, TreeIndex};
+//! use std::future::{self, Future};
+//! use std::pin::pin;
+//! use std::task::{Context, Poll, Waker};
+//!
+//! struct Hashes(Vec<u8>);
+//!
+//! impl NodeStorage for Hashes {
+//!   type Node = u8;
+//!   type Get<'a> = future::Ready<Option<u8>>;
+//!
+//!   fn get(&self, index: usize) -> Self::Get<'_> {
+//!     future::ready(self.0.get(index).cloned())
+//!   }
+//! }
+//!
+//! let mut tree = TreeIndex::new();
+//! for leaf in 0..9 {
+//!   tree.set(leaf * 2);
+//! }
+//! let proof = tree.proof(0, ProofOptions::new()).unwrap();
+//! let storage = Hashes((0..17).collect());
+//! let mut proof = pin!(assemble_proof(&proof, &storage));
+//! let mut cx = Context::from_waker(Waker::noop());
+//! let nodes = match proof.as_mut().poll(&mut cx) {
+//!   Poll::Ready(nodes) => nodes.unwrap(),
+//!   Poll::Pending => unreachable!(),
+//! };
+//! assert_eq!(nodes, [(2, 2), (5, 5), (11, 11), (16, 16)]);
+//! ```
+use super::*;
+
+use std::error;
+use std::fmt;
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::vec;
+
+/// Asynchronous storage of the nodes of a flat-tree, such as hashes kept on
+/// disk or fetched over the network.
+pub trait NodeStorage {
+  /// The data stored for a node.
+  type Node;
+
+  /// The future returned by `get()`.
+  type Get<'a>: Future<Output = Option<Self::Node>>
+  where
+    Self: 'a;
+
+  /// Get the data stored for a node, or `None` if it isn't stored.
+  fn get(&self, index: usize) -> Self::Get<'_>;
+}
+
+/// Error returned when a node needed for a proof isn't stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingNode {
+  /// The node that isn't stored.
+  pub index: usize,
+}
+
+impl fmt::Display for MissingNode {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "node {} isn't stored", self.index)
+  }
+}
+
+impl error::Error for MissingNode {}
+
+/// Fetch the nodes of a proof planned by `TreeIndex::proof()` from
+/// `storage`. Exactly the nodes of the proof are fetched, one at a time in
+/// the order they're needed, and resolve to pairs of index and data in that
+/// order.
+///
+/// The future resolves to an error with the first node that isn't stored.
+pub fn assemble_proof<'a, S: NodeStorage>(
+  proof: &Proof,
+  storage: &'a S,
+) -> AssembleProof<'a, S> {
+  AssembleProof {
+    storage,
+    path: proof.nodes.clone().into_iter(),
+    pending: None,
+    nodes: Vec::new(),
+  }
+}
+
+/// Future that fetches the nodes of a proof.
+///
+/// Created by `assemble_proof()`.
+#[must_use = "futures do nothing unless polled"]
+pub struct AssembleProof<'a, S: NodeStorage + 'a> {
+  storage: &'a S,
+  path: vec::IntoIter<usize>,
+  pending: Option<(usize, Pin<Box<S::Get<'a>>>)>,
+  nodes: Vec<(usize, S::Node)>,
+}
+
+impl<'a, S: NodeStorage + 'a> fmt::Debug for AssembleProof<'a, S> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("AssembleProof")
+      .field("path", &self.path.as_slice())
+      .field("pending", &self.pending.as_ref().map(|(index, _)| index))
+      .field("fetched", &self.nodes.len())
+      .finish()
+  }
+}
+
+// The pending future is boxed, so nothing is ever pinned in place.
+impl<'a, S: NodeStorage + 'a> Unpin for AssembleProof<'a, S> {}
+
+impl<'a, S: NodeStorage + 'a> Future for AssembleProof<'a, S> {
+  type Output = Result<Vec<(usize, S::Node)>, MissingNode>;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+    let this = &mut *self;
+    loop {
+      if let Some((index, get)) = this.pending.as_mut() {
+        let index = *index;
+        match get.as_mut().poll(cx) {
+          Poll::Pending => return Poll::Pending,
+          Poll::Ready(None) => {
+            this.pending = None;
+            return Poll::Ready(Err(MissingNode { index }));
+          }
+          Poll::Ready(Some(data)) => {
+            this.pending = None;
+            this.nodes.push((index, data));
+          }
+        }
+      }
+      match this.path.next() {
+        Some(index) => {
+          this.pending = Some((index, Box::pin(this.storage.get(index))));
+        }
+        None => return Poll::Ready(Ok(mem::take(&mut this.nodes))),
+      }
+    }
+  }
+}
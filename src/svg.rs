@@ -0,0 +1,243 @@
+//! Draw a tree, and which of its nodes are present, as an SVG image.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{to_svg, SvgOptions, TreeIndex};
+//!
+//! let mut tree = TreeIndex::new();
+//! tree.set(0);
+//! let svg = to_svg(&tree, 4, SvgOptions::new().present("green"));
+//! assert!(svg.starts_with("<svg "));
+//! assert!(svg.contains(r#"fill="green""#));
+//! ```
+use super::*;
+
+use highlight::color;
+
+use std::fmt::Write;
+
+/// Options for `to_svg()`. Colors are any SVG color, such as `"#4caf50"`
+/// or `"green"`.
+#[derive(Debug, Clone)]
+pub struct SvgOptions<'a> {
+  present: String,
+  missing: String,
+  root: String,
+  stroke: String,
+  highlights: Option<&'a Highlights<'a>>,
+}
+
+impl<'a> Default for SvgOptions<'a> {
+  fn default() -> Self {
+    Self {
+      present: "#8fd18f".to_string(),
+      missing: "#ffffff".to_string(),
+      root: "#d33f3f".to_string(),
+      stroke: "#555555".to_string(),
+      highlights: None,
+    }
+  }
+}
+
+impl<'a> SvgOptions<'a> {
+  /// Create the default options: present nodes filled in green, missing
+  /// nodes in white, and roots outlined in red.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Set the fill color of present nodes.
+  pub fn present(mut self, color: &str) -> Self {
+    self.present = color.to_string();
+    self
+  }
+
+  /// Set the fill color of missing nodes.
+  pub fn missing(mut self, color: &str) -> Self {
+    self.missing = color.to_string();
+    self
+  }
+
+  /// Set the outline color of the full roots of the tree.
+  pub fn root(mut self, color: &str) -> Self {
+    self.root = color.to_string();
+    self
+  }
+
+  /// Set the color of the edges, and of the outline of the other nodes.
+  pub fn stroke(mut self, color: &str) -> Self {
+    self.stroke = color.to_string();
+    self
+  }
+
+  /// Ring the nodes of each highlighted set in the set's color, one ring
+  /// per set, and list the sets below the tree. A node's note is shown as
+  /// its tooltip.
+  pub fn highlights(mut self, highlights: &'a Highlights<'a>) -> Self {
+    self.highlights = Some(highlights);
+    self
+  }
+}
+
+/// Draw a tree with `tree_len` leaves, with the nodes present in `tree`
+/// filled in and the full roots outlined, see `SvgOptions`.
+///
+/// The leaves are on the bottom row, and each parent is on the row above
+/// its children and halfway between them, like `render_ascii()`. Only
+/// nodes whose leaves are all in the tree are drawn.
+pub fn to_svg<B: Bitfield>(
+  tree: &TreeIndex<B>,
+  tree_len: usize,
+  opts: SvgOptions,
+) -> String {
+  let last = (2 * tree_len).saturating_sub(2);
+  let digits = last.to_string().len();
+  let sets = opts.highlights.map_or(0, |highlights| highlights.len());
+  let radius = 3 * digits + 6;
+  let unit = radius + 3 * sets + 2;
+  let row = 2 * radius + 24;
+  let top = if tree_len == 0 {
+    0
+  } else {
+    (usize::BITS - 1 - tree_len.leading_zeros()) as usize
+  };
+  let x = |i: usize| (i + 1) * unit;
+  let y = |i: usize| (top - depth(i)) * row + row / 2;
+
+  let nodes: Vec<usize> = if tree_len == 0 {
+    Vec::new()
+  } else {
+    (0..=last).filter(|&i| right_span(i) <= last).collect()
+  };
+  let mut roots = Vec::new();
+  full_roots(2 * tree_len, &mut roots);
+
+  let width = (last + 2) * unit;
+  let height = (top + 1) * row + sets * LEGEND_ROW;
+  let mut out = String::new();
+  writeln!(
+    out,
+    concat!(
+      r#"<svg xmlns="http://www.w3.org/2000/svg" "#,
+      r#"width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#
+    ),
+    width, height
+  )
+  .unwrap();
+
+  writeln!(
+    out,
+    r#"<g stroke="{}" stroke-width="1">"#,
+    escape(&opts.stroke)
+  )
+  .unwrap();
+  for &i in &nodes {
+    if let Some((left, right)) = children(i) {
+      for child in [left, right] {
+        writeln!(
+          out,
+          r#"<line x1="{}" y1="{}" x2="{}" y2="{}"/>"#,
+          x(i),
+          y(i),
+          x(child),
+          y(child)
+        )
+        .unwrap();
+      }
+    }
+  }
+  out.push_str("</g>\n");
+
+  out.push_str(
+    r#"<g font-family="sans-serif" font-size="10" text-anchor="middle">"#,
+  );
+  out.push('\n');
+  for &i in &nodes {
+    let fill = if tree.get(i) {
+      &opts.present
+    } else {
+      &opts.missing
+    };
+    let (stroke, stroke_width) = if roots.contains(&i) {
+      (&opts.root, 2)
+    } else {
+      (&opts.stroke, 1)
+    };
+    write!(
+      out,
+      concat!(
+        r#"<circle cx="{}" cy="{}" r="{}" "#,
+        r#"fill="{}" stroke="{}" stroke-width="{}""#
+      ),
+      x(i),
+      y(i),
+      radius,
+      escape(fill),
+      escape(stroke),
+      stroke_width
+    )
+    .unwrap();
+    match opts.highlights.and_then(|highlights| highlights.note(i)) {
+      Some(note) => {
+        writeln!(out, "><title>{}</title></circle>", escape(&note)).unwrap()
+      }
+      None => out.push_str("/>\n"),
+    }
+    let containing = opts
+      .highlights
+      .map_or(Vec::new(), |highlights| highlights.containing(i));
+    for pos in containing {
+      writeln!(
+        out,
+        concat!(
+          r#"<circle cx="{}" cy="{}" r="{}" "#,
+          r#"fill="none" stroke="{}" stroke-width="2"/>"#
+        ),
+        x(i),
+        y(i),
+        radius + 3 * (pos + 1),
+        color(pos)
+      )
+      .unwrap();
+    }
+    writeln!(
+      out,
+      r#"<text x="{}" y="{}" dy="0.35em">{}</text>"#,
+      x(i),
+      y(i),
+      i
+    )
+    .unwrap();
+  }
+  out.push_str("</g>\n");
+
+  if let Some(highlights) = opts.highlights {
+    out.push_str(r#"<g font-family="sans-serif" font-size="10">"#);
+    out.push('\n');
+    for (pos, label) in highlights.labels().enumerate() {
+      writeln!(
+        out,
+        r#"<text x="{}" y="{}" fill="{}">{}</text>"#,
+        unit / 2,
+        (top + 1) * row + pos * LEGEND_ROW + LEGEND_ROW / 2,
+        color(pos),
+        escape(label)
+      )
+      .unwrap();
+    }
+    out.push_str("</g>\n");
+  }
+  out.push_str("</svg>\n");
+  out
+}
+
+/// The height of a line of the list of highlighted sets.
+const LEGEND_ROW: usize = 16;
+
+/// Escape a value for an XML attribute.
+fn escape(value: &str) -> String {
+  value
+    .replace('&', "&amp;")
+    .replace('"', "&quot;")
+    .replace('<', "&lt;")
+}
@@ -0,0 +1,143 @@
+//! Proptest strategies and invariant checks for code built on flat-tree.
+//!
+//! Only available with the `proptest` feature.
+//!
+//! ## Usage
+//! ```rust
+//! # extern crate flat_tree;
+//! # #[macro_use] extern crate proptest;
+//! use flat_tree::testing;
+//!
+//! proptest! {
+//!   fn node_is_consistent(i in testing::node()) {
+//!     testing::assert_node(i);
+//!   }
+//! }
+//! # fn main() { node_is_consistent(); }
+//! ```
+use super::*;
+
+use proptest::collection;
+use proptest::sample::select;
+use proptest::strategy::{Just, Strategy};
+use std::ops::Range;
+
+/// The deepest node the strategies generate, so every index they generate
+/// and the spans of the node fit in a `usize`.
+pub const MAX_DEPTH: usize = Iterator::MAX_DEPTH;
+
+/// Generate a depth and an offset whose index fits in a `usize`.
+pub fn depth_and_offset() -> impl Strategy<Value = (usize, usize)> {
+  (0..=MAX_DEPTH).prop_flat_map(|depth| {
+    let offsets = 1usize << (usize::BITS as usize - 2 - depth);
+    (Just(depth), 0..offsets)
+  })
+}
+
+/// Generate any node, see `depth_and_offset()`.
+pub fn node() -> impl Strategy<Value = usize> {
+  depth_and_offset().prop_map(|(depth, offset)| index(depth, offset))
+}
+
+/// Generate the number of leaves of a tree, from `1` up to `max_leaves`.
+///
+/// ## Panics
+/// Panics if `max_leaves` is zero.
+pub fn tree(max_leaves: usize) -> impl Strategy<Value = usize> {
+  assert!(max_leaves > 0, "A tree needs at least one leaf");
+  1..=max_leaves
+}
+
+/// Generate a tree with up to `max_leaves` leaves, and a node in it, see
+/// `nodes()`.
+pub fn tree_and_node(
+  max_leaves: usize,
+) -> impl Strategy<Value = (usize, usize)> {
+  tree(max_leaves).prop_flat_map(|leaves| {
+    let nodes: Vec<_> = nodes(leaves, Order::InOrder).collect();
+    (Just(leaves), select(nodes))
+  })
+}
+
+/// Generate a set of up to `max_len` nodes of a tree with `leaves` leaves.
+pub fn node_set(
+  leaves: usize,
+  max_len: usize,
+) -> impl Strategy<Value = NodeSet> {
+  let nodes: Vec<_> = nodes(leaves, Order::InOrder).collect();
+  if nodes.is_empty() {
+    return Just(NodeSet::new()).boxed();
+  }
+  collection::vec(select(nodes), 0..=max_len)
+    .prop_map(|nodes| nodes.into_iter().collect())
+    .boxed()
+}
+
+/// Generate a range of leaf numbers of a tree with `leaves` leaves. The
+/// range may be empty.
+pub fn leaf_range(leaves: usize) -> impl Strategy<Value = Range<usize>> {
+  (0..=leaves)
+    .prop_flat_map(move |start| (Just(start), start..=leaves))
+    .prop_map(|(start, end)| start..end)
+}
+
+/// Check that the functions relating node `i` to its neighbours agree with
+/// each other.
+///
+/// ## Panics
+/// Panics, as a failed test, if any of them doesn't.
+pub fn assert_node(i: usize) {
+  let node_depth = depth(i);
+  assert_eq!(index(node_depth, offset(i)), i, "index of node {}", i);
+
+  let (left, right) = spans(i);
+  assert!(left <= i && i <= right, "spans of node {}", i);
+  assert_eq!(right - left, (2 << node_depth) - 2, "spans of node {}", i);
+  assert_eq!(count(i), right - left + 1, "count of node {}", i);
+
+  if node_depth < MAX_DEPTH {
+    let node_parent = parent(i);
+    let node_sibling = sibling(i);
+    assert_eq!(depth(node_parent), node_depth + 1, "parent of node {}", i);
+    assert_eq!(sibling(node_sibling), i, "sibling of node {}", i);
+    assert_eq!(parent(node_sibling), node_parent, "sibling of node {}", i);
+    let expected = if i < node_sibling {
+      (i, node_sibling)
+    } else {
+      (node_sibling, i)
+    };
+    assert_eq!(
+      children(node_parent),
+      Some(expected),
+      "children of the parent of node {}",
+      i
+    );
+  }
+
+  match children(i) {
+    Some((left_child, right_child)) => {
+      assert_eq!(parent(left_child), i, "left child of node {}", i);
+      assert_eq!(parent(right_child), i, "right child of node {}", i);
+      assert_eq!(left_span(left_child), left, "left span of node {}", i);
+      assert_eq!(right_span(right_child), right, "right span of node {}", i);
+    }
+    None => assert_eq!(node_depth, 0, "children of node {}", i),
+  }
+}
+
+/// Check that the full roots of a tree with `leaves` leaves cover every
+/// leaf exactly once, from left to right.
+///
+/// ## Panics
+/// Panics, as a failed test, if they don't.
+pub fn assert_roots(leaves: usize) {
+  let mut roots = Vec::new();
+  full_roots(2 * leaves, &mut roots);
+  let mut next = 0;
+  for root in roots {
+    let (left, right) = spans(root);
+    assert_eq!(left, next, "roots of a tree with {} leaves", leaves);
+    next = right + 2;
+  }
+  assert_eq!(next, 2 * leaves, "roots of a tree with {} leaves", leaves);
+}
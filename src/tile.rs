@@ -0,0 +1,162 @@
+//! Address nodes by the tiles of a tiled transparency log, as used by the Go
+//! checksum database (`golang.org/x/mod/sumdb/tlog`).
+//!
+//! With a tile height of `h`, the tiles at level `l` store the hashes of
+//! the nodes at depth `l * h`, `2^h` of them per tile. The nodes in between
+//! aren't stored, they're computed from the tile below them.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::Tile;
+//!
+//! // Tiles of height 2 store 4 hashes each.
+//! let (tile, offset) = Tile::locate(19, 2).unwrap();
+//! assert_eq!(tile, Tile { height: 2, level: 1, index: 0 });
+//! assert_eq!(offset, 2);
+//! assert_eq!(tile.node(offset), 19);
+//! ```
+use super::*;
+
+use std::iter;
+use std::ops::Range;
+
+/// A tile of hashes, `2^height` nodes wide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Tile {
+  /// The number of levels of the tree the tile spans.
+  pub height: usize,
+  /// The level of the tile, the nodes it stores are at depth
+  /// `level * height`.
+  pub level: usize,
+  /// The index of the tile within its level, from left to right.
+  pub index: usize,
+}
+
+impl Tile {
+  /// Get the tile that stores node `i`, and the offset of the node in the
+  /// tile.
+  ///
+  /// Returns `None` if the node isn't stored in a tile, as its depth isn't a
+  /// multiple of `height`, see `covering()`.
+  ///
+  /// ## Panics
+  /// Panics if `height` is zero.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::Tile;
+  ///
+  /// let tile = Tile { height: 2, level: 0, index: 1 };
+  /// assert_eq!(Tile::locate(10, 2), Some((tile, 1)));
+  /// assert_eq!(Tile::locate(9, 2), None);
+  /// ```
+  pub fn locate(i: usize, height: usize) -> Option<(Self, usize)> {
+    // Only a node at the depth of the tile is covered by a single offset.
+    let (tile, offsets) = Self::covering(i, height);
+    if offsets.len() == 1 {
+      Some((tile, offsets.start))
+    } else {
+      None
+    }
+  }
+
+  /// Get the tile that node `i` is stored in, or computed from, and the
+  /// offsets of the nodes it's made of in that tile.
+  ///
+  /// ## Panics
+  /// Panics if `height` is zero.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::Tile;
+  ///
+  /// let tile = Tile { height: 2, level: 0, index: 1 };
+  /// assert_eq!(Tile::covering(9, 2), (tile, 0..2));
+  /// assert_eq!(Tile::covering(10, 2), (tile, 1..2));
+  /// ```
+  pub fn covering(i: usize, height: usize) -> (Self, Range<usize>) {
+    assert!(height > 0, "The tile height must be at least 1");
+    let depth = depth(i);
+    let (level, below) = (depth / height, depth % height);
+    // The first and last node the node spans, at the depth of the tile.
+    let first = offset_with_depth(i, depth) << below;
+    let last = first + (1 << below) - 1;
+    let tile = Tile {
+      height,
+      level,
+      index: first >> height,
+    };
+    let mask = (1 << height) - 1;
+    (tile, (first & mask)..(last & mask) + 1)
+  }
+
+  /// Get the node stored at `offset` in the tile.
+  ///
+  /// ## Panics
+  /// Panics if `offset` isn't in the tile.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::Tile;
+  ///
+  /// let tile = Tile { height: 2, level: 1, index: 1 };
+  /// assert_eq!(tile.node(0), 35);
+  /// assert_eq!(tile.node(3), 59);
+  /// ```
+  pub fn node(&self, offset: usize) -> usize {
+    assert!(
+      offset < 1 << self.height,
+      "Offset {} is past the end of a tile of height {}",
+      offset,
+      self.height
+    );
+    index(
+      self.level * self.height,
+      (self.index << self.height) | offset,
+    )
+  }
+
+  /// Get the nodes stored in the tile, from left to right.
+  pub fn nodes(&self) -> impl iter::Iterator<Item = usize> {
+    let tile = *self;
+    (0..1 << self.height).map(move |offset| tile.node(offset))
+  }
+
+  /// Get the tile one level up, that stores the root of this one.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::Tile;
+  ///
+  /// let tile = Tile { height: 2, level: 0, index: 5 };
+  /// assert_eq!(tile.parent(), Tile { height: 2, level: 1, index: 1 });
+  /// ```
+  pub fn parent(&self) -> Self {
+    Tile {
+      height: self.height,
+      level: self.level + 1,
+      index: self.index >> self.height,
+    }
+  }
+
+  /// Get the number of nodes the tile stores in a tree with `leaves`
+  /// leaves. A tile is only full once every node it stores is complete,
+  /// before that it's a partial tile.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::Tile;
+  ///
+  /// let tile = Tile { height: 2, level: 0, index: 1 };
+  /// assert_eq!(tile.width(5), 1);
+  /// assert_eq!(tile.width(9), 4);
+  /// assert_eq!(tile.width(3), 0);
+  /// ```
+  pub fn width(&self, leaves: usize) -> usize {
+    let complete = leaves
+      .checked_shr((self.level * self.height) as u32)
+      .unwrap_or(0);
+    let start = self.index << self.height;
+    complete.saturating_sub(start).min(1 << self.height)
+  }
+}
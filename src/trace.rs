@@ -0,0 +1,168 @@
+//! Record the moves of a cursor and draw them one frame per step.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{Cursor, RenderOptions, Trace};
+//!
+//! let mut cursor = Cursor::new(3);
+//! cursor.left_child();
+//! let frames = Trace::from(&cursor).frames(4, RenderOptions::new());
+//! assert_eq!(frames.len(), 2);
+//! assert!(frames[1].starts_with("frame 1: 3 -> 1 (left_child)\n"));
+//! ```
+use super::*;
+
+/// The moves made from a starting node, such as the history of a `Cursor`,
+/// or the nodes visited by `walk()`, see `Trace::walk()`.
+///
+/// Each frame draws the tree with the node moved to marked as `here`, and
+/// the nodes visited before it as `visited`, see `Highlights`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+  start: usize,
+  steps: Vec<Record>,
+}
+
+impl Trace {
+  /// Create a trace starting at `start`, without any moves.
+  pub fn new(start: usize) -> Self {
+    Self {
+      start,
+      steps: Vec::new(),
+    }
+  }
+
+  /// Record a move to `to`, from the node of the last move.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::{Move, Trace};
+  ///
+  /// let mut trace = Trace::new(3);
+  /// trace.push(Move::RightChild, 5);
+  /// trace.push(Move::Seek, 8);
+  /// assert_eq!(trace.path(), [3, 5, 8]);
+  /// assert_eq!(trace.steps()[1].from, 5);
+  /// ```
+  pub fn push(&mut self, op: Move, to: usize) {
+    let from = self.steps.last().map_or(self.start, |step| step.to);
+    self.steps.push(Record { op, from, to });
+  }
+
+  /// Run `walk()` and record the nodes it visits. A move to a child or the
+  /// sibling of the last node is recorded as such, any other move as a
+  /// seek. Returns the trace and the node the walk was stopped at, if any.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::{Move, Step, Trace};
+  ///
+  /// let (trace, found) = Trace::walk(3, 4, |node, _| match node {
+  ///   1 => Step::SkipSubtree,
+  ///   6 => Step::Stop,
+  ///   _ => Step::Descend,
+  /// });
+  /// assert_eq!(found, Some(6));
+  /// assert_eq!(trace.path(), [3, 1, 5, 4, 6]);
+  /// let ops: Vec<Move> = trace.steps().iter().map(|step| step.op).collect();
+  /// assert_eq!(
+  ///   ops,
+  ///   [Move::LeftChild, Move::Sibling, Move::LeftChild, Move::Sibling]
+  /// );
+  /// ```
+  pub fn walk<F>(root: usize, leaves: usize, mut f: F) -> (Self, Option<usize>)
+  where
+    F: FnMut(usize, &WalkContext) -> Step,
+  {
+    let mut trace = Trace::new(root);
+    let found = walk(root, leaves, |node, ctx| {
+      let from = trace.steps.last().map_or(root, |step| step.to);
+      if node != root {
+        let op = if left_child(from) == Some(node) {
+          Move::LeftChild
+        } else if right_child(from) == Some(node) {
+          Move::RightChild
+        } else if sibling(from) == node {
+          Move::Sibling
+        } else {
+          Move::Seek
+        };
+        trace.push(op, node);
+      }
+      f(node, ctx)
+    });
+    (trace, found)
+  }
+
+  /// Get the moves made.
+  #[inline]
+  pub fn steps(&self) -> &[Record] {
+    &self.steps
+  }
+
+  /// Get every node visited, starting with the initial one.
+  pub fn path(&self) -> Vec<usize> {
+    let mut path = vec![self.start];
+    path.extend(self.steps.iter().map(|step| step.to));
+    path
+  }
+
+  /// Draw a frame for the start and for each move, numbered from `0`, in a
+  /// tree with `leaves` leaves, see `render_unicode()`. Any highlights in
+  /// `opts` are replaced.
+  pub fn frames(&self, leaves: usize, opts: RenderOptions) -> Vec<String> {
+    self.each_frame(|title, highlights| {
+      format!(
+        "{}\n{}",
+        title,
+        render_unicode(leaves, opts.highlights(highlights))
+      )
+    })
+  }
+
+  /// Draw a frame for the start and for each move as an SVG image, see
+  /// `to_svg()`. The title of the frame is the title of the image. Any
+  /// highlights in `opts` are replaced.
+  #[cfg(feature = "svg")]
+  pub fn svg_frames(&self, leaves: usize, opts: SvgOptions) -> Vec<String> {
+    let tree = TreeIndex::new();
+    self.each_frame(|title, highlights| {
+      let svg = to_svg(&tree, leaves, opts.clone().highlights(highlights));
+      // Insert the title after the opening tag.
+      let at = svg.find('\n').map_or(svg.len(), |at| at + 1);
+      format!("{}<title>{}</title>\n{}", &svg[..at], title, &svg[at..])
+    })
+  }
+
+  fn each_frame<F>(&self, mut f: F) -> Vec<String>
+  where
+    F: FnMut(&str, &Highlights) -> String,
+  {
+    let path = self.path();
+    let mut frames = Vec::with_capacity(path.len());
+    for (n, &here) in path.iter().enumerate() {
+      let title = match n {
+        0 => format!("frame 0: start at {}", here),
+        _ => format!("frame {}: {}", n, self.steps[n - 1]),
+      };
+      let mut current = NodeSet::new();
+      current.insert(here);
+      let visited: NodeSet =
+        path[..n].iter().cloned().filter(|&i| i != here).collect();
+      let highlights = Highlights::new()
+        .set("here", &current)
+        .set("visited", &visited);
+      frames.push(f(&title, &highlights));
+    }
+    frames
+  }
+}
+
+impl<'a> From<&'a Cursor> for Trace {
+  fn from(cursor: &'a Cursor) -> Self {
+    Self {
+      start: cursor.path()[0],
+      steps: cursor.history().to_vec(),
+    }
+  }
+}
@@ -0,0 +1,745 @@
+//! Traversals over bounded trees.
+//!
+//! A tree with `n` leaves contains every node whose span lies entirely within
+//! the first `n` leaves. When `n` is not a power of two this is a forest whose
+//! roots are the ones returned by `full_roots(2 * n)`.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::{nodes, Order};
+//!
+//! let all: Vec<usize> = nodes(3, Order::InOrder).collect();
+//! assert_eq!(all, [0, 1, 2, 4]);
+//! ```
+use super::*;
+
+use std::error;
+use std::fmt;
+use std::iter;
+
+/// The order in which the nodes of a bounded tree are visited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+  /// Increasing flat-tree index.
+  InOrder,
+  /// Parents before their children, roots from left to right.
+  PreOrder,
+  /// Children before their parents, roots from left to right.
+  PostOrder,
+  /// Level by level starting with the roots at the highest depth, each level
+  /// from left to right.
+  LevelOrder,
+}
+
+/// Iterator over every node of a bounded tree.
+#[derive(Debug)]
+pub struct Nodes {
+  order: Order,
+  leaves: usize,
+  start: usize,
+  next: usize,
+  end: usize,
+  level: usize,
+  stack: Vec<(usize, bool)>,
+  remaining: usize,
+}
+
+/// Returns an iterator over every node in a tree with `leaves` leaves.
+///
+/// ## Panics
+/// If `2 * leaves` doesn't fit in a `usize`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::{nodes, Order};
+///
+/// let all: Vec<usize> = nodes(4, Order::InOrder).collect();
+/// assert_eq!(all, [0, 1, 2, 3, 4, 5, 6]);
+///
+/// let all: Vec<usize> = nodes(4, Order::PreOrder).collect();
+/// assert_eq!(all, [3, 1, 0, 2, 5, 4, 6]);
+///
+/// let all: Vec<usize> = nodes(4, Order::PostOrder).collect();
+/// assert_eq!(all, [0, 2, 1, 4, 6, 5, 3]);
+///
+/// let all: Vec<usize> = nodes(3, Order::PreOrder).collect();
+/// assert_eq!(all, [1, 0, 2, 4]);
+/// ```
+pub fn nodes(leaves: usize, order: Order) -> Nodes {
+  let end = leaves_end(leaves);
+  let mut roots = Vec::new();
+  full_roots(end, &mut roots);
+  Nodes::new(order, &roots, leaves, 0, end)
+}
+
+/// Returns an iterator over every node in the subtree rooted at `root`,
+/// including `root` itself.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::{subtree_nodes, Order};
+///
+/// let all: Vec<usize> = subtree_nodes(5, Order::PreOrder).collect();
+/// assert_eq!(all, [5, 4, 6]);
+/// ```
+pub fn subtree_nodes(root: usize, order: Order) -> Nodes {
+  let (left, right) = spans(root);
+  Nodes::new(order, &[root], right / 2 + 1, left, right + 1)
+}
+
+/// Returns an iterator over every node in a tree with `leaves` leaves, in
+/// increasing index order. Parents whose subtree isn't complete yet are
+/// skipped. Shorthand for `nodes(leaves, Order::InOrder)`.
+///
+/// ## Examples
+/// ```rust
+/// let all: Vec<usize> = flat_tree::inorder(3).collect();
+/// assert_eq!(all, [0, 1, 2, 4]);
+///
+/// let all: Vec<usize> = flat_tree::inorder(6).collect();
+/// assert_eq!(all, [0, 1, 2, 3, 4, 5, 6, 8, 9, 10]);
+/// ```
+pub fn inorder(leaves: usize) -> Nodes {
+  nodes(leaves, Order::InOrder)
+}
+
+/// Returns an iterator over every node in the subtree rooted at `root`, in
+/// increasing index order. Shorthand for
+/// `subtree_nodes(root, Order::InOrder)`.
+///
+/// ## Examples
+/// ```rust
+/// let all: Vec<usize> = flat_tree::inorder_subtree(9).collect();
+/// assert_eq!(all, [8, 9, 10]);
+/// ```
+pub fn inorder_subtree(root: usize) -> Nodes {
+  subtree_nodes(root, Order::InOrder)
+}
+
+/// Returns an iterator over every node in a tree with `leaves` leaves,
+/// parents before their children. Shorthand for
+/// `nodes(leaves, Order::PreOrder)`.
+///
+/// ## Examples
+/// ```rust
+/// let all: Vec<usize> = flat_tree::preorder(6).collect();
+/// assert_eq!(all, [3, 1, 0, 2, 5, 4, 6, 9, 8, 10]);
+/// ```
+pub fn preorder(leaves: usize) -> Nodes {
+  nodes(leaves, Order::PreOrder)
+}
+
+/// Returns an iterator over every node in the subtree rooted at `root`,
+/// parents before their children. Shorthand for
+/// `subtree_nodes(root, Order::PreOrder)`.
+///
+/// ## Examples
+/// ```rust
+/// let all: Vec<usize> = flat_tree::preorder_subtree(11).collect();
+/// assert_eq!(all, [11, 9, 8, 10, 13, 12, 14]);
+/// ```
+pub fn preorder_subtree(root: usize) -> Nodes {
+  subtree_nodes(root, Order::PreOrder)
+}
+
+/// Returns an iterator over every node in a tree with `leaves` leaves,
+/// children before their parents. This is the order in which the hashes of
+/// the nodes become computable. Shorthand for
+/// `nodes(leaves, Order::PostOrder)`.
+///
+/// ## Examples
+/// ```rust
+/// let all: Vec<usize> = flat_tree::postorder(6).collect();
+/// assert_eq!(all, [0, 2, 1, 4, 6, 5, 3, 8, 10, 9]);
+/// ```
+pub fn postorder(leaves: usize) -> Nodes {
+  nodes(leaves, Order::PostOrder)
+}
+
+/// Returns an iterator over every node in the subtree rooted at `root`,
+/// children before their parents. Shorthand for
+/// `subtree_nodes(root, Order::PostOrder)`.
+///
+/// ## Examples
+/// ```rust
+/// let all: Vec<usize> = flat_tree::postorder_subtree(11).collect();
+/// assert_eq!(all, [8, 10, 9, 12, 14, 13, 11]);
+/// ```
+pub fn postorder_subtree(root: usize) -> Nodes {
+  subtree_nodes(root, Order::PostOrder)
+}
+
+/// Returns an iterator over every node in a tree with `leaves` leaves, level
+/// by level starting at the root. Shorthand for
+/// `nodes(leaves, Order::LevelOrder)`.
+///
+/// ## Examples
+/// ```rust
+/// let all: Vec<usize> = flat_tree::levelorder(6).collect();
+/// assert_eq!(all, [3, 1, 5, 9, 0, 2, 4, 6, 8, 10]);
+/// ```
+pub fn levelorder(leaves: usize) -> Nodes {
+  nodes(leaves, Order::LevelOrder)
+}
+
+/// Returns an iterator over every node in the subtree rooted at `root`,
+/// level by level starting at `root`. Shorthand for
+/// `subtree_nodes(root, Order::LevelOrder)`.
+///
+/// ## Examples
+/// ```rust
+/// let all: Vec<usize> = flat_tree::levelorder_subtree(11).collect();
+/// assert_eq!(all, [11, 9, 13, 8, 10, 12, 14]);
+/// ```
+pub fn levelorder_subtree(root: usize) -> Nodes {
+  subtree_nodes(root, Order::LevelOrder)
+}
+
+impl Nodes {
+  fn new(
+    order: Order,
+    roots: &[usize],
+    leaves: usize,
+    start: usize,
+    end: usize,
+  ) -> Self {
+    let remaining = roots.iter().map(|&root| count(root)).sum();
+    let level = roots.first().map_or(0, |&root| depth(root));
+    let (next, stack) = match order {
+      Order::InOrder => (start, Vec::new()),
+      Order::LevelOrder => ((start / 2) >> level, Vec::new()),
+      _ => (0, roots.iter().rev().map(|&root| (root, false)).collect()),
+    };
+
+    Nodes {
+      order,
+      leaves,
+      start,
+      next,
+      end,
+      level,
+      stack,
+      remaining,
+    }
+  }
+
+  /// Yield the `Coords` of each index instead of the bare index.
+  pub fn with_coords(self) -> WithCoords<Self> {
+    WithCoords::new(self)
+  }
+
+  fn next_in_order(&mut self) -> Option<usize> {
+    while self.next < self.end {
+      let index = self.next;
+      self.next += 1;
+      if in_tree(index, self.leaves) {
+        return Some(index);
+      }
+    }
+    None
+  }
+
+  fn next_level_order(&mut self) -> Option<usize> {
+    while self.remaining > 0 {
+      let index = index(self.level, self.next);
+      if index < self.end && in_tree(index, self.leaves) {
+        self.next += 1;
+        return Some(index);
+      }
+      self.level -= 1;
+      self.next = (self.start / 2) >> self.level;
+    }
+    None
+  }
+
+  fn next_pre_order(&mut self) -> Option<usize> {
+    let (index, _) = self.stack.pop()?;
+    if let Some((left, right)) = children(index) {
+      self.stack.push((right, false));
+      self.stack.push((left, false));
+    }
+    Some(index)
+  }
+
+  fn next_post_order(&mut self) -> Option<usize> {
+    loop {
+      let (index, expanded) = self.stack.pop()?;
+      match children(index) {
+        Some((left, right)) if !expanded => {
+          self.stack.push((index, true));
+          self.stack.push((right, false));
+          self.stack.push((left, false));
+        }
+        _ => return Some(index),
+      }
+    }
+  }
+}
+
+impl iter::Iterator for Nodes {
+  type Item = usize;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let next = match self.order {
+      Order::InOrder => self.next_in_order(),
+      Order::PreOrder => self.next_pre_order(),
+      Order::PostOrder => self.next_post_order(),
+      Order::LevelOrder => self.next_level_order(),
+    };
+    if next.is_some() {
+      self.remaining -= 1;
+    }
+    next
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.remaining, Some(self.remaining))
+  }
+}
+
+impl iter::ExactSizeIterator for Nodes {}
+
+impl iter::FusedIterator for Nodes {}
+
+/// Iterator over the ancestors of a node, from its parent upwards.
+///
+/// By default it stops at `Iterator::MAX_DEPTH`, use `to_depth()` or
+/// `within()` to stop earlier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ancestors {
+  index: usize,
+  depth: usize,
+  max_depth: usize,
+}
+
+/// Returns an iterator over `parent(i)`, `parent(parent(i))` and so on.
+///
+/// ## Examples
+/// ```rust
+/// let ancestors: Vec<usize> = flat_tree::ancestors(0).take(3).collect();
+/// assert_eq!(ancestors, [1, 3, 7]);
+///
+/// let ancestors: Vec<usize> = flat_tree::ancestors(8).to_depth(2).collect();
+/// assert_eq!(ancestors, [9, 11]);
+///
+/// let ancestors: Vec<usize> = flat_tree::ancestors(8).within(6).collect();
+/// assert_eq!(ancestors, [9]);
+/// ```
+pub fn ancestors(i: usize) -> Ancestors {
+  Ancestors {
+    index: i,
+    depth: depth(i),
+    max_depth: Iterator::MAX_DEPTH,
+  }
+}
+
+/// Returns an iterator over the nodes on the path from `node` up to
+/// `ancestor`, excluding `node` and including `ancestor`.
+///
+/// ## Errors
+/// If `ancestor` doesn't span `node`.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::{path_to_ancestor, NotAncestor};
+///
+/// let path: Vec<usize> = path_to_ancestor(4, 7).unwrap().collect();
+/// assert_eq!(path, [5, 3, 7]);
+///
+/// assert_eq!(path_to_ancestor(4, 4).unwrap().count(), 0);
+///
+/// assert_eq!(
+///   path_to_ancestor(4, 9).unwrap_err(),
+///   NotAncestor { node: 4, ancestor: 9 }
+/// );
+/// ```
+pub fn path_to_ancestor(
+  node: usize,
+  ancestor: usize,
+) -> Result<Ancestors, NotAncestor> {
+  let (left, right) = spans(ancestor);
+  if node < left || node > right {
+    return Err(NotAncestor { node, ancestor });
+  }
+  Ok(ancestors(node).to_depth(depth(ancestor)))
+}
+
+/// Error returned when a node isn't spanned by the expected ancestor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotAncestor {
+  /// The node.
+  pub node: usize,
+  /// The node that was expected to span `node`.
+  pub ancestor: usize,
+}
+
+impl fmt::Display for NotAncestor {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "node {} doesn't span node {}", self.ancestor, self.node)
+  }
+}
+
+impl error::Error for NotAncestor {}
+
+impl Ancestors {
+  /// Stop at the ancestor at `depth`.
+  pub fn to_depth(mut self, depth: usize) -> Self {
+    self.max_depth = self.max_depth.min(depth);
+    self
+  }
+
+  /// Stop at the root of a tree with `leaves` leaves.
+  pub fn within(mut self, leaves: usize) -> Self {
+    let mut index = self.index;
+    let mut depth = self.depth;
+    if !in_tree(index, leaves) {
+      self.max_depth = self.depth;
+      return self;
+    }
+    while depth < self.max_depth {
+      let parent = parent_with_depth(index, depth);
+      if !in_tree(parent, leaves) {
+        break;
+      }
+      index = parent;
+      depth += 1;
+    }
+    self.max_depth = depth;
+    self
+  }
+
+  /// Yield the `Coords` of each index instead of the bare index.
+  pub fn with_coords(self) -> WithCoords<Self> {
+    WithCoords::new(self)
+  }
+}
+
+impl iter::Iterator for Ancestors {
+  type Item = usize;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.depth >= self.max_depth {
+      return None;
+    }
+    self.index = parent_with_depth(self.index, self.depth);
+    self.depth += 1;
+    Some(self.index)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.max_depth.saturating_sub(self.depth);
+    (len, Some(len))
+  }
+}
+
+impl iter::ExactSizeIterator for Ancestors {}
+
+impl iter::FusedIterator for Ancestors {}
+
+/// Iterator over the descendants of a node, in increasing index order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Descendants {
+  root: usize,
+  root_depth: usize,
+  next: usize,
+  last: usize,
+  step: usize,
+}
+
+/// Returns an iterator over every node in the subtree rooted at `i`,
+/// excluding `i` itself, in increasing index order.
+///
+/// ## Examples
+/// ```rust
+/// let descendants: Vec<usize> = flat_tree::descendants(3).collect();
+/// assert_eq!(descendants, [0, 1, 2, 4, 5, 6]);
+///
+/// let descendants: Vec<usize> = flat_tree::descendants(7).to_depth(1).collect();
+/// assert_eq!(descendants, [1, 3, 5, 9, 11, 13]);
+///
+/// assert_eq!(flat_tree::descendants(4).count(), 0);
+/// ```
+pub fn descendants(i: usize) -> Descendants {
+  let depth = depth(i);
+  let (left, right) = spans_with_depth(i, depth);
+  Descendants {
+    root: i,
+    root_depth: depth,
+    next: left,
+    last: right,
+    step: 1,
+  }
+}
+
+impl Descendants {
+  /// Only yield the descendants at `depth` or above.
+  pub fn to_depth(mut self, depth: usize) -> Self {
+    if depth >= self.root_depth {
+      self.next = self.root;
+      self.last = self.root;
+    } else {
+      let (left, right) = spans_with_depth(self.root, self.root_depth);
+      self.next = left + (1 << depth) - 1;
+      self.last = right + 1 - (1 << depth);
+      self.step = 1 << depth;
+    }
+    self
+  }
+
+  /// Yield the `Coords` of each index instead of the bare index.
+  pub fn with_coords(self) -> WithCoords<Self> {
+    WithCoords::new(self)
+  }
+
+  fn is_exhausted(&self) -> bool {
+    self.next > self.last || (self.next == self.root && self.last == self.root)
+  }
+}
+
+impl iter::Iterator for Descendants {
+  type Item = usize;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.is_exhausted() {
+      return None;
+    }
+    if self.next == self.root {
+      self.next += self.step;
+    }
+    let index = self.next;
+    self.next += self.step;
+    Some(index)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = if self.is_exhausted() {
+      0
+    } else if self.next <= self.root && self.root <= self.last {
+      (self.last - self.next) / self.step
+    } else {
+      (self.last - self.next) / self.step + 1
+    };
+    (len, Some(len))
+  }
+}
+
+impl iter::DoubleEndedIterator for Descendants {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    if self.is_exhausted() {
+      return None;
+    }
+    if self.last == self.root {
+      self.last -= self.step;
+    }
+    let index = self.last;
+    if self.last < self.next + self.step {
+      self.next = self.last + 1;
+    } else {
+      self.last -= self.step;
+    }
+    Some(index)
+  }
+}
+
+impl iter::ExactSizeIterator for Descendants {}
+
+impl iter::FusedIterator for Descendants {}
+
+/// Iterator over the nodes from the root of a bounded tree down to a target
+/// node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PathFromRoot {
+  index: usize,
+  depth: usize,
+  target: usize,
+  remaining: usize,
+}
+
+/// Returns an iterator over the path from the root of the tree with `leaves`
+/// leaves that spans `target`, down to `target`. Both the root and `target`
+/// are included. This is the reverse of `ancestors(target).within(leaves)`.
+/// The iterator is empty if `target` isn't in the tree.
+///
+/// ## Examples
+/// ```rust
+/// let path: Vec<usize> = flat_tree::path_from_root(4, 4).collect();
+/// assert_eq!(path, [3, 5, 4]);
+///
+/// let path: Vec<usize> = flat_tree::path_from_root(10, 6).collect();
+/// assert_eq!(path, [9, 10]);
+///
+/// assert_eq!(flat_tree::path_from_root(12, 6).count(), 0);
+/// ```
+pub fn path_from_root(target: usize, leaves: usize) -> PathFromRoot {
+  let root = ancestors(target).within(leaves).last().unwrap_or(target);
+  let depth = depth(root);
+  let remaining = if in_tree(target, leaves) {
+    depth - self::depth(target) + 1
+  } else {
+    0
+  };
+  PathFromRoot {
+    index: root,
+    depth,
+    target,
+    remaining,
+  }
+}
+
+impl PathFromRoot {
+  /// Yield the `Coords` of each index instead of the bare index.
+  pub fn with_coords(self) -> WithCoords<Self> {
+    WithCoords::new(self)
+  }
+}
+
+impl iter::Iterator for PathFromRoot {
+  type Item = usize;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.remaining == 0 {
+      return None;
+    }
+    let index = self.index;
+    self.remaining -= 1;
+    if self.remaining > 0 {
+      let (left, right) = children_with_depth(index, self.depth)?;
+      self.index = if self.target < index { left } else { right };
+      self.depth -= 1;
+    }
+    Some(index)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.remaining, Some(self.remaining))
+  }
+}
+
+impl iter::ExactSizeIterator for PathFromRoot {}
+
+impl iter::FusedIterator for PathFromRoot {}
+
+/// Iterator over the siblings needed to verify a node against the root of
+/// its bounded tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AuditPath {
+  ancestors: Ancestors,
+}
+
+/// Returns an iterator over the audit path of `node` in a tree with `leaves`
+/// leaves: the sibling of `node`, then its uncle, and so on up to, but
+/// excluding, the root of the tree that spans `node`. These are the nodes of
+/// a merkle inclusion proof, from the bottom up. The iterator is empty if
+/// `node` isn't in the tree.
+///
+/// ## Examples
+/// ```rust
+/// let path: Vec<usize> = flat_tree::audit_path(0, 8).collect();
+/// assert_eq!(path, [2, 5, 11]);
+///
+/// let path: Vec<usize> = flat_tree::audit_path(4, 6).collect();
+/// assert_eq!(path, [6, 1]);
+///
+/// assert_eq!(flat_tree::audit_path(8, 5).count(), 0);
+/// ```
+pub fn audit_path(node: usize, leaves: usize) -> AuditPath {
+  AuditPath {
+    ancestors: ancestors(node).within(leaves),
+  }
+}
+
+impl AuditPath {
+  /// Yield the `Coords` of each index instead of the bare index.
+  pub fn with_coords(self) -> WithCoords<Self> {
+    WithCoords::new(self)
+  }
+}
+
+impl iter::Iterator for AuditPath {
+  type Item = usize;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let Ancestors { index, depth, .. } = self.ancestors;
+    self.ancestors.next()?;
+    Some(sibling_with_depth(index, depth))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.ancestors.size_hint()
+  }
+}
+
+impl iter::ExactSizeIterator for AuditPath {}
+
+impl iter::FusedIterator for AuditPath {}
+
+/// Iterator over the parents of a bounded tree, level by level from the
+/// bottom up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParentsByLevel {
+  leaves: usize,
+  depth: usize,
+  offset: usize,
+  remaining: usize,
+}
+
+/// Returns an iterator over every parent in a tree with `leaves` leaves,
+/// together with its left and right child. All parents at depth 1 are
+/// yielded first, from left to right, then the ones at depth 2 and so on, so
+/// the children of a parent are always yielded before it.
+///
+/// ## Panics
+/// If `2 * leaves` doesn't fit in a `usize`.
+///
+/// ## Examples
+/// ```rust
+/// let parents: Vec<_> = flat_tree::parents_by_level(5).collect();
+/// assert_eq!(parents, [(1, 0, 2), (5, 4, 6), (3, 1, 5)]);
+/// ```
+pub fn parents_by_level(leaves: usize) -> ParentsByLevel {
+  let mut roots = Vec::new();
+  full_roots(leaves_end(leaves), &mut roots);
+  ParentsByLevel {
+    leaves,
+    depth: 1,
+    offset: 0,
+    remaining: leaves - roots.len(),
+  }
+}
+
+impl iter::Iterator for ParentsByLevel {
+  type Item = (usize, usize, usize);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while self.remaining > 0 {
+      let index = index(self.depth, self.offset);
+      if in_tree(index, self.leaves) {
+        self.offset += 1;
+        self.remaining -= 1;
+        let (left, right) = children_with_depth(index, self.depth)?;
+        return Some((index, left, right));
+      }
+      self.depth += 1;
+      self.offset = 0;
+    }
+    None
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.remaining, Some(self.remaining))
+  }
+}
+
+impl iter::ExactSizeIterator for ParentsByLevel {}
+
+impl iter::FusedIterator for ParentsByLevel {}
+
+/// Returns `2 * leaves`, the index after the last leaf of a tree.
+fn leaves_end(leaves: usize) -> usize {
+  leaves.checked_mul(2).unwrap_or_else(|| {
+    panic!(
+      "A tree with {} leaves has more nodes than fit in a usize",
+      leaves
+    )
+  })
+}
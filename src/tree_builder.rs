@@ -0,0 +1,131 @@
+//! Build a merkle tree incrementally, like merkle-tree-stream.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::TreeBuilder;
+//!
+//! let mut builder = TreeBuilder::new(|_, left: &String, right: &String| {
+//!   format!("({}{})", left, right)
+//! });
+//! assert_eq!(builder.push("a".to_string()), [(0, "a".to_string())]);
+//! assert_eq!(
+//!   builder.push("b".to_string()),
+//!   [(2, "b".to_string()), (1, "(ab)".to_string())]
+//! );
+//! ```
+use super::*;
+
+use std::fmt;
+use std::iter;
+
+/// Builds a merkle tree from leaves pushed one at a time, emitting each
+/// node, with its flat-tree index, as soon as its value is known.
+///
+/// The value of a parent is computed by a user function, which is called
+/// with the index of the parent and the values of its left and right
+/// child, like in `Verifier`.
+#[derive(Clone)]
+pub struct TreeBuilder<T, F> {
+  frontier: Frontier,
+  values: Vec<T>,
+  parent: F,
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for TreeBuilder<T, F> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("TreeBuilder")
+      .field("frontier", &self.frontier)
+      .field("values", &self.values)
+      .finish()
+  }
+}
+
+impl<T, F> TreeBuilder<T, F>
+where
+  T: Clone,
+  F: FnMut(usize, &T, &T) -> T,
+{
+  /// Create a builder for an empty tree, which computes the value of
+  /// parents with `parent`.
+  pub fn new(parent: F) -> Self {
+    Self {
+      frontier: Frontier::new(),
+      values: Vec::new(),
+      parent,
+    }
+  }
+
+  /// Append a leaf with value `leaf`. Returns the new leaf and the parents
+  /// it completes, from the bottom up, with their values.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::TreeBuilder;
+  ///
+  /// let sum = |_: usize, left: &u32, right: &u32| left + right;
+  /// let mut builder = TreeBuilder::new(sum);
+  /// for value in 1..4 {
+  ///   builder.push(value);
+  /// }
+  /// assert_eq!(builder.push(4), [(6, 4), (5, 7), (3, 10)]);
+  /// ```
+  pub fn push(&mut self, leaf: T) -> Vec<(usize, T)> {
+    let index = self.frontier.push();
+    let mut nodes = vec![(index, leaf.clone())];
+    let mut value = leaf;
+    for parent in completed_parents(index) {
+      let left = self
+        .values
+        .pop()
+        .expect("Frontier has a root for every completed parent");
+      value = (self.parent)(parent, &left, &value);
+      nodes.push((parent, value.clone()));
+    }
+    self.values.push(value);
+    nodes
+  }
+}
+
+impl<T, F> TreeBuilder<T, F> {
+  /// Get the number of leaves pushed so far.
+  #[inline]
+  pub fn leaves(&self) -> usize {
+    self.frontier.leaves()
+  }
+
+  /// Check if no leaves were pushed yet.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.frontier.is_empty()
+  }
+
+  /// Get the frontier of the tree built so far.
+  #[inline]
+  pub fn frontier(&self) -> &Frontier {
+    &self.frontier
+  }
+
+  /// Get the full roots of the tree built so far, from left to right, with
+  /// their values.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::TreeBuilder;
+  ///
+  /// let sum = |_: usize, left: &u32, right: &u32| left + right;
+  /// let mut builder = TreeBuilder::new(sum);
+  /// for value in 1..4 {
+  ///   builder.push(value);
+  /// }
+  /// let roots: Vec<_> = builder.roots().collect();
+  /// assert_eq!(roots, [(1, &3), (4, &3)]);
+  /// ```
+  pub fn roots(&self) -> impl iter::Iterator<Item = (usize, &T)> {
+    self
+      .frontier
+      .roots()
+      .iter()
+      .cloned()
+      .zip(self.values.iter())
+  }
+}
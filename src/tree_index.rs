@@ -0,0 +1,449 @@
+//! Track which nodes of a flat-tree are present, as done by hypercore's
+//! tree-index.
+//!
+//! ## Usage
+//! ```rust
+//! let mut tree = flat_tree::TreeIndex::new();
+//! tree.set(0);
+//! assert!(!tree.get(1));
+//! tree.set(2);
+//! assert!(tree.get(1));
+//! ```
+use super::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::cmp;
+
+/// Options for `TreeIndex::proof()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProofOptions<'a> {
+  hash: bool,
+  digest: usize,
+  remote: Option<&'a TreeIndex>,
+}
+
+impl<'a> ProofOptions<'a> {
+  /// Create the default options: don't include the requested node, and
+  /// assume the remote peer has no nodes.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Include the requested node itself in the proof.
+  pub fn hash(mut self, hash: bool) -> Self {
+    self.hash = hash;
+    self
+  }
+
+  /// Skip the nodes the remote peer reports having in a digest, see
+  /// `TreeIndex::digest()`.
+  pub fn digest(mut self, digest: usize) -> Self {
+    self.digest = digest;
+    self
+  }
+
+  /// Skip the nodes the remote peer already has.
+  pub fn remote(mut self, remote: &'a TreeIndex) -> Self {
+    self.remote = Some(remote);
+    self
+  }
+}
+
+/// The nodes needed by a remote peer to verify a node.
+///
+/// Created by `TreeIndex::proof()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proof {
+  /// The nodes to send, in the order they're needed.
+  pub nodes: Vec<usize>,
+  /// The index after the last leaf of the tree whose roots verify the
+  /// nodes, or `0` if the remote peer can verify them with the nodes it
+  /// already has.
+  pub verified_by: usize,
+}
+
+/// Presence of the nodes of a flat-tree, stored in a `Bitfield`. By default
+/// the bitfield is a growable `Vec<u8>`.
+///
+/// Setting a node also sets every parent whose children are both present.
+///
+/// With the `serde` feature a tree index is serialized as its bitfield, so
+/// a `TreeIndex` snapshot is the bytes of its `Vec<u8>`, see `Bitfield` for
+/// the bit order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(transparent)
+)]
+pub struct TreeIndex<B = Vec<u8>> {
+  bits: B,
+}
+
+impl TreeIndex {
+  /// Create a new, empty, tree index.
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+impl<B: Bitfield> TreeIndex<B> {
+  /// Create a tree index over an existing bitfield.
+  pub fn from_bitfield(bits: B) -> Self {
+    Self { bits }
+  }
+
+  /// Get a reference to the underlying bitfield.
+  #[inline]
+  pub fn bitfield(&self) -> &B {
+    &self.bits
+  }
+
+  /// Unwrap the underlying bitfield.
+  pub fn into_inner(self) -> B {
+    self.bits
+  }
+
+  /// Check if a node is present.
+  #[inline]
+  pub fn get(&self, index: usize) -> bool {
+    self.bits.get(index)
+  }
+
+  /// Mark a node as present. Every parent whose children are now both
+  /// present is marked as well. Returns `false` if the node was already
+  /// present.
+  ///
+  /// ## Panics
+  /// If the bitfield can't grow to hold the node, or a parent being marked.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut tree = flat_tree::TreeIndex::new();
+  /// assert!(tree.set(4));
+  /// assert!(tree.set(6));
+  /// assert!(tree.get(5));
+  /// assert!(!tree.get(3));
+  /// assert!(!tree.set(6));
+  /// ```
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "trace", skip(self), ret)
+  )]
+  pub fn set(&mut self, index: usize) -> bool {
+    if !self.set_bit(index) {
+      return false;
+    }
+    let mut index = index;
+    while self.get(sibling(index)) {
+      index = parent(index);
+      if !self.set_bit(index) {
+        break;
+      }
+      #[cfg(feature = "tracing")]
+      tracing::trace!(parent = index, "completed parent");
+    }
+    true
+  }
+
+  /// Get the nodes needed by a remote peer to verify `index`: the sibling
+  /// at every level up to a node the remote peer has, or else up to a root,
+  /// followed by the other roots of the tree that verify it. Nodes the
+  /// remote peer already has are skipped. Returns `None` if `index` isn't
+  /// present.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::{ProofOptions, TreeIndex};
+  ///
+  /// let mut tree = TreeIndex::new();
+  /// for leaf in &[0, 2, 4] {
+  ///   tree.set(*leaf);
+  /// }
+  /// let proof = tree.proof(0, ProofOptions::new()).unwrap();
+  /// assert_eq!(proof.nodes, [2, 4]);
+  /// assert_eq!(proof.verified_by, 6);
+  /// assert_eq!(tree.proof(6, ProofOptions::new()), None);
+  /// ```
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+      level = "debug",
+      skip(self, opts),
+      fields(digest = opts.digest, hash = opts.hash),
+      ret
+    )
+  )]
+  pub fn proof(&self, index: usize, opts: ProofOptions) -> Option<Proof> {
+    if !self.get(index) {
+      return None;
+    }
+    let mut remote = opts.remote.cloned().unwrap_or_default();
+    self.decode_digest(index, opts.digest, &mut remote);
+    let mut nodes = Vec::new();
+    if opts.hash {
+      nodes.push(index);
+    }
+
+    let mut next = index;
+    while !remote.get(next) {
+      let sibling = sibling(next);
+      if !self.get(sibling) {
+        let verified_by = self.verified_by(next);
+        let mut roots = Vec::new();
+        full_roots(verified_by, &mut roots);
+        for root in roots {
+          if root != next && !remote.get(root) {
+            nodes.push(root);
+          }
+        }
+        return Some(Proof { nodes, verified_by });
+      }
+      if !remote.get(sibling) {
+        nodes.push(sibling);
+      }
+      next = parent(next);
+    }
+
+    Some(Proof {
+      nodes,
+      verified_by: 0,
+    })
+  }
+
+  /// Summarize which nodes needed to verify `index` are present, so a
+  /// remote peer can skip them when sending a proof for `index`.
+  ///
+  /// Returns `1` if `index` itself is present. Otherwise bit `n` of the
+  /// digest is set if the sibling at level `n - 1` on the path from `index`
+  /// upwards is present. If an ancestor is present, its bit is set along
+  /// with bit `0`, and no higher bits are set. A present ancestor implies
+  /// the roots to its left are present as well.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut tree = flat_tree::TreeIndex::new();
+  /// assert_eq!(tree.digest(0), 0b0);
+  /// tree.set(2);
+  /// assert_eq!(tree.digest(0), 0b10);
+  /// tree.set(3);
+  /// assert_eq!(tree.digest(0), 0b1011);
+  /// tree.set(0);
+  /// assert_eq!(tree.digest(0), 0b1);
+  /// ```
+  pub fn digest(&self, index: usize) -> usize {
+    if self.get(index) {
+      return 1;
+    }
+
+    let mut digest = 0;
+    let mut next = sibling(index);
+    let max = cmp::max(next + 2, self.len());
+    let mut bit = 2;
+    let mut depth = depth(index);
+    let mut parent = parent_with_depth(next, depth);
+    depth += 1;
+
+    while right_span(next) < max || left_span(parent) > 0 {
+      if self.get(next) {
+        digest += bit;
+      }
+      if self.get(parent) {
+        digest += 2 * bit;
+        if is_even(digest) {
+          digest += 1;
+        }
+        if digest + 1 == 4 * bit {
+          return 1;
+        }
+        return digest;
+      }
+      next = sibling(parent);
+      parent = parent_with_depth(next, depth);
+      depth += 1;
+      bit *= 2;
+    }
+
+    digest
+  }
+
+  /// Mark the nodes a remote peer reported having, in a digest for `index`,
+  /// as present in `remote`. Only nodes that are present locally are
+  /// marked. This is the inverse of `digest()`, as used when serving a
+  /// proof.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut tree = flat_tree::TreeIndex::new();
+  /// tree.set(0);
+  /// tree.set(2);
+  ///
+  /// let mut remote = flat_tree::TreeIndex::new();
+  /// tree.decode_digest(0, 0b10, &mut remote);
+  /// assert!(remote.get(2));
+  /// assert!(!remote.get(0));
+  /// ```
+  pub fn decode_digest<R: Bitfield>(
+    &self,
+    index: usize,
+    digest: usize,
+    remote: &mut TreeIndex<R>,
+  ) {
+    if digest == 1 {
+      if self.get(index) {
+        remote.set(index);
+      }
+      return;
+    }
+
+    let has_root = is_odd(digest);
+    let mut digest = digest >> 1;
+    let mut next = index;
+    while digest > 0 {
+      if digest == 1 && has_root {
+        if self.get(next) {
+          remote.set(next);
+        }
+        // Having a root implies having the roots to its left as well.
+        if sibling(next) < next {
+          next = sibling(next);
+        }
+        let mut roots = Vec::new();
+        full_roots(right_span(next) + 2, &mut roots);
+        for root in roots {
+          if self.get(root) {
+            remote.set(root);
+          }
+        }
+        return;
+      }
+      if is_odd(digest) && self.get(sibling(next)) {
+        remote.set(sibling(next));
+      }
+      next = parent(next);
+      digest >>= 1;
+    }
+  }
+
+  /// Get the number of leaves in the verified prefix: the contiguous run of
+  /// present nodes starting at the first leaf.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut tree = flat_tree::TreeIndex::new();
+  /// assert_eq!(tree.blocks(), 0);
+  /// tree.set(0);
+  /// tree.set(4);
+  /// assert_eq!(tree.blocks(), 1);
+  /// tree.set(2);
+  /// assert_eq!(tree.blocks(), 3);
+  /// ```
+  pub fn blocks(&self) -> usize {
+    let mut top = 0;
+    let mut next = 0;
+    let max = self.len();
+    while right_span(next) < max {
+      next = parent(next);
+      if self.get(next) {
+        top = next;
+      }
+    }
+    self.verified_by(top) / 2
+  }
+
+  /// Get the roots of the verified prefix, see `blocks()`.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut tree = flat_tree::TreeIndex::new();
+  /// for leaf in &[0, 2, 4] {
+  ///   tree.set(*leaf);
+  /// }
+  /// assert_eq!(tree.roots(), [1, 4]);
+  /// ```
+  pub fn roots(&self) -> Vec<usize> {
+    let mut roots = Vec::new();
+    full_roots(2 * self.blocks(), &mut roots);
+    roots
+  }
+
+  /// Get the index after the last leaf of the contiguous run of present
+  /// nodes that `index` is part of. The roots of a tree of that length, see
+  /// `full_roots()`, verify `index`. Returns `0` if `index` isn't present.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut tree = flat_tree::TreeIndex::new();
+  /// tree.set(0);
+  /// tree.set(2);
+  /// assert_eq!(tree.verified_by(0), 4);
+  /// tree.set(6);
+  /// assert_eq!(tree.verified_by(0), 4);
+  /// tree.set(4);
+  /// assert_eq!(tree.verified_by(0), 8);
+  /// assert_eq!(tree.verified_by(8), 0);
+  /// ```
+  pub fn verified_by(&self, index: usize) -> usize {
+    if !self.get(index) {
+      return 0;
+    }
+
+    // Find the root of the tree the node is in.
+    let mut depth = depth(index);
+    let mut top = index;
+    let mut parent = parent_with_depth(top, depth);
+    depth += 1;
+    while self.get(parent) && self.get(sibling(top)) {
+      top = parent;
+      parent = parent_with_depth(top, depth);
+      depth += 1;
+    }
+
+    // Expand to the right, down to the leaves.
+    depth -= 1;
+    while depth > 0 {
+      let next = super::index(depth, offset_with_depth(top, depth) + 1);
+      top = next - two_pow(depth - 1);
+      depth -= 1;
+      while !self.get(top) && depth > 0 {
+        top -= two_pow(depth - 1);
+        depth -= 1;
+      }
+    }
+
+    if self.get(top) {
+      top + 2
+    } else {
+      top
+    }
+  }
+
+  /// Get the number of bits in the bitfield. Every node at or past this
+  /// index is absent.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.bits.len()
+  }
+
+  /// Check if the bitfield is empty.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.bits.is_empty()
+  }
+
+  fn set_bit(&mut self, index: usize) -> bool {
+    if index >= self.bits.len() {
+      assert!(
+        self.bits.grow(index + 1),
+        "The bitfield can't grow to hold node {}",
+        index
+      );
+    }
+    self.bits.set(index, true)
+  }
+}
+
+fn two_pow(n: usize) -> usize {
+  1 << n
+}
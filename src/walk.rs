@@ -0,0 +1,145 @@
+//! ## Usage
+//! ```rust
+//! use flat_tree::{walk, Step};
+//!
+//! let mut visited = Vec::new();
+//! walk(7, 8, |node, ctx| {
+//!   visited.push(node);
+//!   if ctx.depth == 1 {
+//!     Step::SkipSubtree
+//!   } else {
+//!     Step::Descend
+//!   }
+//! });
+//! assert_eq!(visited, [7, 3, 1, 5, 11, 9, 13]);
+//! ```
+use super::*;
+
+/// What `walk()` should do after visiting a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+  /// Visit the children of the node.
+  Descend,
+  /// Don't visit the children of the node.
+  SkipSubtree,
+  /// Stop walking.
+  Stop,
+}
+
+/// Information about the node being visited by `walk()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalkContext {
+  /// The depth of the node.
+  pub depth: usize,
+  /// The offset of the node.
+  pub offset: usize,
+  /// The number of steps between the node and the root of the walk.
+  pub level: usize,
+  /// The number of leaves in the tree.
+  pub leaves: usize,
+}
+
+impl WalkContext {
+  /// Check if the node is a leaf.
+  #[inline]
+  pub fn is_leaf(&self) -> bool {
+    self.depth == 0
+  }
+}
+
+/// Walks the subtree rooted at `root`, in a tree with `leaves` leaves,
+/// parents before their children. The closure decides after every node
+/// whether to descend into its children, skip them, or stop. Nodes that
+/// aren't in the tree are never visited.
+///
+/// Returns the node the walk was stopped at, if any.
+///
+/// ## Examples
+/// ```rust
+/// use flat_tree::{walk, Step};
+///
+/// let found = walk(7, 8, |node, _| {
+///   if node == 10 {
+///     Step::Stop
+///   } else if flat_tree::spans(node).0 <= 10 && 10 <= flat_tree::spans(node).1 {
+///     Step::Descend
+///   } else {
+///     Step::SkipSubtree
+///   }
+/// });
+/// assert_eq!(found, Some(10));
+///
+/// let mut count = 0;
+/// walk(7, 6, |_, _| {
+///   count += 1;
+///   Step::Descend
+/// });
+/// assert_eq!(count, 0);
+/// ```
+pub fn walk<F>(root: usize, leaves: usize, mut f: F) -> Option<usize>
+where
+  F: FnMut(usize, &WalkContext) -> Step,
+{
+  if !in_tree(root, leaves) {
+    return None;
+  }
+  let root_depth = depth(root);
+  let mut stack = vec![(root, root_depth)];
+  while let Some((node, depth)) = stack.pop() {
+    let ctx = WalkContext {
+      depth,
+      offset: offset_with_depth(node, depth),
+      level: root_depth - depth,
+      leaves,
+    };
+    match f(node, &ctx) {
+      Step::Stop => return Some(node),
+      Step::SkipSubtree => {}
+      Step::Descend => {
+        if let Some((left, right)) = children_with_depth(node, depth) {
+          stack.push((right, depth - 1));
+          stack.push((left, depth - 1));
+        }
+      }
+    }
+  }
+  None
+}
+
+/// Folds the subtree rooted at `root` bottom-up. Every leaf is turned into a
+/// value by `init`, and every parent combines the values of its left and
+/// right child with `f`. Returns the value of `root`.
+///
+/// ## Examples
+/// ```rust
+/// let leaves = flat_tree::fold_subtree(7, |_| 1, |_, left, right| left + right);
+/// assert_eq!(leaves, 8);
+///
+/// let sum = flat_tree::fold_subtree(3, |leaf| leaf, |_, left, right| left + right);
+/// assert_eq!(sum, 0 + 2 + 4 + 6);
+///
+/// let tree = flat_tree::fold_subtree(
+///   5,
+///   |leaf| leaf.to_string(),
+///   |parent, left, right| format!("({} {} {})", left, parent, right),
+/// );
+/// assert_eq!(tree, "(4 5 6)");
+/// ```
+pub fn fold_subtree<T, L, F>(root: usize, mut init: L, mut f: F) -> T
+where
+  L: FnMut(usize) -> T,
+  F: FnMut(usize, T, T) -> T,
+{
+  let mut values = Vec::with_capacity(depth(root) + 1);
+  for node in postorder_subtree(root) {
+    let value = if is_even(node) {
+      init(node)
+    } else {
+      let right = values.pop().unwrap(); // children are always folded first
+      let left = values.pop().unwrap();
+      f(node, left, right)
+    };
+    values.push(value);
+  }
+  values.pop().unwrap() // the root is always folded last
+}
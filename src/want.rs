@@ -0,0 +1,88 @@
+//! Compare the nodes present locally with the ones a remote peer has.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::TreeIndex;
+//!
+//! let mut local = TreeIndex::new();
+//! local.set(0);
+//!
+//! let mut remote = TreeIndex::new();
+//! for leaf in &[0, 2, 4, 6, 8] {
+//!   remote.set(*leaf);
+//! }
+//!
+//! assert_eq!(local.want(&remote).as_slice(), [2, 5, 8]);
+//! ```
+use super::*;
+
+use std::cmp;
+
+impl<B: Bitfield> TreeIndex<B> {
+  /// Get the smallest set of nodes to request from a remote peer, to get
+  /// every node it has that isn't present locally. A subtree the remote
+  /// peer has, where no node is present locally, is collapsed to its root.
+  ///
+  /// A node being present is taken to mean its whole subtree is, like when
+  /// it was set by `set()` because both children were.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// use flat_tree::TreeIndex;
+  ///
+  /// let local = TreeIndex::new();
+  /// let mut remote = TreeIndex::new();
+  /// remote.set(3);
+  /// assert_eq!(local.want(&remote).as_slice(), [3]);
+  /// ```
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+      level = "debug",
+      skip_all,
+      fields(local_len = self.len(), remote_len = remote.len())
+    )
+  )]
+  pub fn want<R: Bitfield>(&self, remote: &TreeIndex<R>) -> NodeSet {
+    let len = cmp::max(self.len(), remote.len());
+    let mut nodes = NodeSet::new();
+    if len == 0 {
+      return nodes;
+    }
+    let mut depth = 0;
+    while right_span_with_depth(index(depth, 0), depth) < len - 1 {
+      depth += 1;
+    }
+    self.want_subtree(remote, index(depth, 0), false, &mut nodes);
+    #[cfg(feature = "tracing")]
+    tracing::debug!(nodes = ?nodes.as_slice(), "wanted nodes");
+    nodes
+  }
+
+  fn want_subtree<R: Bitfield>(
+    &self,
+    remote: &TreeIndex<R>,
+    node: usize,
+    remote_has: bool,
+    nodes: &mut NodeSet,
+  ) {
+    if self.get(node) {
+      return;
+    }
+    let remote_has = remote_has || remote.get(node);
+    if remote_has && self.is_subtree_empty(node) {
+      nodes.insert(node);
+      return;
+    }
+    if let Some((left, right)) = children(node) {
+      self.want_subtree(remote, left, remote_has, nodes);
+      self.want_subtree(remote, right, remote_has, nodes);
+    }
+  }
+
+  /// Check if no node in the subtree rooted at `node` is present.
+  fn is_subtree_empty(&self, node: usize) -> bool {
+    let (left, right) = spans(node);
+    (left..cmp::min(right + 1, self.len())).all(|index| !self.get(index))
+  }
+}
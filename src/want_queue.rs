@@ -0,0 +1,132 @@
+//! Queue of wanted nodes, where a node covers its whole subtree.
+//!
+//! ## Usage
+//! ```rust
+//! use flat_tree::WantQueue;
+//!
+//! let mut queue = WantQueue::new();
+//! queue.insert(0, 5);
+//! queue.insert(4, 1);
+//! assert!(queue.insert(3, 3));
+//! assert!(!queue.insert(2, 0));
+//! assert_eq!(queue.len(), 1);
+//! assert_eq!(queue.pop().map(|request| request.node), Some(3));
+//! ```
+use super::*;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Priority queue of nodes to request, without overlap. Requesting a node
+/// is taken to mean requesting its whole subtree, so inserting a node
+/// supersedes its queued descendants, and a node is ignored if one of its
+/// ancestors is queued.
+///
+/// Like `Request`, a lower priority is more urgent. Nodes with the same
+/// priority are popped in prefix order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WantQueue {
+  nodes: BTreeMap<usize, usize>,
+  queue: BTreeSet<(usize, usize, usize)>,
+}
+
+impl WantQueue {
+  /// Create an empty queue.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Get the number of queued nodes.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.nodes.len()
+  }
+
+  /// Check if no nodes are queued.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.nodes.is_empty()
+  }
+
+  /// Check if `node` itself is queued.
+  #[inline]
+  pub fn contains(&self, node: usize) -> bool {
+    self.nodes.contains_key(&node)
+  }
+
+  /// Check if `node`, or one of its ancestors, is queued.
+  pub fn covers(&self, node: usize) -> bool {
+    self.contains(node) || ancestors(node).any(|parent| self.contains(parent))
+  }
+
+  /// Get the priority of a queued node.
+  #[inline]
+  pub fn priority(&self, node: usize) -> Option<usize> {
+    self.nodes.get(&node).cloned()
+  }
+
+  /// Queue `node` with `priority`. Its queued descendants are removed, and
+  /// it takes the most urgent of their priorities if that's more urgent
+  /// than `priority`. Returns `false`, and changes nothing, if `node` is
+  /// already covered, see `covers()`.
+  ///
+  /// ## Examples
+  /// ```rust
+  /// let mut queue = flat_tree::WantQueue::new();
+  /// queue.insert(8, 2);
+  /// queue.insert(12, 7);
+  /// assert!(queue.insert(11, 4));
+  /// assert_eq!(queue.priority(11), Some(2));
+  /// assert!(!queue.contains(8));
+  /// ```
+  pub fn insert(&mut self, node: usize, priority: usize) -> bool {
+    if self.covers(node) {
+      return false;
+    }
+    let (left, right) = spans(node);
+    let descendants: Vec<usize> = self
+      .nodes
+      .range(left..=right)
+      .map(|(&index, _)| index)
+      .collect();
+    let mut priority = priority;
+    for descendant in descendants {
+      let superseded = self.remove(descendant).expect("Descendant is queued");
+      priority = priority.min(superseded);
+    }
+    self.nodes.insert(node, priority);
+    self.queue.insert((priority, left, node));
+    true
+  }
+
+  /// Remove `node` from the queue, returning its priority if it was queued.
+  /// Only removes the node itself, not a queued ancestor covering it.
+  pub fn remove(&mut self, node: usize) -> Option<usize> {
+    let priority = self.nodes.remove(&node)?;
+    self.queue.remove(&(priority, left_span(node), node));
+    Some(priority)
+  }
+
+  /// Get the most urgent request, without removing it.
+  pub fn peek(&self) -> Option<Request> {
+    self
+      .queue
+      .iter()
+      .next()
+      .map(|&(priority, _, node)| Request { node, priority })
+  }
+
+  /// Remove and return the most urgent request.
+  pub fn pop(&mut self) -> Option<Request> {
+    let request = self.peek()?;
+    self.remove(request.node);
+    Some(request)
+  }
+}
+
+impl Extend<Request> for WantQueue {
+  fn extend<I: IntoIterator<Item = Request>>(&mut self, iter: I) {
+    for request in iter {
+      self.insert(request.node, request.priority);
+    }
+  }
+}
@@ -0,0 +1,102 @@
+//! ## Usage
+//! ```rust
+//! let mut nodes = vec![0; 7];
+//! let mut zipper = flat_tree::Zipper::new(&mut nodes, 4);
+//! zipper.set(1);
+//! while zipper.go_up().is_some() {
+//!   *zipper.get_mut() += 1;
+//! }
+//! assert_eq!(nodes, [0, 0, 0, 1, 1, 1, 0]);
+//! ```
+use super::*;
+
+use std::mem;
+
+/// A cursor with mutable access to the nodes of a flat-tree stored in a
+/// slice, indexed by flat-tree index.
+///
+/// Moves return `None`, without moving the cursor, if the target node isn't
+/// stored in the slice.
+#[derive(Debug)]
+pub struct Zipper<'a, T> {
+  iter: Iterator,
+  nodes: &'a mut [T],
+}
+
+impl<'a, T> Zipper<'a, T> {
+  /// Create a new zipper positioned at `index`.
+  ///
+  /// ## Panics
+  /// If `index` is out of bounds of `nodes`.
+  pub fn new(nodes: &'a mut [T], index: usize) -> Self {
+    assert!(
+      index < nodes.len(),
+      "Index {} is out of bounds of {} nodes",
+      index,
+      nodes.len()
+    );
+    Self {
+      iter: Iterator::new(index),
+      nodes,
+    }
+  }
+
+  /// Get the current index.
+  #[inline]
+  pub fn index(&self) -> usize {
+    self.iter.index()
+  }
+
+  /// Get the value of the current node.
+  #[inline]
+  pub fn get(&self) -> &T {
+    &self.nodes[self.iter.index()]
+  }
+
+  /// Get a mutable reference to the value of the current node.
+  #[inline]
+  pub fn get_mut(&mut self) -> &mut T {
+    &mut self.nodes[self.iter.index()]
+  }
+
+  /// Replace the value of the current node, returning the previous one.
+  pub fn set(&mut self, value: T) -> T {
+    mem::replace(self.get_mut(), value)
+  }
+
+  /// Move to the parent.
+  pub fn go_up(&mut self) -> Option<usize> {
+    if self.iter.depth() == Iterator::MAX_DEPTH
+      || self.iter.peek_parent() >= self.nodes.len()
+    {
+      return None;
+    }
+    self.iter.try_parent()
+  }
+
+  /// Move to the left child.
+  pub fn go_left(&mut self) -> Option<usize> {
+    self.iter.try_left_child()
+  }
+
+  /// Move to the right child.
+  pub fn go_right(&mut self) -> Option<usize> {
+    if self.iter.peek_right_child() >= self.nodes.len() {
+      return None;
+    }
+    self.iter.try_right_child()
+  }
+
+  /// Move to the sibling.
+  pub fn go_sibling(&mut self) -> Option<usize> {
+    if self.iter.peek_sibling() >= self.nodes.len() {
+      return None;
+    }
+    Some(self.iter.sibling())
+  }
+
+  /// Unwrap the underlying slice.
+  pub fn into_inner(self) -> &'a mut [T] {
+    self.nodes
+  }
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "arbitrary")]
+
+extern crate arbitrary;
+extern crate flat_tree;
+
+use arbitrary::{Arbitrary, Unstructured};
+use flat_tree::{BoundedCursor, Coords, GenericIterator, NodeIndex};
+
+/// Pseudo random bytes to generate values from.
+fn bytes(seed: u64) -> Vec<u8> {
+  let mut state = seed;
+  (0..4096)
+    .map(|_| {
+      state = state
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+      (state >> 56) as u8
+    })
+    .collect()
+}
+
+#[test]
+fn node_index_is_any_index() {
+  let data = [5, 0, 0, 0, 0, 0, 0, 0];
+  let mut u = Unstructured::new(&data);
+  let node = NodeIndex::arbitrary(&mut u).unwrap();
+  assert_eq!(node, NodeIndex(5));
+}
+
+#[test]
+fn coords_are_consistent() {
+  for seed in 0..20 {
+    let data = bytes(seed);
+    let mut u = Unstructured::new(&data);
+    while let Ok(coords) = Coords::arbitrary(&mut u) {
+      if u.is_empty() {
+        break;
+      }
+      assert_eq!(coords, Coords::new(coords.index));
+    }
+  }
+  let data = [0xff; 8];
+  let mut u = Unstructured::new(&data);
+  let coords = Coords::arbitrary(&mut u).unwrap();
+  assert_eq!(coords.depth, flat_tree::Iterator::MAX_DEPTH);
+}
+
+#[test]
+fn iterator_is_a_valid_position() {
+  for seed in 0..20 {
+    let data = bytes(seed);
+    let mut u = Unstructured::new(&data);
+    for _ in 0..100 {
+      let iter = GenericIterator::<u32>::arbitrary(&mut u).unwrap();
+      assert!(iter.depth() <= GenericIterator::<u32>::MAX_DEPTH);
+      assert_eq!(iter, GenericIterator::new(iter.index()));
+    }
+  }
+  let data = [0xff; 8];
+  let mut u = Unstructured::new(&data);
+  let iter = flat_tree::Iterator::arbitrary(&mut u).unwrap();
+  assert_eq!(iter.depth(), flat_tree::Iterator::MAX_DEPTH);
+}
+
+#[test]
+fn bounded_cursor_is_in_its_tree() {
+  for seed in 0..20 {
+    let data = bytes(seed);
+    let mut u = Unstructured::new(&data);
+    for _ in 0..100 {
+      let cursor = BoundedCursor::<u32>::arbitrary(&mut u).unwrap();
+      let leaves = cursor.tree_len();
+      assert!(leaves > 0);
+      // The first leaf the node spans is in the tree.
+      assert!(cursor.offset() << cursor.depth() < leaves);
+    }
+  }
+}
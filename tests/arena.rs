@@ -0,0 +1,56 @@
+extern crate flat_tree;
+
+use flat_tree::{Arena, NodeIndex};
+
+#[test]
+fn arena_reuses_slots() {
+  let mut arena = Arena::new();
+  for i in 0..4 {
+    assert_eq!(arena.insert(NodeIndex(i), i), None);
+  }
+  assert_eq!(arena.insert(NodeIndex(2), 20), Some(2));
+  assert_eq!(arena.slots(), 4);
+
+  assert_eq!(arena.remove(NodeIndex(1)), Some(1));
+  assert_eq!(arena.remove(NodeIndex(1)), None);
+  arena.insert(NodeIndex(100), 100);
+  assert_eq!(arena.slots(), 4);
+  assert_eq!(arena.get(NodeIndex(100)), Some(&100));
+
+  *arena.get_mut(NodeIndex(0)).unwrap() += 5;
+  let values: Vec<_> =
+    arena.iter().map(|(node, value)| (node.0, *value)).collect();
+  assert_eq!(values, [(0, 5), (2, 20), (3, 3), (100, 100)]);
+}
+
+#[test]
+fn arena_remove_subtree() {
+  let mut arena: Arena<_> = (0..31).map(|i| (NodeIndex(i), i)).collect();
+  let evicted: Vec<_> = arena
+    .remove_subtree(NodeIndex(11))
+    .into_iter()
+    .map(|(node, _)| node.0)
+    .collect();
+  assert_eq!(evicted, (8..15).collect::<Vec<_>>());
+  assert!(arena.contains(NodeIndex(7)));
+  assert!(arena.contains(NodeIndex(15)));
+  assert!(!arena.contains(NodeIndex(8)));
+  assert_eq!(arena.len(), 24);
+
+  assert!(arena.remove_subtree(NodeIndex(11)).is_empty());
+  let slots = arena.slots();
+  for i in 8..15 {
+    arena.insert(NodeIndex(i), i);
+  }
+  assert_eq!(arena.slots(), slots);
+}
+
+#[test]
+fn arena_clear() {
+  let mut arena: Arena<_> = (0..4).map(|i| (NodeIndex(i), i)).collect();
+  arena.clear();
+  assert!(arena.is_empty());
+  assert_eq!(arena.remove_range(NodeIndex(3)..NodeIndex(1)), []);
+  arena.insert(NodeIndex(9), 9);
+  assert_eq!(arena.slots(), 4);
+}
@@ -0,0 +1,56 @@
+extern crate flat_tree;
+
+use flat_tree::AtomicTreeIndex;
+use std::sync::Arc;
+use std::thread;
+
+#[test]
+fn set_and_get() {
+  let tree = AtomicTreeIndex::new(5);
+  assert_eq!(tree.tree_len(), 5);
+  assert!(!tree.get(0));
+  assert!(tree.set(0));
+  assert!(!tree.set(0));
+  tree.set(2);
+  tree.set(6);
+  tree.set(4);
+  assert!(tree.get(1));
+  assert!(tree.get(5));
+  assert!(tree.get(3));
+  assert!(!tree.get(7));
+  tree.set(8);
+  assert!(!tree.get(7));
+  assert!(!tree.get(1000));
+  assert_eq!(tree.to_tree_index().roots(), [3, 8]);
+}
+
+#[test]
+#[should_panic]
+fn set_outside_of_tree() {
+  let tree = AtomicTreeIndex::new(3);
+  tree.set(6);
+}
+
+#[test]
+fn concurrent_set() {
+  let leaves = 1 << 12;
+  for _ in 0..8 {
+    let tree = Arc::new(AtomicTreeIndex::new(leaves));
+    let handles: Vec<_> = (0..4)
+      .map(|thread| {
+        let tree = tree.clone();
+        thread::spawn(move || {
+          for leaf in (0..leaves).filter(|leaf| leaf % 4 == thread) {
+            tree.set(2 * leaf);
+          }
+        })
+      })
+      .collect();
+    for handle in handles {
+      handle.join().unwrap();
+    }
+    let root = leaves - 1;
+    assert!(tree.get(root));
+    assert_eq!(tree.to_tree_index().blocks(), leaves);
+  }
+}
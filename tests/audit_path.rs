@@ -0,0 +1,39 @@
+extern crate flat_tree;
+
+#[test]
+fn audit_path_reaches_root() {
+  for leaves in 1..130 {
+    let mut roots = Vec::new();
+    flat_tree::full_roots(2 * leaves, &mut roots);
+    for node in flat_tree::inorder(leaves) {
+      let mut i = node;
+      for sibling in flat_tree::audit_path(node, leaves) {
+        assert!(flat_tree::right_span(sibling) < 2 * leaves);
+        assert_eq!(flat_tree::sibling(i), sibling);
+        i = flat_tree::parent(i);
+      }
+      assert!(roots.contains(&i));
+    }
+  }
+}
+
+#[test]
+fn audit_path_len() {
+  for leaves in 1..130 {
+    for node in flat_tree::inorder(leaves) {
+      let path = flat_tree::audit_path(node, leaves);
+      assert_eq!(path.len(), path.count());
+      assert_eq!(
+        flat_tree::audit_path(node, leaves).len(),
+        flat_tree::ancestors(node).within(leaves).len()
+      );
+    }
+  }
+}
+
+#[test]
+fn audit_path_outside_tree() {
+  assert_eq!(flat_tree::audit_path(0, 0).count(), 0);
+  assert_eq!(flat_tree::audit_path(3, 3).count(), 0);
+  assert_eq!(flat_tree::audit_path(6, 3).count(), 0);
+}
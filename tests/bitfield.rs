@@ -0,0 +1,56 @@
+extern crate flat_tree;
+
+use flat_tree::{Bitfield, TreeIndex};
+
+#[test]
+fn vec_bitfield() {
+  let mut bits: Vec<u8> = Vec::new();
+  assert!(Bitfield::is_empty(&bits));
+  assert!(!Bitfield::get(&bits, 3));
+  assert!(bits.grow(9));
+  assert_eq!(Bitfield::len(&bits), 16);
+  assert!(Bitfield::set(&mut bits, 9, true));
+  assert!(!Bitfield::set(&mut bits, 9, true));
+  assert!(Bitfield::get(&bits, 9));
+  assert_eq!(bits, [0, 0b0100_0000]);
+  assert!(Bitfield::set(&mut bits, 9, false));
+  assert!(!Bitfield::get(&bits, 9));
+}
+
+#[test]
+fn slice_bitfield() {
+  let mut buf = [0u8; 2];
+  {
+    let mut bits = &mut buf[..];
+    assert_eq!(Bitfield::len(&bits), 16);
+    assert!(bits.grow(16));
+    assert!(!bits.grow(17));
+    assert!(bits.set(0, true));
+    assert!(bits.set(15, true));
+  }
+  assert_eq!(buf, [0b1000_0000, 0b0000_0001]);
+}
+
+#[test]
+fn tree_index_over_slice() {
+  let mut buf = [0u8; 2];
+  {
+    let mut tree = TreeIndex::from_bitfield(&mut buf[..]);
+    tree.set(0);
+    tree.set(2);
+    tree.set(6);
+    tree.set(4);
+    assert!(tree.get(3));
+    assert_eq!(tree.blocks(), 4);
+    assert_eq!(tree.len(), 16);
+  }
+  assert_eq!(buf, [0b1111_1110, 0]);
+}
+
+#[test]
+#[should_panic]
+fn tree_index_over_full_slice() {
+  let mut buf = [0u8; 1];
+  let mut tree = TreeIndex::from_bitfield(&mut buf[..]);
+  tree.set(8);
+}
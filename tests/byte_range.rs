@@ -0,0 +1,77 @@
+extern crate flat_tree;
+
+use flat_tree::{byte_range_of, nodes_covering_bytes, Layout};
+
+#[test]
+fn byte_range_of_leaf_is_its_block() {
+  let layout = Layout::new(40, 1000);
+  for leaf in 0..100 {
+    assert_eq!(byte_range_of(2 * leaf, 1000), layout.data_range(2 * leaf));
+  }
+}
+
+#[test]
+fn byte_range_of_parent_joins_children() {
+  for i in flat_tree::inorder(64) {
+    if let Some((left, right)) = flat_tree::children(i) {
+      let range = byte_range_of(i, 7);
+      assert_eq!(range.start, byte_range_of(left, 7).start);
+      assert_eq!(range.end, byte_range_of(right, 7).end);
+    }
+  }
+}
+
+#[test]
+fn nodes_covering_bytes_cover_every_byte() {
+  let block_size = 10;
+  for leaves in 1..20 {
+    for start in 0..220 {
+      for end in start..220 {
+        let nodes = nodes_covering_bytes(start..end, block_size, leaves);
+        let data_len = leaves as u64 * block_size;
+        if start == end || start >= data_len {
+          assert!(nodes.is_empty());
+          continue;
+        }
+        let mut next = start - start % block_size;
+        for i in &nodes {
+          assert!(flat_tree::spans(i).1 < 2 * leaves);
+          assert_eq!(byte_range_of(i, block_size).start, next);
+          next = byte_range_of(i, block_size).end;
+        }
+        let end = end.min(data_len);
+        assert!(next >= end && next < end + block_size);
+      }
+    }
+  }
+}
+
+#[test]
+fn end_of_the_range() {
+  // The last leaf is block `2^63 - 1`.
+  let end = 1 << 63;
+  assert_eq!(byte_range_of(usize::MAX - 1, 1), end - 1..end);
+  assert_eq!(byte_range_of(usize::MAX - 2, 1), end - 2..end);
+  let nodes = nodes_covering_bytes(0..u64::MAX, 1, 4);
+  assert_eq!(nodes.as_slice(), [3]);
+  let nodes = nodes_covering_bytes(u64::MAX - 1..u64::MAX, 1, usize::MAX / 2);
+  assert!(nodes.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "past the end of a u64")]
+fn byte_range_of_overflows() {
+  byte_range_of(usize::MAX - 1, 2);
+}
+
+#[test]
+#[should_panic(expected = "larger than the maximum cursor depth")]
+fn byte_range_of_too_deep() {
+  byte_range_of(usize::MAX, 1);
+}
+
+#[test]
+#[should_panic]
+fn nodes_covering_bytes_zero_block_size() {
+  nodes_covering_bytes(0..10, 0, 4);
+}
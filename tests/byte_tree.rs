@@ -0,0 +1,55 @@
+extern crate flat_tree;
+
+use flat_tree::ByteTree;
+
+fn lengths() -> Vec<u64> {
+  (0..37).map(|leaf| (leaf * 7 % 5) as u64).collect()
+}
+
+#[test]
+fn byte_tree_matches_linear_scan() {
+  let lengths = lengths();
+  let tree: ByteTree = lengths.iter().cloned().collect();
+  assert_eq!(tree.len(), lengths.len());
+  assert_eq!(tree.byte_len(), lengths.iter().sum::<u64>());
+
+  let mut start = 0;
+  for (leaf, &len) in lengths.iter().enumerate() {
+    assert_eq!(tree.leaf_len(leaf), Some(len));
+    assert_eq!(tree.byte_range(leaf), Some(start..start + len));
+    for offset in 0..len {
+      assert_eq!(tree.seek(start + offset), Some((leaf, offset)));
+    }
+    start += len;
+  }
+  assert_eq!(tree.seek(start), None);
+  assert_eq!(tree.byte_range(lengths.len()), None);
+}
+
+#[test]
+fn byte_tree_set_len() {
+  let mut lengths = lengths();
+  let mut tree: ByteTree = lengths.iter().cloned().collect();
+  for leaf in (0..lengths.len()).step_by(3) {
+    lengths[leaf] = 10 - lengths[leaf];
+    tree.set_len(leaf, lengths[leaf]);
+  }
+  assert_eq!(tree, lengths.iter().cloned().collect());
+  assert_eq!(tree.byte_len(), lengths.iter().sum::<u64>());
+}
+
+#[test]
+fn byte_tree_empty() {
+  let tree = ByteTree::new();
+  assert!(tree.is_empty());
+  assert_eq!(tree.byte_len(), 0);
+  assert_eq!(tree.seek(0), None);
+  assert_eq!(tree.byte_range(0), None);
+}
+
+#[test]
+#[should_panic]
+fn byte_tree_set_len_out_of_range() {
+  let mut tree: ByteTree = vec![1, 2].into_iter().collect();
+  tree.set_len(2, 1);
+}
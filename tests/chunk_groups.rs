@@ -0,0 +1,75 @@
+extern crate flat_tree;
+
+use flat_tree::ChunkGroups;
+
+#[test]
+fn leaf_of_chunk() {
+  for group_log in 0..5 {
+    let groups = ChunkGroups::new(group_log);
+    for chunk in 0..200 {
+      let leaf = groups.leaf(chunk);
+      assert!(groups.chunks(leaf).contains(&chunk));
+      assert_eq!(groups.chunks(leaf).start % groups.group_len(), 0);
+    }
+  }
+}
+
+#[test]
+fn chunks_of_node_are_its_groups() {
+  for group_log in 0..5 {
+    let groups = ChunkGroups::new(group_log);
+    for i in flat_tree::inorder(64) {
+      let chunks = groups.chunks(i);
+      let (left, right) = flat_tree::spans(i);
+      assert_eq!(chunks.start, groups.chunks(left).start);
+      assert_eq!(chunks.end, groups.chunks(right).end);
+      assert_eq!(
+        chunks.end - chunks.start,
+        groups.group_len() << flat_tree::depth(i)
+      );
+    }
+  }
+}
+
+#[test]
+fn roots_cover_every_chunk() {
+  for group_log in 0..4 {
+    let groups = ChunkGroups::new(group_log);
+    for chunks in 0..100 {
+      let mut next = 0;
+      for root in groups.roots(chunks) {
+        assert_eq!(groups.chunks(root).start, next);
+        next = groups.chunks(root).end;
+      }
+      assert!(next >= chunks && next < chunks + groups.group_len());
+    }
+  }
+}
+
+#[test]
+fn covering_is_group_aligned() {
+  for group_log in 0..4 {
+    let groups = ChunkGroups::new(group_log);
+    for start in 0..40 {
+      for end in start..40 {
+        let nodes = groups.covering(start..end);
+        if start == end {
+          assert!(nodes.is_empty());
+          continue;
+        }
+        let mut next = start - start % groups.group_len();
+        for i in &nodes {
+          assert_eq!(groups.chunks(i).start, next);
+          next = groups.chunks(i).end;
+        }
+        assert!(next >= end && next < end + groups.group_len());
+      }
+    }
+  }
+}
+
+#[test]
+#[should_panic]
+fn group_too_large() {
+  ChunkGroups::new(64);
+}
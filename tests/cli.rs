@@ -0,0 +1,104 @@
+#![cfg(feature = "cli")]
+
+use std::process::Command;
+
+fn run(args: &[&str]) -> (Option<i32>, String) {
+  let output = Command::new(env!("CARGO_BIN_EXE_flat-tree"))
+    .args(args)
+    .output()
+    .unwrap();
+  let stdout = String::from_utf8(output.stdout).unwrap();
+  (output.status.code(), stdout.trim_end().to_string())
+}
+
+#[test]
+fn prints_text() {
+  assert_eq!(run(&["parent", "42"]), (Some(0), "41".to_string()));
+  assert_eq!(run(&["index", "1", "2"]), (Some(0), "9".to_string()));
+  assert_eq!(run(&["spans", "23"]), (Some(0), "16 30".to_string()));
+  assert_eq!(run(&["children", "0"]), (Some(0), "none".to_string()));
+  assert_eq!(run(&["full-roots", "16"]), (Some(0), "7".to_string()));
+  assert_eq!(
+    run(&["roots", "--leaves", "7"]),
+    (Some(0), "3 9 12".to_string())
+  );
+  assert_eq!(
+    run(&["path", "0", "parent", "parent", "left"]),
+    (Some(0), "0 1 3 1".to_string())
+  );
+}
+
+#[test]
+fn prints_json() {
+  assert_eq!(
+    run(&["--json", "parent", "42"]),
+    (Some(0), "41".to_string())
+  );
+  assert_eq!(
+    run(&["spans", "23", "--json"]),
+    (Some(0), "[16,30]".to_string())
+  );
+  assert_eq!(
+    run(&["--json", "children", "0"]),
+    (Some(0), "null".to_string())
+  );
+  assert_eq!(
+    run(&["--json", "roots", "--leaves", "0"]),
+    (Some(0), "[]".to_string())
+  );
+  assert_eq!(
+    run(&["--json", "path", "3", "right_child", "sibling"]),
+    (Some(0), "[3,5,1]".to_string())
+  );
+}
+
+#[test]
+fn reports_errors() {
+  assert_eq!(run(&[]).0, Some(2));
+  assert_eq!(run(&["grandparent", "0"]).0, Some(2));
+  assert_eq!(run(&["parent", "x"]).0, Some(2));
+  assert_eq!(run(&["parent", "1", "2"]).0, Some(2));
+  assert_eq!(run(&["path", "0", "up"]).0, Some(2));
+  assert_eq!(run(&["path", "0", "left"]).0, Some(1));
+  assert_eq!(run(&["full-roots", "1"]).0, Some(1));
+}
+
+#[test]
+fn rejects_indices_out_of_range() {
+  let max = usize::MAX.to_string();
+  let deepest = ((1usize << 63) - 1).to_string();
+  for args in &[
+    &["parent", &max][..],
+    &["sibling", &max],
+    &["uncle", &max],
+    &["offset", &max],
+    &["children", &max],
+    &["spans", &max],
+    &["count", &max],
+    &["parent", &deepest],
+    &["path", &deepest, "parent"],
+    &["path", &max, "left_span"],
+    &["index", "64", "0"],
+    &["index", "63", "0"],
+    &["index", "0", &max],
+  ] {
+    let output = Command::new(env!("CARGO_BIN_EXE_flat-tree"))
+      .args(*args)
+      .output()
+      .unwrap();
+    assert_eq!(output.status.code(), Some(1), "{:?}", args);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("error: "), "{:?}: {}", args, stderr);
+    assert!(!stderr.contains("panicked"), "{:?}: {}", args, stderr);
+  }
+
+  assert_eq!(
+    run(&["index", "62", "1"]),
+    (Some(0), ((1usize << 63) | ((1 << 62) - 1)).to_string())
+  );
+  assert_eq!(
+    run(&["spans", &(usize::MAX - 2).to_string()]),
+    (Some(0), format!("{} {}", usize::MAX - 3, usize::MAX - 1))
+  );
+  assert_eq!(run(&["depth", &max]), (Some(0), "64".to_string()));
+}
@@ -0,0 +1,68 @@
+extern crate flat_tree;
+
+use flat_tree::{DecodeError, TreeIndex};
+
+#[test]
+fn round_trip() {
+  let tree = TreeIndex::new();
+  let bytes = tree.to_bytes();
+  assert_eq!(bytes, b"FTIX\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00");
+  assert_eq!(TreeIndex::from_bytes(&bytes), Ok(tree));
+
+  let mut tree = TreeIndex::new();
+  for leaf in &[0, 2, 4, 6, 8, 20] {
+    tree.set(*leaf);
+  }
+  let bytes = tree.to_bytes();
+  assert_eq!(&bytes[6..14], &[3, 0, 0, 0, 0, 0, 0, 0]);
+  assert_eq!(&bytes[14..], &[0b1111_1110, 0b1000_0000, 0b0000_1000]);
+  assert_eq!(TreeIndex::from_bytes(&bytes), Ok(tree));
+}
+
+#[test]
+fn trailing_bytes_are_ignored() {
+  let mut tree = TreeIndex::new();
+  tree.set(0);
+  let mut bytes = tree.to_bytes();
+  bytes.extend_from_slice(&[1, 2, 3]);
+  assert_eq!(TreeIndex::from_bytes(&bytes), Ok(tree));
+}
+
+#[test]
+fn from_slice_bitfield() {
+  let mut buf = [0u8; 16];
+  let mut tree = TreeIndex::from_bitfield(&mut buf[..]);
+  tree.set(2);
+  let decoded = TreeIndex::from_bytes(&tree.to_bytes()).unwrap();
+  assert!(decoded.get(2));
+  assert_eq!(decoded.len(), 8);
+}
+
+#[test]
+fn errors() {
+  assert_eq!(TreeIndex::from_bytes(b""), Err(DecodeError::InvalidMagic));
+  assert_eq!(
+    TreeIndex::from_bytes(b"FTIY\x01\x00"),
+    Err(DecodeError::InvalidMagic)
+  );
+  assert_eq!(
+    TreeIndex::from_bytes(b"FTIX\x01\x00"),
+    Err(DecodeError::Truncated)
+  );
+  assert_eq!(
+    TreeIndex::from_bytes(b"FTIX\x02\x00\x00\x00\x00\x00\x00\x00\x00\x00"),
+    Err(DecodeError::UnsupportedVersion(2))
+  );
+  assert_eq!(
+    TreeIndex::from_bytes(b"FTIX\x01\x07\x00\x00\x00\x00\x00\x00\x00\x00"),
+    Err(DecodeError::UnknownEncoding(7))
+  );
+  assert_eq!(
+    TreeIndex::from_bytes(b"FTIX\x01\x00\x02\x00\x00\x00\x00\x00\x00\x00\x01"),
+    Err(DecodeError::Truncated)
+  );
+  assert_eq!(
+    DecodeError::UnknownEncoding(7).to_string(),
+    "unknown bitfield encoding 7"
+  );
+}
@@ -0,0 +1,98 @@
+extern crate flat_tree;
+
+use flat_tree::ct;
+
+/// Returns the flat-tree node for leaves `start..end` of a log whose last
+/// leaf is before `end`.
+fn node(start: usize, end: usize) -> usize {
+  let depth = (end - start).next_power_of_two().trailing_zeros() as usize;
+  flat_tree::index(depth, start >> depth)
+}
+
+/// The audit path of RFC 6962, section 2.1.1, as leaf ranges.
+fn path(m: usize, start: usize, end: usize, proof: &mut Vec<usize>) {
+  if end - start == 1 {
+    return;
+  }
+  let k = (end - start).next_power_of_two() / 2;
+  if m < k {
+    path(m, start, start + k, proof);
+    proof.push(node(start + k, end));
+  } else {
+    path(m - k, start + k, end, proof);
+    proof.push(node(start, start + k));
+  }
+}
+
+/// The consistency proof of RFC 6962, section 2.1.2, as leaf ranges.
+fn subproof(
+  m: usize,
+  start: usize,
+  end: usize,
+  complete: bool,
+  proof: &mut Vec<usize>,
+) {
+  if m == end - start {
+    if !complete {
+      proof.push(node(start, end));
+    }
+    return;
+  }
+  let k = (end - start).next_power_of_two() / 2;
+  if m <= k {
+    subproof(m, start, start + k, complete, proof);
+    proof.push(node(start + k, end));
+  } else {
+    subproof(m - k, start + k, end, false, proof);
+    proof.push(node(start, start + k));
+  }
+}
+
+#[test]
+fn inclusion_proof_rfc_example() {
+  assert_eq!(ct::inclusion_proof(0, 7), Some(vec![2, 5, 11]));
+  assert_eq!(ct::inclusion_proof(3, 7), Some(vec![4, 1, 11]));
+  assert_eq!(ct::inclusion_proof(4, 7), Some(vec![10, 12, 3]));
+  assert_eq!(ct::inclusion_proof(6, 7), Some(vec![9, 3]));
+}
+
+#[test]
+fn inclusion_proof_matches_rfc() {
+  for size in 1..130 {
+    for leaf in 0..size {
+      let mut expected = Vec::new();
+      path(leaf, 0, size, &mut expected);
+      assert_eq!(ct::inclusion_proof(leaf, size), Some(expected));
+    }
+    assert_eq!(ct::inclusion_proof(size, size), None);
+  }
+}
+
+#[test]
+fn inclusion_proof_of_complete_tree_is_audit_path() {
+  for leaf in 0..16 {
+    let proof = ct::inclusion_proof(leaf, 16).unwrap();
+    let expected: Vec<_> = flat_tree::audit_path(2 * leaf, 16).collect();
+    assert_eq!(proof, expected);
+  }
+}
+
+#[test]
+fn consistency_proof_rfc_example() {
+  assert_eq!(ct::consistency_proof(3, 7), Some(vec![4, 6, 1, 11]));
+  assert_eq!(ct::consistency_proof(4, 7), Some(vec![11]));
+  assert_eq!(ct::consistency_proof(6, 7), Some(vec![9, 12, 3]));
+}
+
+#[test]
+fn consistency_proof_matches_rfc() {
+  for new_size in 1..70 {
+    for old_size in 1..=new_size {
+      let mut expected = Vec::new();
+      subproof(old_size, 0, new_size, true, &mut expected);
+      assert_eq!(ct::consistency_proof(old_size, new_size), Some(expected));
+    }
+    assert_eq!(ct::consistency_proof(0, new_size), None);
+    assert_eq!(ct::consistency_proof(new_size + 1, new_size), None);
+  }
+}
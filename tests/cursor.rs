@@ -0,0 +1,58 @@
+extern crate flat_tree;
+
+use flat_tree::{Cursor, Move, Record};
+
+#[test]
+fn undo_redo() {
+  let mut cursor = Cursor::new(0);
+  assert_eq!(cursor.undo(), None);
+  assert_eq!(cursor.redo(), None);
+  assert_eq!(cursor.parent(), 1);
+  assert_eq!(cursor.parent(), 3);
+  assert_eq!(cursor.right_child(), 5);
+  assert_eq!(cursor.undo(), Some(3));
+  assert_eq!(cursor.undo(), Some(1));
+  assert_eq!(cursor.get_ref().offset(), 0);
+  assert_eq!(cursor.next(), Some(5));
+  assert_eq!(cursor.redo(), None);
+  assert_eq!(cursor.left_child(), 4);
+  assert_eq!(cursor.undo(), Some(5));
+  assert_eq!(cursor.redo(), Some(4));
+  assert_eq!(cursor.get_ref().offset(), 2);
+  assert_eq!(cursor.path(), [0, 1, 5, 4]);
+}
+
+#[test]
+fn history() {
+  let mut cursor = Cursor::new(2);
+  cursor.prev();
+  cursor.prev();
+  cursor.seek(23);
+  cursor.left_span();
+  assert_eq!(
+    cursor.history(),
+    [
+      Record {
+        op: Move::Prev,
+        from: 2,
+        to: 0,
+      },
+      Record {
+        op: Move::Prev,
+        from: 0,
+        to: 0,
+      },
+      Record {
+        op: Move::Seek,
+        from: 0,
+        to: 23,
+      },
+      Record {
+        op: Move::LeftSpan,
+        from: 23,
+        to: 16,
+      },
+    ]
+  );
+  assert_eq!(cursor.history()[3].to_string(), "23 -> 16 (left_span)");
+}
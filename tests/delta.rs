@@ -0,0 +1,188 @@
+extern crate flat_tree;
+
+use flat_tree::{delta, DecodeError, NodeSet};
+
+/// Pseudo random numbers for the fuzz tests.
+fn numbers(seed: u64) -> impl Iterator<Item = u64> {
+  let mut state = seed;
+  std::iter::repeat_with(move || {
+    state = state
+      .wrapping_mul(6364136223846793005)
+      .wrapping_add(1442695040888963407);
+    state >> 32
+  })
+}
+
+#[test]
+fn encode() {
+  assert!(delta::encode(&[]).is_empty());
+  assert_eq!(delta::encode(&[0]), [0]);
+  assert_eq!(delta::encode(&[5]), [0b1010]);
+  assert_eq!(delta::encode(&[0, 1]), [0, 0b10]);
+  assert_eq!(delta::encode(&[2, 4, 6]), [0b101, 0]);
+  assert_eq!(delta::encode(&[3, 5, 7, 9, 20]), [0b110, 0b101, 0, 0b10110]);
+  assert_eq!(
+    delta::encode(&[usize::MAX]),
+    [0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x03]
+  );
+}
+
+#[test]
+#[should_panic]
+fn encode_unsorted() {
+  delta::encode(&[2, 1]);
+}
+
+#[test]
+#[should_panic]
+fn encode_duplicates() {
+  delta::encode(&[2, 2]);
+}
+
+#[test]
+fn decode() {
+  assert_eq!(delta::decode(&[]), Ok(vec![]));
+  assert_eq!(delta::decode(&[0, 0b10]), Ok(vec![0, 1]));
+  assert_eq!(delta::decode(&[0b11, 0]), Ok(vec![1, 2, 3]));
+  assert_eq!(delta::decode(&[0b11, 2]), Ok(vec![1, 2, 3, 4, 5]));
+  assert_eq!(delta::decode(&[0x80]), Err(DecodeError::Truncated));
+  assert_eq!(delta::decode(&[0b11]), Err(DecodeError::Truncated));
+  assert_eq!(delta::decode(&[0b10, 0]), Err(DecodeError::NotSorted));
+  assert_eq!(delta::decode(&[0b1, 0]), Err(DecodeError::NotSorted));
+  assert_eq!(delta::decode(&[0xff; 20]), Err(DecodeError::Overflow));
+
+  let mut max = delta::encode(&[usize::MAX]);
+  max.push(0b10);
+  assert_eq!(delta::decode(&max), Err(DecodeError::Overflow));
+}
+
+#[test]
+fn decode_rejects_overflowing_runs() {
+  // The run length overflows when adding the shortest run.
+  let mut run = vec![0b11];
+  run.extend_from_slice(&[0xff; 18]);
+  run.push(0x03);
+  assert_eq!(delta::decode(&run), Err(DecodeError::Overflow));
+  assert_eq!(
+    delta::decoder(&run).next(),
+    Some(Err(DecodeError::Overflow))
+  );
+
+  // The last index of the run doesn't fit in a usize.
+  let mut run = vec![0b11];
+  run.extend_from_slice(&[0xff; 9]);
+  run.push(0x01);
+  assert_eq!(delta::decode(&run), Err(DecodeError::Overflow));
+  assert_eq!(
+    delta::decoder(&run).next(),
+    Some(Err(DecodeError::Overflow))
+  );
+  let mut run = delta::encode(&[usize::MAX - 4]);
+  run.extend_from_slice(&[0b11, 0x02]);
+  assert_eq!(
+    delta::decoder(&run).nth(1),
+    Some(Err(DecodeError::Overflow))
+  );
+  run.pop();
+  run.push(0x01);
+  assert_eq!(delta::decode(&run).unwrap().last(), Some(&usize::MAX));
+
+  // A run that fits but is too long to decode into memory.
+  let mut run = vec![0b11];
+  run.extend_from_slice(&[0xff; 8]);
+  run.push(0x7f);
+  assert_eq!(delta::decode(&run), Err(DecodeError::Overflow));
+  assert_eq!(delta::decode_max(&[0b11, 0], 2), Err(DecodeError::Overflow));
+  assert_eq!(
+    delta::decode_max(&[0, 0b10, 0b100], 2),
+    Err(DecodeError::Overflow)
+  );
+  assert_eq!(delta::decode_max(&[0], 0), Err(DecodeError::Overflow));
+  assert_eq!(delta::decode_max(&[0, 0b10], 2), Ok(vec![0, 1]));
+}
+
+#[test]
+fn decode_rejects_bits_past_the_end() {
+  // The 19th byte of a varint only has room for two bits. The varints are
+  // the length of a run of zero differences.
+  let mut varint = vec![0b1];
+  varint.extend_from_slice(&[0x80; 18]);
+  varint.push(0x03);
+  assert_eq!(delta::decoder(&varint).next(), Some(Ok(0)));
+  varint.pop();
+  varint.push(0x04);
+  assert_eq!(
+    delta::decoder(&varint).next(),
+    Some(Err(DecodeError::Overflow))
+  );
+}
+
+#[test]
+fn decoder_stops_after_an_error() {
+  let mut nodes = delta::decoder(&[0b10, 0, 0b10]);
+  assert_eq!(nodes.next(), Some(Ok(1)));
+  assert_eq!(nodes.next(), Some(Err(DecodeError::NotSorted)));
+  assert_eq!(nodes.next(), None);
+}
+
+#[test]
+fn runs_are_compact() {
+  let leaves: Vec<_> = (0..100_000).map(|leaf| leaf * 2).collect();
+  assert_eq!(delta::encode(&leaves).len(), 5);
+
+  let set: NodeSet = (0..1000).map(|i| i * 7 + i % 3).collect();
+  let encoded = delta::encode(set.as_slice());
+  assert!(encoded.len() <= set.len());
+  assert_eq!(delta::decode(&encoded).unwrap(), set.as_slice());
+}
+
+#[test]
+fn fuzz_round_trip() {
+  for seed in 0..200 {
+    let mut numbers = numbers(seed);
+    let len = numbers.next().unwrap() % 64;
+    let mut nodes: NodeSet = NodeSet::new();
+    let mut node = 0usize;
+    for _ in 0..len {
+      // Mostly small steps, with runs of equal steps and some large jumps.
+      let step = match numbers.next().unwrap() % 4 {
+        0 => 2,
+        1 => 1 << (numbers.next().unwrap() % 60),
+        _ => numbers.next().unwrap() as usize % 16 + 1,
+      };
+      node = match node.checked_add(step) {
+        Some(node) => node,
+        None => break,
+      };
+      nodes.insert(node);
+    }
+    let encoded = delta::encode(nodes.as_slice());
+    assert_eq!(delta::decode(&encoded).unwrap(), nodes.as_slice());
+  }
+}
+
+#[test]
+fn fuzz_decode() {
+  for seed in 0..2000 {
+    let mut numbers = numbers(seed);
+    let len = numbers.next().unwrap() % 32;
+    let bytes: Vec<u8> = numbers
+      .by_ref()
+      .take(len as usize)
+      .map(|n| n as u8)
+      .collect();
+
+    // Decoding arbitrary bytes never panics, and whatever decodes is sorted
+    // and encodes back to a list decoding the same way.
+    let nodes: Vec<_> = delta::decoder(&bytes)
+      .take(10_000)
+      .take_while(Result::is_ok)
+      .map(Result::unwrap)
+      .collect();
+    assert!(nodes.windows(2).all(|w| w[0] < w[1]));
+    if let Ok(decoded) = delta::decode_max(&bytes, 10_000) {
+      assert_eq!(decoded, nodes);
+    }
+    assert_eq!(delta::decode(&delta::encode(&nodes)), Ok(nodes));
+  }
+}
@@ -0,0 +1,125 @@
+extern crate flat_tree;
+
+use flat_tree::{DenseTree, Order};
+
+#[test]
+fn dense_tree_get_set() {
+  let mut tree = DenseTree::new();
+  assert!(tree.is_empty());
+  assert_eq!(tree.get(3), None);
+  assert_eq!(tree.set(3, 'd'), None);
+  assert_eq!(tree.len(), 4);
+  assert_eq!(tree.set(3, 'D'), Some('d'));
+  assert!(tree.contains(3));
+  assert!(!tree.contains(2));
+
+  *tree.get_mut(3).unwrap() = 'x';
+  assert_eq!(tree.remove(3), Some('x'));
+  assert_eq!(tree.remove(3), None);
+  assert_eq!(tree.len(), 4);
+}
+
+#[test]
+fn dense_tree_iterators() {
+  let tree: DenseTree<usize> =
+    flat_tree::inorder(4).map(|i| (i, i * 10)).collect();
+  let all: Vec<_> = tree.iter().map(|(i, _)| i).collect();
+  assert_eq!(all, [0, 1, 2, 3, 4, 5, 6]);
+
+  let post: Vec<_> =
+    tree.subtree(3, Order::PostOrder).map(|(i, _)| i).collect();
+  assert_eq!(post, [0, 2, 1, 4, 6, 5, 3]);
+  let sub: Vec<_> = tree.subtree(5, Order::InOrder).collect();
+  assert_eq!(sub, [(4, &40), (5, &50), (6, &60)]);
+
+  let up: Vec<_> = tree.ancestors(6).map(|(i, _)| i).collect();
+  assert_eq!(up, [5, 3]);
+  assert_eq!(tree.ancestors(8).count(), 0);
+}
+
+#[test]
+fn dense_tree_ancestors_past_end() {
+  let mut tree = DenseTree::new();
+  tree.set(1, ());
+  assert_eq!(tree.ancestors(2).map(|(i, _)| i).collect::<Vec<_>>(), [1]);
+}
+
+#[test]
+fn dense_tree_take() {
+  let mut tree = DenseTree::new();
+  tree.set(4, "e");
+  tree.set(0, "a");
+  assert_eq!(tree.take(), [(0, "a"), (4, "e")]);
+  assert!(tree.is_empty());
+
+  tree.set(2, "c");
+  tree.clear();
+  assert_eq!(tree.len(), 3);
+  assert_eq!(tree.iter().count(), 0);
+}
+
+#[test]
+fn dense_tree_snapshots_are_frozen() {
+  let mut tree = DenseTree::new();
+  for i in 0..100 {
+    tree.set(i, i);
+  }
+  let before = tree.snapshot();
+  for i in 50..200 {
+    tree.set(i, i + 1000);
+  }
+  tree.remove(0);
+  *tree.get_mut(1).unwrap() = 7;
+  let after = tree.snapshot();
+  tree.clear();
+
+  assert_eq!(before.len(), 100);
+  assert!(before.iter().all(|(i, value)| *value == i));
+  assert_eq!(after.len(), 200);
+  assert_eq!(after.get(0), None);
+  assert_eq!(after.get(1), Some(&7));
+  assert_eq!(after.get(199), Some(&1199));
+  assert_eq!(tree.len(), 200);
+  assert_eq!(tree.iter().count(), 0);
+}
+
+#[test]
+fn dense_tree_take_shared() {
+  let mut tree: DenseTree<_> = vec![(0, 'a'), (70, 'b')].into_iter().collect();
+  let snapshot = tree.snapshot();
+  assert_eq!(tree.take(), [(0, 'a'), (70, 'b')]);
+  assert_eq!(snapshot.iter().count(), 2);
+  assert_eq!(snapshot, snapshot.snapshot());
+}
+
+#[test]
+fn dense_tree_push_leaf_matches_tree_builder() {
+  let concat = |_: usize, left: &String, right: &String| left.clone() + right;
+  let mut builder = flat_tree::TreeBuilder::new(concat);
+  let mut tree = DenseTree::new();
+  for leaf in 0..20 {
+    let value = leaf.to_string();
+    let expected = builder.push(value.clone());
+    let mut filled = vec![(2 * leaf, value.clone())];
+    for parent in tree.push_leaf(value) {
+      let (left, right) = flat_tree::children(parent).unwrap();
+      let combined =
+        concat(parent, tree.get(left).unwrap(), tree.get(right).unwrap());
+      tree.set(parent, combined.clone());
+      filled.push((parent, combined));
+    }
+    assert_eq!(filled, expected);
+  }
+  assert_eq!(tree.leaves(), 20);
+  assert_eq!(tree.iter().count(), flat_tree::inorder(20).count());
+}
+
+#[test]
+fn dense_tree_push_leaf_clears_stale_parents() {
+  let mut tree = DenseTree::new();
+  tree.set(1, "stale");
+  tree.set(0, "a");
+  let parents: Vec<_> = tree.push_leaf("b").collect();
+  assert_eq!(parents, [1]);
+  assert_eq!(tree.get(1), None);
+}
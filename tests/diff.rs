@@ -0,0 +1,58 @@
+extern crate flat_tree;
+
+use flat_tree::{
+  render_diff, NodeSet, Orientation, RenderOptions, TreeDiff, TreeIndex,
+};
+
+fn set(nodes: &[usize]) -> NodeSet {
+  nodes.iter().cloned().collect()
+}
+
+#[test]
+fn splits_nodes() {
+  let diff = TreeDiff::new(&set(&[0, 1, 4]), &set(&[1, 2, 4, 6]));
+  assert_eq!(diff.added, set(&[2, 6]));
+  assert_eq!(diff.removed, set(&[0]));
+  assert_eq!(diff.common, set(&[1, 4]));
+  assert!(!diff.is_empty());
+  assert!(TreeDiff::new(&set(&[3]), &set(&[3])).is_empty());
+}
+
+#[test]
+fn between_snapshots() {
+  let mut before = TreeIndex::new();
+  before.set(0);
+  let mut after = before.clone();
+  after.set(2);
+  after.set(4);
+  let diff = TreeDiff::between(&before, &after, 3);
+  assert_eq!(diff.added, set(&[1, 2, 4]));
+  assert!(diff.removed.is_empty());
+  assert_eq!(diff.common, set(&[0]));
+
+  // Nodes past the end of the tree aren't compared.
+  assert_eq!(TreeDiff::between(&before, &after, 2).added, set(&[1, 2]));
+  assert_eq!(TreeDiff::between(&before, &after, 0), TreeDiff::default());
+}
+
+#[test]
+fn renders_changes() {
+  let diff = TreeDiff::new(&set(&[0, 1, 2]), &set(&[0, 3, 4]));
+  let opts = RenderOptions::new().orientation(Orientation::LeftRight);
+  assert_eq!(
+    render_diff(4, &diff, opts),
+    concat!(
+      "3*\n",
+      "├── 1+\n",
+      "│   ├── 0#\n",
+      "│   └── 2+\n",
+      "└── 5\n",
+      "    ├── 4*\n",
+      "    └── 6\n",
+      "\n",
+      "* added\n",
+      "+ removed\n",
+      "# common\n",
+    )
+  );
+}
@@ -0,0 +1,53 @@
+extern crate flat_tree;
+
+use flat_tree::explain;
+
+#[test]
+fn matches_the_index_functions() {
+  for i in 0..1024 {
+    let explanation = explain(i);
+    assert_eq!(explanation.index, i);
+    assert_eq!(explanation.depth, flat_tree::depth(i));
+    assert_eq!(explanation.offset, flat_tree::offset(i));
+    assert_eq!(explanation.parent, flat_tree::parent(i));
+    assert_eq!(explanation.sibling, flat_tree::sibling(i));
+    assert_eq!(explanation.children, flat_tree::children(i));
+    assert_eq!(explanation.spans, flat_tree::spans(i));
+    assert_eq!(explanation.leaves, flat_tree::count(i) / 2 + 1);
+  }
+}
+
+#[test]
+fn explains_a_leaf() {
+  assert_eq!(
+    explain(4).to_string(),
+    concat!(
+      "node 4 (0b100)\n",
+      "depth 0: the index is even\n",
+      "offset 2: 0b100 >> 1\n",
+      "parent 5: depth 1, offset 2 / 2 = 1\n",
+      "sibling 6: depth 0, offset 2 ^ 1 = 3\n",
+      "children: none, the node is a leaf\n",
+      "spans 4 to 4: leaves 2 to 2\n",
+      "leaves 1: 2^0\n",
+    )
+  );
+}
+
+#[test]
+fn end_of_the_range() {
+  let deepest = flat_tree::index(flat_tree::Iterator::MAX_DEPTH - 1, 3);
+  for &i in &[usize::MAX - 1, usize::MAX - 2, deepest] {
+    let explanation = explain(i);
+    assert_eq!(explanation.parent, flat_tree::parent(i));
+    assert_eq!(explanation.spans, flat_tree::spans(i));
+    assert_eq!(explanation.spans.1, usize::MAX - 1);
+    assert!(explanation.to_string().starts_with(&format!("node {} ", i)));
+  }
+}
+
+#[test]
+#[should_panic(expected = "has no parent within the maximum cursor depth")]
+fn too_deep() {
+  explain(usize::MAX);
+}
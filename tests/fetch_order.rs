@@ -0,0 +1,50 @@
+extern crate flat_tree;
+
+use flat_tree::FetchOrder;
+
+fn sorted(mut nodes: Vec<usize>) -> Vec<usize> {
+  nodes.sort();
+  nodes
+}
+
+#[test]
+fn fetch_orders_are_permutations() {
+  let orders = [
+    FetchOrder::Linear,
+    FetchOrder::Reverse,
+    FetchOrder::Random(0),
+    FetchOrder::Random(42),
+    FetchOrder::CenterOut,
+  ];
+  for leaves in 0..33 {
+    let linear = FetchOrder::Linear.leaves(leaves);
+    assert_eq!(linear, (0..leaves).map(|leaf| 2 * leaf).collect::<Vec<_>>());
+    for order in &orders {
+      assert_eq!(sorted(order.leaves(leaves)), linear);
+    }
+  }
+}
+
+#[test]
+fn fetch_order_reverse() {
+  assert_eq!(FetchOrder::Reverse.arrange(vec![5, 1, 3]), [5, 3, 1]);
+}
+
+#[test]
+fn fetch_order_random_depends_on_seed() {
+  let a = FetchOrder::Random(1).leaves(64);
+  assert_eq!(a, FetchOrder::Random(1).leaves(64));
+  assert_ne!(a, FetchOrder::Random(2).leaves(64));
+  assert_ne!(a, FetchOrder::Linear.leaves(64));
+}
+
+#[test]
+fn fetch_order_center_out() {
+  assert_eq!(FetchOrder::CenterOut.leaves(0), [0usize; 0]);
+  assert_eq!(FetchOrder::CenterOut.leaves(1), [0]);
+  assert_eq!(FetchOrder::CenterOut.leaves(4), [4, 2, 6, 0]);
+  assert_eq!(
+    FetchOrder::CenterOut.arrange(vec![1, 5, 9, 13, 17, 21]),
+    [13, 9, 17, 5, 21, 1]
+  );
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "ffi")]
+
+extern crate flat_tree;
+
+use flat_tree::ffi::*;
+use std::ptr;
+
+#[test]
+fn index_functions() {
+  for i in 0..256 {
+    assert_eq!(flat_tree_parent(i), flat_tree::parent(i));
+    assert_eq!(flat_tree_sibling(i), flat_tree::sibling(i));
+    assert_eq!(flat_tree_depth(i), flat_tree::depth(i));
+    assert_eq!(flat_tree_offset(i), flat_tree::offset(i));
+    assert_eq!(flat_tree_index(flat_tree_depth(i), flat_tree_offset(i)), i);
+    assert_eq!(flat_tree_left_span(i), flat_tree::left_span(i));
+    assert_eq!(flat_tree_right_span(i), flat_tree::right_span(i));
+    assert_eq!(flat_tree_count(i), flat_tree::count(i));
+    let (mut left, mut right) = (0, 0);
+    let has_children = unsafe { flat_tree_children(i, &mut left, &mut right) };
+    match flat_tree::children(i) {
+      Some(children) => {
+        assert!(has_children);
+        assert_eq!((left, right), children);
+      }
+      None => assert!(!has_children),
+    }
+  }
+}
+
+#[test]
+fn full_roots_into_a_buffer() {
+  let mut out = [0; 2];
+  unsafe {
+    assert_eq!(flat_tree_full_roots(14, ptr::null_mut(), 0), 3);
+    assert_eq!(flat_tree_full_roots(14, out.as_mut_ptr(), out.len()), 3);
+    assert_eq!(out, [3, 9]);
+    assert_eq!(flat_tree_full_roots(8, out.as_mut_ptr(), out.len()), 1);
+    assert_eq!(out[0], 3);
+    assert_eq!(flat_tree_full_roots(1, out.as_mut_ptr(), out.len()), !0);
+  }
+}
+
+#[test]
+fn cursor_steps() {
+  let cursor = flat_tree_cursor_new(0);
+  unsafe {
+    assert!(!flat_tree_cursor_prev(cursor));
+    assert!(!flat_tree_cursor_left_child(cursor));
+    assert!(flat_tree_cursor_parent(cursor));
+    assert_eq!(flat_tree_cursor_index(cursor), 1);
+    assert!(flat_tree_cursor_next(cursor));
+    assert_eq!(flat_tree_cursor_index(cursor), 5);
+    assert!(flat_tree_cursor_sibling(cursor));
+    assert_eq!(flat_tree_cursor_index(cursor), 1);
+    assert!(flat_tree_cursor_right_child(cursor));
+    assert_eq!(flat_tree_cursor_index(cursor), 2);
+    flat_tree_cursor_seek(cursor, 11);
+    assert_eq!(flat_tree_cursor_depth(cursor), 2);
+    assert_eq!(flat_tree_cursor_offset(cursor), 1);
+    assert!(flat_tree_cursor_right_span(cursor));
+    assert_eq!(flat_tree_cursor_index(cursor), 14);
+    flat_tree_cursor_free(cursor);
+    flat_tree_cursor_free(ptr::null_mut());
+  }
+}
+
+#[test]
+fn tree_index_operations() {
+  let tree = flat_tree_tree_index_new();
+  let mut out = [0; 4];
+  let mut verified_by = 0;
+  unsafe {
+    for leaf in &[0, 2, 4] {
+      assert!(flat_tree_tree_index_set(tree, *leaf));
+    }
+    assert!(flat_tree_tree_index_get(tree, 1));
+    assert!(!flat_tree_tree_index_get(tree, 3));
+    assert_eq!(flat_tree_tree_index_blocks(tree), 3);
+    assert_eq!(flat_tree_tree_index_verified_by(tree, 0), 6);
+    assert_eq!(flat_tree_tree_index_digest(tree, 0), 1);
+    assert_eq!(flat_tree_tree_index_roots(tree, out.as_mut_ptr(), 4), 2);
+    assert_eq!(out[..2], [1, 4]);
+    let len = flat_tree_tree_index_proof(
+      tree,
+      0,
+      0,
+      false,
+      out.as_mut_ptr(),
+      4,
+      &mut verified_by,
+    );
+    assert_eq!(out[..len], [2, 4]);
+    assert_eq!(verified_by, 6);
+    let len = flat_tree_tree_index_proof(
+      tree,
+      6,
+      0,
+      false,
+      out.as_mut_ptr(),
+      4,
+      &mut verified_by,
+    );
+    assert_eq!(len, !0);
+    flat_tree_tree_index_free(tree);
+  }
+}
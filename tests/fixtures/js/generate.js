@@ -0,0 +1,81 @@
+// Writes the fixtures checked by tests/js_parity.rs, using the reference
+// JavaScript implementation:
+//
+//   npm install flat-tree flat-tree-iterator
+//   node tests/fixtures/js/generate.js > tests/fixtures/js/flat-tree.json
+//
+// Versions of flat-tree without a built-in iterator use flat-tree-iterator
+// for the iterator steps. Functions that the installed version doesn't have
+// are left out. Until the output is committed, the parity test in
+// tests/js_parity.rs is ignored.
+'use strict'
+
+const flat = require('flat-tree')
+const { version } = require('flat-tree/package.json')
+const iterator = flat.iterator || require('flat-tree-iterator')
+const packages = ['flat-tree@' + version]
+if (!flat.iterator) {
+  packages.push('flat-tree-iterator@' +
+    require('flat-tree-iterator/package.json').version)
+}
+
+const MAX_INDEX = 1024
+const FUNCTIONS = [
+  'depth', 'offset', 'parent', 'sibling', 'uncle', 'children', 'leftChild',
+  'rightChild', 'leftSpan', 'rightSpan', 'spans', 'count', 'fullRoots'
+]
+const STEPS = [
+  'next', 'prev', 'parent', 'sibling', 'leftChild', 'rightChild', 'leftSpan',
+  'rightSpan', 'nextTree', 'prevTree', 'isLeft', 'isRight'
+]
+
+// A small seeded generator, so the fixtures are reproducible.
+let state = 1
+function random (n) {
+  state = (state * 1103515245 + 12345) % 2147483648
+  return state % n
+}
+
+function call (name, args) {
+  try {
+    return { name, args, result: flat[name](...args) }
+  } catch (err) {
+    return { name, args, throws: true }
+  }
+}
+
+const functions = []
+for (const name of FUNCTIONS) {
+  if (typeof flat[name] !== 'function') continue
+  for (let i = 0; i < MAX_INDEX; i++) functions.push(call(name, [i]))
+}
+for (let depth = 0; depth < 12; depth++) {
+  for (let offset = 0; offset < 64; offset++) {
+    functions.push(call('index', [depth, offset]))
+  }
+}
+
+const iterators = []
+for (let n = 0; n < 200; n++) {
+  const start = random(MAX_INDEX)
+  const ite = iterator(start)
+  const steps = []
+  for (let s = 0; s < 32; s++) {
+    const op = STEPS[random(STEPS.length)]
+    if (typeof ite[op] !== 'function') continue
+    // Stay well within the integers a double represents exactly.
+    if (op === 'parent' && ite.factor >= 2 ** 40) continue
+    const result = ite[op]()
+    steps.push({
+      op,
+      result,
+      index: ite.index,
+      offset: ite.offset,
+      factor: ite.factor
+    })
+  }
+  iterators.push({ start, steps })
+}
+
+const fixture = { package: packages.join(' '), functions, iterators }
+process.stdout.write(JSON.stringify(fixture) + '\n')
@@ -0,0 +1,67 @@
+extern crate flat_tree;
+
+use flat_tree::{completed_parents, full_roots, Frontier};
+
+#[test]
+fn completed_parents_span_up_to_leaf() {
+  for leaf in (0..256).step_by(2) {
+    for parent in completed_parents(leaf) {
+      assert_eq!(flat_tree::right_span(parent), leaf);
+    }
+    let next = completed_parents(leaf).last().unwrap_or(leaf);
+    assert_ne!(flat_tree::right_span(flat_tree::parent(next)), leaf);
+  }
+}
+
+#[test]
+#[should_panic]
+fn completed_parents_of_parent() {
+  completed_parents(1);
+}
+
+#[test]
+fn frontier_matches_full_roots() {
+  let mut frontier = Frontier::new();
+  assert!(frontier.is_empty());
+  assert_eq!(frontier.roots(), [0usize; 0]);
+  for leaves in 1..128 {
+    assert_eq!(frontier.push(), 2 * (leaves - 1));
+    assert_eq!(frontier.leaves(), leaves);
+    let mut roots = Vec::new();
+    full_roots(2 * leaves, &mut roots);
+    assert_eq!(frontier.roots(), &roots[..]);
+    assert_eq!(frontier, Frontier::with_leaves(leaves));
+  }
+}
+
+#[test]
+fn frontier_diff_covers_every_leaf_once() {
+  for local in 0..20 {
+    for remote in 0..20 {
+      let diff =
+        Frontier::with_leaves(local).diff(&Frontier::with_leaves(remote));
+      assert_eq!(diff.shared_leaves, local.min(remote));
+      let mut leaves = Vec::new();
+      for node in diff.shared.iter().chain(&diff.local).chain(&diff.remote) {
+        let (left, right) = flat_tree::spans(node);
+        leaves.extend((left..right + 1).step_by(2));
+      }
+      leaves.sort();
+      let expected: Vec<_> = (0..local.max(remote)).map(|n| 2 * n).collect();
+      assert_eq!(leaves, expected);
+      assert!(diff.local.is_empty() || diff.remote.is_empty());
+    }
+  }
+}
+
+#[test]
+fn frontier_diff_forks() {
+  let diff = Frontier::with_leaves(6).diff(&Frontier::with_leaves(6));
+  assert_eq!(diff.shared.as_slice(), [3, 9]);
+  assert!(diff.local.is_empty());
+  assert!(diff.remote.is_empty());
+
+  let diff = Frontier::with_leaves(5).diff(&Frontier::with_leaves(2));
+  assert_eq!(diff.shared.as_slice(), [1]);
+  assert_eq!(diff.local.as_slice(), [5, 8]);
+}
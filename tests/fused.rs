@@ -0,0 +1,22 @@
+extern crate flat_tree;
+
+use flat_tree::{nodes, Order};
+use std::iter::FusedIterator;
+
+fn assert_fused<I: FusedIterator>(mut iter: I) {
+  while iter.next().is_some() {}
+  for _ in 0..4 {
+    assert!(iter.next().is_none());
+  }
+}
+
+#[test]
+fn fused() {
+  assert_fused(nodes(5, Order::InOrder));
+  assert_fused(nodes(5, Order::PreOrder));
+  assert_fused(nodes(5, Order::PostOrder));
+  assert_fused(nodes(5, Order::LevelOrder));
+  assert_fused(nodes(5, Order::PostOrder).with_coords());
+  assert_fused(flat_tree::Iterator::new(0).bounded(10));
+  assert_fused(flat_tree::Iterator::new(7).leaves());
+}
@@ -0,0 +1,131 @@
+extern crate flat_tree;
+
+use flat_tree::{to_dot, to_mermaid, GraphOptions, Highlights, NodeSet};
+
+fn edges(dot: &str) -> Vec<&str> {
+  dot.lines().filter(|line| line.contains("->")).collect()
+}
+
+fn nodes(dot: &str) -> Vec<&str> {
+  dot
+    .lines()
+    .filter(|line| line.contains("[label="))
+    .collect()
+}
+
+#[test]
+fn dot_whole_tree() {
+  let dot = to_dot(4, GraphOptions::new());
+  assert!(dot.starts_with("digraph flat_tree {\n"));
+  assert!(dot.ends_with("}\n"));
+  assert_eq!(nodes(&dot).len(), 7);
+  assert_eq!(
+    edges(&dot),
+    [
+      "  n1 -> n0;",
+      "  n1 -> n2;",
+      "  n3 -> n1;",
+      "  n3 -> n5;",
+      "  n5 -> n4;",
+      "  n5 -> n6;",
+    ]
+  );
+  assert!(dot.contains("  n3 [label=\"3\\ndepth 2, offset 0\"];\n"));
+}
+
+#[test]
+fn dot_forest() {
+  let dot = to_dot(3, GraphOptions::new());
+  assert_eq!(nodes(&dot).len(), 4);
+  assert!(!dot.contains("n3"));
+  assert!(!dot.contains("n5"));
+  assert_eq!(edges(&dot), ["  n1 -> n0;", "  n1 -> n2;"]);
+
+  assert_eq!(nodes(&to_dot(0, GraphOptions::new())).len(), 0);
+}
+
+#[test]
+fn dot_subtree() {
+  let dot = to_dot(8, GraphOptions::new().subtree(11));
+  assert_eq!(nodes(&dot).len(), 7);
+  assert_eq!(edges(&dot).len(), 6);
+  assert!(dot.contains("n8 ["));
+  assert!(!dot.contains("n7 ["));
+
+  let dot = to_dot(2, GraphOptions::new().subtree(5));
+  assert_eq!(nodes(&dot).len(), 0);
+}
+
+#[test]
+fn dot_node_set() {
+  let set: NodeSet = vec![0, 1, 3, 6, 100].into_iter().collect();
+  let dot = to_dot(4, GraphOptions::new().nodes(&set));
+  assert_eq!(nodes(&dot).len(), 4);
+  assert_eq!(edges(&dot), ["  n1 -> n0;", "  n3 -> n1;"]);
+
+  let dot = to_dot(4, GraphOptions::new().nodes(&set).subtree(5));
+  assert_eq!(nodes(&dot), ["  n6 [label=\"6\\ndepth 0, offset 3\"];"]);
+}
+
+#[test]
+fn mermaid_matches_dot() {
+  let set: NodeSet = (0..20).collect();
+  for opts in &[
+    GraphOptions::new(),
+    GraphOptions::new().subtree(7),
+    GraphOptions::new().nodes(&set),
+  ] {
+    let dot = to_dot(7, *opts);
+    let mermaid = to_mermaid(7, *opts);
+    assert!(mermaid.starts_with("flowchart TD\n"));
+    let mermaid_edges: Vec<_> = mermaid
+      .lines()
+      .filter(|line| line.contains("-->"))
+      .collect();
+    let dot_edges: Vec<_> = edges(&dot)
+      .iter()
+      .map(|edge| edge.replace(" -> ", " --> ").replace(';', ""))
+      .collect();
+    assert_eq!(mermaid_edges, dot_edges);
+    assert_eq!(
+      mermaid
+        .lines()
+        .filter(|line| line.contains("<br/>"))
+        .count(),
+      nodes(&dot).len()
+    );
+  }
+  assert_eq!(to_mermaid(0, GraphOptions::new()), "flowchart TD\n");
+}
+
+#[test]
+fn highlights() {
+  let have: NodeSet = vec![0, 1].into_iter().collect();
+  let want: NodeSet = vec![1, 2].into_iter().collect();
+  let highlights = Highlights::new()
+    .set("have", &have)
+    .set("want", &want)
+    .annotate(|i| {
+      if i == 2 {
+        Some(r#"a "b""#.to_string())
+      } else {
+        None
+      }
+    });
+  let opts = GraphOptions::new().highlights(&highlights);
+  assert_eq!(
+    nodes(&to_dot(2, opts)),
+    [
+      r##"  n0 [label="0\ndepth 0, offset 0\nhave" style=filled fillcolor="#1f77b4"];"##,
+      r##"  n1 [label="1\ndepth 1, offset 0\nhave, want" style=filled fillcolor="#1f77b4"];"##,
+      r##"  n2 [label="2\ndepth 0, offset 1\nwant\na \"b\"" style=filled fillcolor="#ff7f0e"];"##,
+    ]
+  );
+  let mermaid = to_mermaid(2, opts);
+  assert!(mermaid.contains(
+    r#"  n2["2<br/>depth 0, offset 1<br/>want<br/>a #quot;b#quot;"]"#
+  ));
+  assert!(mermaid.contains("  classDef s0 fill:#1f77b4\n"));
+  assert!(mermaid.contains("  classDef s1 fill:#ff7f0e\n"));
+  assert!(mermaid.ends_with("  class n0 s0\n  class n1 s0\n  class n2 s1\n"));
+}
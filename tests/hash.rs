@@ -0,0 +1,90 @@
+#![cfg(feature = "hash")]
+
+extern crate flat_tree;
+extern crate sha2;
+
+use flat_tree::{leaf_hash, merkle_root, node_hash, parent_hash};
+use sha2::{Digest, Sha256};
+
+fn hex(bytes: &[u8]) -> String {
+  bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[test]
+fn merkle_root_empty() {
+  assert_eq!(merkle_root::<Sha256, &[u8]>(&[]), None);
+}
+
+#[test]
+fn merkle_root_single_leaf() {
+  let leaves = [b"hello"];
+  assert_eq!(
+    merkle_root::<Sha256, _>(&leaves),
+    Some(leaf_hash::<Sha256>(b"hello"))
+  );
+}
+
+#[test]
+fn leaf_and_parent_are_domain_separated() {
+  let leaf = leaf_hash::<Sha256>(b"");
+  assert_eq!(leaf, Sha256::digest([0u8]));
+  assert_ne!(leaf, Sha256::digest(b""));
+
+  let parent = parent_hash::<Sha256>(b"", b"");
+  assert_eq!(parent, Sha256::digest([1u8]));
+  assert_ne!(leaf, parent);
+}
+
+#[test]
+fn node_hash_combines_in_flat_tree_order() {
+  let leaves = [b"a", b"b", b"c", b"d"];
+  let hashes: Vec<_> = leaves
+    .iter()
+    .map(|leaf| leaf_hash::<Sha256>(*leaf))
+    .collect();
+  let left = parent_hash::<Sha256>(&hashes[0], &hashes[1]);
+  let right = parent_hash::<Sha256>(&hashes[2], &hashes[3]);
+
+  assert_eq!(node_hash::<Sha256, _>(4, &leaves), hashes[2]);
+  assert_eq!(node_hash::<Sha256, _>(1, &leaves), left);
+  assert_eq!(node_hash::<Sha256, _>(5, &leaves), right);
+  assert_eq!(
+    node_hash::<Sha256, _>(3, &leaves),
+    parent_hash::<Sha256>(&left, &right)
+  );
+}
+
+#[test]
+#[should_panic]
+fn node_hash_out_of_range() {
+  node_hash::<Sha256, _>(3, &[b"a", b"b", b"c"]);
+}
+
+#[test]
+fn merkle_root_matches_rfc_6962() {
+  // Test vectors from the certificate transparency reference implementation.
+  let leaves: Vec<&[u8]> = vec![
+    b"",
+    b"\x00",
+    b"\x10",
+    b"\x20\x21",
+    b"\x30\x31",
+    b"\x40\x41\x42\x43",
+    b"\x50\x51\x52\x53\x54\x55\x56\x57",
+    b"\x60\x61\x62\x63\x64\x65\x66\x67\x68\x69\x6a\x6b\x6c\x6d\x6e\x6f",
+  ];
+  let roots = [
+    "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d",
+    "fac54203e7cc696cf0dfcb42c92a1d9dbaf70ad9e621f4bd8d98662f00e3c125",
+    "aeb6bcfe274b70a14fb067a5e5578264db0fa9b51af5e0ba159158f329e06e77",
+    "d37ee418976dd95753c1c73862b9398fa2a2cf9b4ff0fdfe8b30cd95209614b7",
+    "4e3bbb1f7b478dcfe71fb631631519a3bca12c9aefca1612bfce4c13a86264d4",
+    "76e67dadbcdf1e10e1b74ddc608abd2f98dfb16fbce75277b5232a127f2087ef",
+    "ddb89be403809e325750d3d263cd78929c2942b7942a34b77e122c9594a74c8c",
+    "5dc9da79a70659a9ad559cb701ded9a2ab9d823aad2f4960cfe370eff4604328",
+  ];
+  for (len, root) in roots.iter().enumerate() {
+    let computed = merkle_root::<Sha256, _>(&leaves[..len + 1]).unwrap();
+    assert_eq!(hex(&computed), *root, "{} leaves", len + 1);
+  }
+}
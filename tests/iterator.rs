@@ -1,6 +1,7 @@
 extern crate flat_tree;
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn iterator() {
   let mut iterator = flat_tree::Iterator::default();
   assert_eq!(iterator.index(), 0);
@@ -55,3 +56,317 @@ fn non_leaf_start() {
   assert_eq!(iterator.next(), Some(13));
   assert_eq!(iterator.left_span(), 12);
 }
+
+#[test]
+fn bounded() {
+  let mut iterator = flat_tree::Iterator::new(3).bounded(20);
+  assert_eq!(iterator.next(), Some(11));
+  assert_eq!(iterator.next(), Some(19));
+  assert_eq!(iterator.next(), None);
+  assert_eq!(iterator.next(), None);
+  assert_eq!(iterator.get_ref().index(), 19);
+
+  let mut iterator = flat_tree::Iterator::new(4).bounded(3);
+  assert_eq!(iterator.next(), None);
+  assert_eq!(iterator.into_inner().index(), 4);
+
+  let mut iterator =
+    flat_tree::Iterator::new(usize::MAX - 1).bounded(usize::MAX);
+  assert_eq!(iterator.next(), None);
+}
+
+#[test]
+fn next_tree() {
+  let mut iterator = flat_tree::Iterator::new(0);
+  assert_eq!(iterator.next_tree(), 2);
+  assert_eq!(iterator.next_tree(), 4);
+  assert_eq!(iterator.next_tree(), 6);
+  iterator.seek(1);
+  assert_eq!(iterator.next_tree(), 4);
+  iterator.seek(7);
+  assert_eq!(iterator.next_tree(), 16);
+  assert_eq!(iterator.offset(), 8);
+  assert_eq!(iterator.parent(), 17);
+}
+
+#[test]
+fn prev_tree() {
+  let mut iterator = flat_tree::Iterator::new(0);
+  assert_eq!(iterator.prev_tree(), 0);
+  iterator.seek(2);
+  assert_eq!(iterator.prev_tree(), 0);
+  iterator.seek(5);
+  assert_eq!(iterator.prev_tree(), 2);
+  iterator.seek(23);
+  assert_eq!(iterator.prev_tree(), 14);
+  assert_eq!(iterator.offset(), 7);
+  assert_eq!(iterator.parent(), 13);
+}
+
+#[test]
+fn full_root() {
+  let mut iterator = flat_tree::Iterator::new(0);
+  assert!(!iterator.full_root(0));
+  assert_eq!(iterator.index(), 0);
+
+  let mut roots = Vec::new();
+  flat_tree::full_roots(18, &mut roots);
+  let mut walked = Vec::new();
+  while iterator.full_root(18) {
+    walked.push(iterator.index());
+    iterator.next_tree();
+  }
+  assert_eq!(walked, roots);
+
+  iterator.seek(3);
+  assert!(!iterator.full_root(18));
+  assert_eq!(iterator.index(), 3);
+}
+
+#[test]
+fn contains() {
+  for i in 0..64 {
+    let iterator = flat_tree::Iterator::new(i);
+    let (left, right) = flat_tree::spans(i);
+    for j in 0..128 {
+      assert_eq!(iterator.contains(j), left <= j && j <= right);
+    }
+  }
+}
+
+#[test]
+fn depth_and_factor() {
+  let mut iterator = flat_tree::Iterator::new(0);
+  for depth in 0..40 {
+    assert_eq!(iterator.depth(), depth);
+    assert_eq!(iterator.depth(), flat_tree::depth(iterator.index()));
+    assert_eq!(iterator.factor(), 2 << depth);
+    iterator.parent();
+  }
+}
+
+#[test]
+fn peek() {
+  for i in 0..256 {
+    let iterator = flat_tree::Iterator::new(i);
+    assert_eq!(iterator.peek_parent(), flat_tree::parent(i));
+    assert_eq!(iterator.peek_sibling(), flat_tree::sibling(i));
+    assert_eq!(
+      iterator.peek_left_child(),
+      flat_tree::left_child(i).unwrap_or(i)
+    );
+    assert_eq!(
+      iterator.peek_right_child(),
+      flat_tree::right_child(i).unwrap_or(i)
+    );
+    assert_eq!(iterator.index(), i);
+
+    let mut moved = flat_tree::Iterator::new(i);
+    assert_eq!(moved.parent(), iterator.peek_parent());
+    let mut moved = flat_tree::Iterator::new(i);
+    assert_eq!(moved.sibling(), iterator.peek_sibling());
+    let mut moved = flat_tree::Iterator::new(i);
+    assert_eq!(moved.left_child(), iterator.peek_left_child());
+    let mut moved = flat_tree::Iterator::new(i);
+    assert_eq!(moved.right_child(), iterator.peek_right_child());
+  }
+}
+
+#[test]
+fn try_prev() {
+  let mut iterator = flat_tree::Iterator::new(19);
+  assert_eq!(iterator.try_prev(), Some(11));
+  assert_eq!(iterator.try_prev(), Some(3));
+  assert_eq!(iterator.try_prev(), None);
+  assert_eq!(iterator.index(), 3);
+  assert_eq!(iterator.offset(), 0);
+  assert_eq!(iterator.prev(), 3);
+}
+
+#[test]
+fn try_child() {
+  let mut iterator = flat_tree::Iterator::new(23);
+  let mut path = Vec::new();
+  while let Some(index) = iterator.try_right_child() {
+    path.push(index);
+  }
+  assert_eq!(path, [27, 29, 30]);
+  assert_eq!(iterator.offset(), 15);
+
+  iterator.seek(23);
+  let mut path = Vec::new();
+  while let Some(index) = iterator.try_left_child() {
+    path.push(index);
+  }
+  assert_eq!(path, [19, 17, 16]);
+  assert_eq!(iterator.offset(), 8);
+}
+
+#[test]
+fn with_tree_len() {
+  let mut cursor = flat_tree::Iterator::new(0).with_tree_len(6);
+  assert_eq!(cursor.tree_len(), 6);
+  assert_eq!(cursor.parent(), Some(1));
+  assert_eq!(cursor.parent(), Some(3));
+  assert_eq!(cursor.parent(), None);
+  assert_eq!(cursor.sibling(), None);
+  assert_eq!(cursor.index(), 3);
+  assert_eq!(cursor.right_child(), Some(5));
+  assert_eq!(cursor.next(), Some(9));
+  assert_eq!(cursor.next(), None);
+  assert_eq!(cursor.right_child(), Some(10));
+  assert_eq!(cursor.right_child(), None);
+  assert_eq!(cursor.next(), None);
+  assert_eq!(cursor.prev(), Some(8));
+  assert_eq!(cursor.parent(), Some(9));
+  assert_eq!(cursor.parent(), None);
+  assert_eq!(cursor.into_inner().index(), 9);
+
+  let cursor = flat_tree::Iterator::new(0).with_tree_len(5);
+  assert_eq!(cursor.collect::<Vec<_>>(), [2, 4, 6, 8]);
+}
+
+#[test]
+fn checkpoint() {
+  use std::collections::HashSet;
+
+  let mut iterator = flat_tree::Iterator::new(0);
+  iterator.parent();
+  let checkpoint = iterator;
+  iterator.parent();
+  iterator.right_child();
+  assert_ne!(iterator, checkpoint);
+  iterator = checkpoint;
+  assert_eq!(iterator.index(), 1);
+  assert_eq!(iterator, flat_tree::Iterator::new(1));
+
+  let mut seen = HashSet::new();
+  assert!(seen.insert(iterator));
+  assert!(!seen.insert(flat_tree::Iterator::new(1)));
+}
+
+#[test]
+fn leaves() {
+  for i in 0..128 {
+    let iterator = flat_tree::Iterator::new(i);
+    let (left, right) = flat_tree::spans(i);
+    let leaves: Vec<usize> = iterator.leaves().collect();
+    assert_eq!(leaves, (left..=right).step_by(2).collect::<Vec<_>>());
+    assert_eq!(iterator.leaves().len(), leaves.len());
+    let mut reversed: Vec<usize> = iterator.leaves().rev().collect();
+    reversed.reverse();
+    assert_eq!(reversed, leaves);
+    assert_eq!(iterator.index(), i);
+  }
+}
+
+#[test]
+fn leaves_at_the_end_of_the_range() {
+  let iterator = flat_tree::GenericIterator::<u32>::new(u32::MAX - 1);
+  assert_eq!(iterator.leaves().collect::<Vec<_>>(), [u32::MAX - 1]);
+  let iterator = flat_tree::GenericIterator::<u32>::new(u32::MAX - 2);
+  let leaves = [u32::MAX - 3, u32::MAX - 1];
+  assert_eq!(iterator.leaves().collect::<Vec<_>>(), leaves);
+  assert_eq!(
+    iterator.leaves().rev().collect::<Vec<_>>(),
+    [leaves[1], leaves[0]]
+  );
+}
+
+#[test]
+fn new_at_root() {
+  for leaves in 1..200 {
+    let iterator = flat_tree::Iterator::new_at_root(leaves);
+    let mut roots = Vec::new();
+    flat_tree::full_roots(2 * leaves, &mut roots);
+    assert_eq!(iterator.index(), roots[0]);
+    assert_eq!(iterator, flat_tree::Iterator::new(roots[0]));
+  }
+}
+
+#[test]
+#[should_panic]
+fn new_at_root_without_leaves() {
+  flat_tree::Iterator::new_at_root(0);
+}
+
+#[test]
+fn max_depth() {
+  let max_depth = flat_tree::Iterator::MAX_DEPTH;
+  let top = flat_tree::index(max_depth, 0);
+  let mut iterator = flat_tree::Iterator::new(top);
+  assert_eq!(iterator.depth(), max_depth);
+  assert_eq!(iterator.try_parent(), None);
+  assert_eq!(iterator.parent(), top);
+  assert_eq!(iterator.index(), top);
+  assert_eq!(iterator.next(), Some(flat_tree::index(max_depth, 1)));
+  assert_eq!(iterator.next(), None);
+  assert_eq!(iterator.offset(), 1);
+  assert_eq!(iterator.sibling(), top);
+  assert_eq!(iterator.sibling(), flat_tree::index(max_depth, 1));
+
+  let mut iterator = flat_tree::Iterator::new(usize::MAX - 1);
+  assert_eq!(iterator.next(), None);
+  assert_eq!(iterator.next_tree(), usize::MAX - 1);
+  assert_eq!(iterator.parent(), usize::MAX - 2);
+
+  let mut iterator = flat_tree::Iterator::new(0);
+  assert!(iterator.full_root(usize::MAX - 1));
+  assert_eq!(iterator.index(), top);
+}
+
+#[test]
+#[should_panic]
+fn seek_too_deep() {
+  flat_tree::Iterator::new(usize::MAX);
+}
+
+#[test]
+fn generic_index() {
+  let mut iterator = flat_tree::GenericIterator::new(0u32);
+  assert_eq!(iterator.next(), Some(2));
+  assert_eq!(iterator.parent(), 1);
+  assert_eq!(iterator.parent(), 3);
+  assert_eq!(iterator.right_child(), 5);
+  assert_eq!(iterator.leaves().collect::<Vec<_>>(), [4, 6]);
+  assert_eq!(flat_tree::GenericIterator::<u32>::MAX_DEPTH, 30);
+
+  let top = (1u64 << 62) - 1;
+  let mut iterator = flat_tree::GenericIterator::new(0u64);
+  assert!(iterator.full_root(u64::MAX - 1));
+  assert_eq!(iterator.index(), top);
+  assert_eq!(iterator.try_parent(), None);
+  assert_eq!(iterator.depth(), 62);
+
+  let mut iterator = flat_tree::GenericIterator::new(u128::MAX - 1);
+  assert_eq!(iterator.next(), None);
+  assert_eq!(iterator.parent(), u128::MAX - 2);
+
+  let mut cursor = flat_tree::GenericIterator::new(4u64).with_tree_len(3);
+  assert_eq!(cursor.parent(), None);
+  assert_eq!(cursor.prev(), Some(2));
+  assert_eq!(cursor.parent(), Some(1));
+  assert_eq!(cursor.next(), None);
+}
+
+#[test]
+#[should_panic]
+fn generic_index_too_deep() {
+  flat_tree::GenericIterator::new(u32::MAX);
+}
+
+#[test]
+fn debug_shows_position() {
+  let iterator = flat_tree::Iterator::new(11);
+  assert_eq!(
+    format!("{:?}", iterator),
+    "Iterator { index: 11, depth: 2, offset: 1, factor: 2^3 }"
+  );
+  assert_eq!(
+    format!("{:?}", flat_tree::GenericIterator::new(4u32).bounded(8)),
+    concat!(
+      "BoundedIter { iter: Iterator { index: 4, depth: 0, offset: 2, ",
+      "factor: 2^1 }, max_index: 8 }"
+    )
+  );
+}
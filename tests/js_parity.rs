@@ -0,0 +1,154 @@
+extern crate flat_tree;
+extern crate serde_json;
+
+mod support;
+
+use serde_json::Value;
+use support::js_fixtures::{self, Fixture};
+
+/// Call the index function named `name` in JavaScript, returning the value
+/// the JavaScript function returns, or `None` where it throws.
+///
+/// ## Panics
+/// If there's no such function.
+fn call(name: &str, args: &[usize]) -> Option<Value> {
+  let i = args[0];
+  let value = match name {
+    "index" => Value::from(flat_tree::index(args[0], args[1])),
+    "depth" => Value::from(flat_tree::depth(i)),
+    "offset" => Value::from(flat_tree::offset(i)),
+    "parent" => Value::from(flat_tree::parent(i)),
+    "sibling" => Value::from(flat_tree::sibling(i)),
+    "uncle" => Value::from(flat_tree::uncle(i)),
+    "children" => match flat_tree::children(i) {
+      Some((left, right)) => Value::from(vec![left, right]),
+      None => Value::Null,
+    },
+    // JavaScript returns -1 for the children of a leaf.
+    "leftChild" => {
+      flat_tree::left_child(i).map_or(Value::from(-1), Value::from)
+    }
+    "rightChild" => {
+      flat_tree::right_child(i).map_or(Value::from(-1), Value::from)
+    }
+    "leftSpan" => Value::from(flat_tree::left_span(i)),
+    "rightSpan" => Value::from(flat_tree::right_span(i)),
+    "spans" => {
+      let (left, right) = flat_tree::spans(i);
+      Value::from(vec![left, right])
+    }
+    "count" => Value::from(flat_tree::count(i)),
+    "fullRoots" => {
+      if i % 2 == 1 {
+        return None;
+      }
+      let mut roots = Vec::new();
+      flat_tree::full_roots(i, &mut roots);
+      Value::from(roots)
+    }
+    _ => panic!("no function {:?}", name),
+  };
+  Some(value)
+}
+
+/// Call the iterator method named `op` in JavaScript.
+///
+/// ## Panics
+/// If there's no such method.
+fn step(iter: &mut flat_tree::Iterator, op: &str) -> Value {
+  let index = iter.index();
+  match op {
+    "next" => Value::from(std::iter::Iterator::next(iter).unwrap_or(index)),
+    "prev" => Value::from(iter.prev()),
+    "parent" => Value::from(iter.parent()),
+    "sibling" => Value::from(iter.sibling()),
+    "leftChild" => Value::from(iter.left_child()),
+    "rightChild" => Value::from(iter.right_child()),
+    "leftSpan" => Value::from(iter.left_span()),
+    "rightSpan" => Value::from(iter.right_span()),
+    "nextTree" => Value::from(iter.next_tree()),
+    "prevTree" => Value::from(iter.prev_tree()),
+    "isLeft" => Value::from(iter.is_left()),
+    "isRight" => Value::from(iter.is_right()),
+    _ => panic!("no iterator method {:?}", op),
+  }
+}
+
+/// Check every case of a fixture. Returns the number of cases checked.
+fn check(fixture: &Fixture) -> usize {
+  for case in &fixture.functions {
+    assert_eq!(
+      call(&case.name, &case.args),
+      case.result,
+      "{}({:?}) in {}",
+      case.name,
+      case.args,
+      fixture.package
+    );
+  }
+  for case in &fixture.iterators {
+    let mut iter = flat_tree::Iterator::new(case.start);
+    for (n, step_case) in case.steps.iter().enumerate() {
+      let context = || {
+        let ops: Vec<_> = case.steps[..=n].iter().map(|s| &s.op).collect();
+        format!("iterator({}) {:?} in {}", case.start, ops, fixture.package)
+      };
+      assert_eq!(
+        step(&mut iter, &step_case.op),
+        step_case.result,
+        "{}",
+        context()
+      );
+      assert_eq!(iter.index(), step_case.index, "{}", context());
+      assert_eq!(iter.offset(), step_case.offset, "{}", context());
+      assert_eq!(iter.factor(), step_case.factor, "{}", context());
+    }
+  }
+  fixture.functions.len() + fixture.iterators.len()
+}
+
+#[test]
+fn parses_fixtures() {
+  let fixture = js_fixtures::parse(
+    r#"{
+      "package": "flat-tree@0.0.0",
+      "functions": [
+        {"name": "children", "args": [3], "result": [1, 5]},
+        {"name": "fullRoots", "args": [1], "throws": true}
+      ],
+      "iterators": [
+        {"start": 0, "steps": [
+          {"op": "isLeft", "result": true, "index": 0, "offset": 0, "factor": 2}
+        ]}
+      ]
+    }"#,
+  )
+  .unwrap();
+  assert_eq!(fixture.package, "flat-tree@0.0.0");
+  assert_eq!(fixture.functions.len(), 2);
+  assert_eq!(fixture.functions[0].args, [3]);
+  assert_eq!(fixture.functions[1].result, None);
+  assert_eq!(fixture.iterators[0].steps[0].result, Value::Bool(true));
+  assert_eq!(check(&fixture), 3);
+
+  assert!(js_fixtures::parse("{}").is_err());
+  assert!(js_fixtures::parse(r#"{"package": "x", "functions": [{}]}"#).is_err());
+}
+
+// The fixtures aren't generated yet, so this is ignored rather than passing
+// without checking anything. Remove the `ignore` once `flat-tree.json` is
+// committed.
+#[test]
+#[ignore = "tests/fixtures/js/flat-tree.json isn't generated yet"]
+fn matches_js_fixtures() {
+  let fixtures = js_fixtures::load_all();
+  assert!(
+    !fixtures.is_empty(),
+    "no fixtures in {}, see generate.js there",
+    js_fixtures::dir().display()
+  );
+  for (path, fixture) in fixtures {
+    let cases = check(&fixture);
+    assert!(cases > 0, "{} has no cases", path.display());
+  }
+}
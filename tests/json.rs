@@ -0,0 +1,86 @@
+#![cfg(feature = "json")]
+
+extern crate flat_tree;
+
+use flat_tree::{Document, Frontier, JsonError, NodeSet, TreeDescription};
+
+#[test]
+fn node_sets_round_trip() {
+  let nodes: NodeSet = vec![9, 1, 4, 1].into_iter().collect();
+  let json = Document::from(nodes.clone()).to_json();
+  assert_eq!(json, r#"{"version":1,"kind":"node_set","nodes":[1,4,9]}"#);
+  assert_eq!(Document::from_json(&json), Ok(Document::NodeSet(nodes)));
+
+  let json = r#"{"kind":"node_set","nodes":[4,1,4],"version":1}"#;
+  assert_eq!(
+    Document::from_json(json),
+    Ok(Document::NodeSet(vec![1, 4].into_iter().collect()))
+  );
+}
+
+#[test]
+fn frontiers_round_trip() {
+  for leaves in 0..64 {
+    let frontier = Frontier::with_leaves(leaves);
+    let json = Document::from(frontier.clone()).to_json();
+    assert_eq!(Document::from_json(&json), Ok(Document::Frontier(frontier)));
+  }
+  assert_eq!(
+    Document::from(Frontier::new()).to_json(),
+    r#"{"version":1,"kind":"frontier","leaves":0,"roots":[]}"#
+  );
+}
+
+#[test]
+fn trees_round_trip() {
+  let tree = TreeDescription {
+    leaves: 3,
+    nodes: vec![0, 1, 4].into_iter().collect(),
+  };
+  let json = Document::from(tree.clone()).to_json();
+  assert_eq!(
+    json,
+    r#"{"version":1,"kind":"tree","leaves":3,"nodes":[0,1,4]}"#
+  );
+  assert_eq!(Document::from_json(&json), Ok(Document::Tree(tree)));
+}
+
+#[test]
+fn ignores_unknown_fields() {
+  let json = r#"{"version":1,"kind":"tree","leaves":1,"nodes":[],"note":"x"}"#;
+  assert_eq!(
+    Document::from_json(json),
+    Ok(Document::Tree(TreeDescription {
+      leaves: 1,
+      nodes: NodeSet::new(),
+    }))
+  );
+}
+
+#[test]
+fn rejects_invalid_documents() {
+  let err = |json: &str| Document::from_json(json).unwrap_err();
+
+  assert!(matches!(err("[]"), JsonError::Syntax(_)));
+  assert!(matches!(err(r#"{"version":1}"#), JsonError::Syntax(_)));
+  assert!(matches!(
+    err(r#"{"version":1,"kind":"forest","nodes":[]}"#),
+    JsonError::Syntax(_)
+  ));
+  assert_eq!(
+    err(r#"{"version":2,"kind":"node_set","nodes":[]}"#),
+    JsonError::UnsupportedVersion(2)
+  );
+  assert_eq!(
+    err(r#"{"version":1,"kind":"frontier","leaves":3,"roots":[3]}"#),
+    JsonError::RootsMismatch { leaves: 3 }
+  );
+  assert_eq!(
+    err(r#"{"version":1,"kind":"tree","leaves":3,"nodes":[5]}"#),
+    JsonError::NotInTree { node: 5, leaves: 3 }
+  );
+  assert_eq!(
+    err(r#"{"version":1,"kind":"tree","leaves":0,"nodes":[0]}"#),
+    JsonError::NotInTree { node: 0, leaves: 0 }
+  );
+}
@@ -0,0 +1,72 @@
+extern crate flat_tree;
+
+use flat_tree::kary;
+
+#[test]
+fn binary_matches_flat_tree() {
+  for i in 0..1024 {
+    assert_eq!(kary::depth(2, i), flat_tree::depth(i));
+    assert_eq!(kary::offset(2, i), flat_tree::offset(i));
+    assert_eq!(kary::parent(2, i), flat_tree::parent(i));
+    assert_eq!(kary::spans(2, i), flat_tree::spans(i));
+    let children = kary::children(2, i).map(|c| c.collect::<Vec<_>>());
+    let expected = flat_tree::children(i).map(|(l, r)| vec![l, r]);
+    assert_eq!(children, expected);
+  }
+}
+
+#[test]
+fn index_round_trips() {
+  for &arity in &[3, 4, 16] {
+    for i in 0..2000 {
+      let depth = kary::depth(arity, i);
+      let offset = kary::offset(arity, i);
+      assert_eq!(kary::index(arity, depth, offset), i);
+    }
+  }
+}
+
+#[test]
+fn parent_of_children() {
+  for &arity in &[3, 4, 16] {
+    for i in 0..2000 {
+      match kary::children(arity, i) {
+        Some(children) => {
+          let children: Vec<_> = children.collect();
+          assert_eq!(children.len(), arity);
+          for child in children {
+            assert_eq!(kary::parent(arity, child), i);
+            assert_eq!(kary::depth(arity, child) + 1, kary::depth(arity, i));
+          }
+        }
+        None => assert_eq!(kary::depth(arity, i), 0),
+      }
+    }
+  }
+}
+
+#[test]
+fn subtree_is_a_range() {
+  for &arity in &[3, 4, 16] {
+    for i in 0..2000 {
+      let (left, right) = kary::spans(arity, i);
+      let depth = kary::depth(arity, i);
+      let len: usize = (0..=depth as u32).map(|d| arity.pow(d)).sum();
+      assert_eq!(right - left + 1, len);
+      assert!(left <= i && i <= right);
+      for j in left..=right {
+        let mut node = j;
+        while kary::depth(arity, node) < depth {
+          node = kary::parent(arity, node);
+        }
+        assert_eq!(node, i);
+      }
+    }
+  }
+}
+
+#[test]
+#[should_panic]
+fn arity_of_one() {
+  kary::depth(1, 0);
+}
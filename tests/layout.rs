@@ -0,0 +1,42 @@
+extern crate flat_tree;
+
+use flat_tree::Layout;
+
+#[test]
+fn layout_round_trips() {
+  let layout = Layout::new(40, 1024).header(32);
+  assert_eq!(layout.record_size(), 40);
+  assert_eq!(layout.block_size(), 1024);
+  assert_eq!(layout.header_len(), 32);
+  for i in 0..64 {
+    let range = layout.node_range(i);
+    assert_eq!(range.end - range.start, 40);
+    assert_eq!(layout.node_at(range.start), Some(i));
+    assert_eq!(layout.node_at(range.end - 1), Some(i));
+  }
+  for leaf in (0..64).step_by(2) {
+    let range = layout.data_range(leaf);
+    assert_eq!(layout.leaf_at(range.start), leaf);
+    assert_eq!(layout.leaf_at(range.end - 1), leaf);
+  }
+}
+
+#[test]
+fn layout_file_lens() {
+  let layout = Layout::new(40, 1024).header(32);
+  assert_eq!(layout.tree_file_len(0), 32);
+  assert_eq!(layout.tree_file_len(1), 72);
+  assert_eq!(layout.tree_file_len(4), layout.node_range(6).end);
+  assert_eq!(layout.data_file_len(0), 0);
+  assert_eq!(layout.data_file_len(4), layout.data_range(6).end);
+
+  let layout = Layout::new(40, 1024);
+  assert_eq!(layout.node_offset(0), 0);
+  assert_eq!(layout.tree_file_len(0), 0);
+}
+
+#[test]
+#[should_panic]
+fn layout_data_of_parent() {
+  Layout::new(40, 1024).data_offset(1);
+}
@@ -0,0 +1,55 @@
+extern crate flat_tree;
+
+use flat_tree::mirrored;
+
+#[test]
+fn mirrored_children_swap_sides() {
+  for i in 0..1024 {
+    assert_eq!(mirrored::left_child(i), flat_tree::right_child(i));
+    assert_eq!(mirrored::right_child(i), flat_tree::left_child(i));
+    if let Some((left, right)) = mirrored::children(i) {
+      assert!(left > right);
+      assert_eq!(mirrored::left_span(left), mirrored::left_span(i));
+      assert_eq!(mirrored::right_span(right), mirrored::right_span(i));
+    }
+    assert_eq!(
+      mirrored::spans(i),
+      (mirrored::left_span(i), mirrored::right_span(i))
+    );
+  }
+}
+
+#[test]
+fn to_mirrored_round_trips() {
+  for leaves in 1..70 {
+    for i in flat_tree::inorder(leaves) {
+      let m = match mirrored::to_mirrored(i, leaves) {
+        Some(m) => m,
+        None => continue,
+      };
+      assert_eq!(mirrored::from_mirrored(m, leaves), Some(i));
+      assert_eq!(flat_tree::depth(m), flat_tree::depth(i));
+    }
+  }
+}
+
+#[test]
+fn to_mirrored_flips_leaves() {
+  for leaves in 1..70 {
+    for leaf in 0..leaves {
+      assert_eq!(
+        mirrored::to_mirrored(2 * leaf, leaves),
+        Some(2 * (leaves - 1 - leaf))
+      );
+    }
+    assert_eq!(mirrored::to_mirrored(2 * leaves, leaves), None);
+  }
+}
+
+#[test]
+fn to_mirrored_complete_tree() {
+  // With a power of two leaves, every node has a mirror image.
+  for i in flat_tree::inorder(32) {
+    assert_eq!(mirrored::to_mirrored(i, 32), Some(62 - i));
+  }
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "memmap2")]
+
+extern crate flat_tree;
+
+use flat_tree::{Bitfield, MmapBitfield, TreeIndex};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_path(name: &str) -> PathBuf {
+  let path = env::temp_dir().join(format!("flat-tree-{}.bitfield", name));
+  let _ = fs::remove_file(&path);
+  path
+}
+
+#[test]
+fn grows_in_pages() {
+  let path = temp_path("grows-in-pages");
+  let mut bits = MmapBitfield::open(&path).unwrap();
+  assert_eq!(bits.len(), 0);
+  assert!(!bits.get(10));
+  assert!(bits.grow(11));
+  assert_eq!(bits.len(), 4096 * 8);
+  assert!(bits.grow(4096 * 8 + 1));
+  assert_eq!(bits.len(), 2 * 4096 * 8);
+  assert_eq!(bits.file().metadata().unwrap().len(), 2 * 4096);
+  fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn persists_tree_index() {
+  let path = temp_path("persists-tree-index");
+  {
+    let mut tree = TreeIndex::from_bitfield(MmapBitfield::open(&path).unwrap());
+    for leaf in &[0, 2, 4, 6, 100_000] {
+      tree.set(*leaf);
+    }
+    tree.bitfield().sync().unwrap();
+  }
+
+  let tree = TreeIndex::from_bitfield(MmapBitfield::open(&path).unwrap());
+  assert!(tree.get(3));
+  assert!(tree.get(100_000));
+  assert_eq!(tree.blocks(), 4);
+  assert_eq!(
+    fs::read(&path).unwrap()[0],
+    0b1111_1110,
+    "the bits are laid out like a Vec<u8> bitfield"
+  );
+  fs::remove_file(&path).unwrap();
+}
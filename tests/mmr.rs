@@ -0,0 +1,73 @@
+extern crate flat_tree;
+
+use flat_tree::{
+  from_postorder, is_peak, mmr_leaves, mmr_size, peaks, to_postorder,
+};
+
+#[test]
+fn peaks_are_full_roots() {
+  for leaves in 0..130 {
+    let mut roots = Vec::new();
+    flat_tree::full_roots(2 * leaves, &mut roots);
+    let peaks = peaks(leaves);
+    assert_eq!(peaks.len(), roots.len());
+    for (&pos, &root) in peaks.iter().zip(&roots) {
+      assert_eq!(from_postorder(pos, leaves), Some(root));
+      assert_eq!(to_postorder(root, leaves), Some(pos));
+    }
+  }
+}
+
+#[test]
+fn last_peak_is_last_node() {
+  for leaves in 1..130 {
+    assert_eq!(peaks(leaves).last(), Some(&(mmr_size(leaves) - 1)));
+  }
+}
+
+#[test]
+fn is_peak_matches_peaks() {
+  for leaves in 0..130 {
+    let peaks = peaks(leaves);
+    for pos in 0..mmr_size(leaves) + 2 {
+      assert_eq!(is_peak(pos, leaves), peaks.contains(&pos));
+    }
+  }
+}
+
+#[test]
+fn end_of_the_range() {
+  let leaves = usize::MAX / 2;
+  let size = mmr_size(leaves);
+  assert_eq!(size, usize::MAX - 1 - 63);
+  assert_eq!(peaks(leaves).len(), 63);
+  assert_eq!(peaks(leaves).last(), Some(&(size - 1)));
+  assert_eq!(mmr_leaves(size), Some(leaves));
+  assert_eq!(mmr_leaves(usize::MAX), None);
+}
+
+#[test]
+#[should_panic(expected = "more nodes than fit in a usize")]
+fn peaks_too_many_leaves() {
+  peaks(usize::MAX);
+}
+
+#[test]
+#[should_panic(expected = "more nodes than fit in a usize")]
+fn mmr_size_too_many_leaves() {
+  mmr_size(usize::MAX / 2 + 1);
+}
+
+#[test]
+fn mmr_size_round_trips() {
+  let mut sizes = Vec::new();
+  for leaves in 0..130 {
+    let size = mmr_size(leaves);
+    assert_eq!(size, flat_tree::postorder(leaves).count());
+    assert_eq!(mmr_leaves(size), Some(leaves));
+    sizes.push(size);
+  }
+  for size in 0..*sizes.last().unwrap() {
+    assert_eq!(mmr_leaves(size).is_some(), sizes.contains(&size));
+  }
+}
@@ -0,0 +1,15 @@
+extern crate flat_tree;
+
+use flat_tree::NodeIndex;
+
+#[test]
+fn node_index() {
+  let node = NodeIndex::leaf(3);
+  assert_eq!(node, NodeIndex(6));
+  assert_eq!(node.index(), 6);
+  assert_eq!(node.offset(), 3);
+  assert_eq!(node.sibling(), NodeIndex(4));
+  assert_eq!(NodeIndex(5).children(), Some((NodeIndex(4), node)));
+  assert_eq!(NodeIndex(5).spans(), (NodeIndex(4), node));
+  assert_eq!(node.to_string(), "6");
+}
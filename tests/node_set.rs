@@ -0,0 +1,27 @@
+extern crate flat_tree;
+
+use flat_tree::NodeSet;
+
+#[test]
+fn insert_and_remove() {
+  let mut set = NodeSet::new();
+  assert!(set.is_empty());
+  assert!(set.insert(7));
+  assert!(set.insert(1));
+  assert!(!set.insert(7));
+  assert_eq!(set.len(), 2);
+  assert_eq!(set.as_slice(), [1, 7]);
+  assert!(set.remove(1));
+  assert!(!set.remove(1));
+  assert!(!set.contains(1));
+  assert!(set.contains(7));
+}
+
+#[test]
+fn collect_and_extend() {
+  let mut set: NodeSet = vec![5, 1, 5, 3].into_iter().collect();
+  assert_eq!(set.as_slice(), [1, 3, 5]);
+  set.extend(vec![0, 3]);
+  assert_eq!((&set).into_iter().collect::<Vec<_>>(), [0, 1, 3, 5]);
+  assert_eq!(set.into_iter().rev().collect::<Vec<_>>(), [5, 3, 1, 0]);
+}
@@ -0,0 +1,77 @@
+extern crate flat_tree;
+
+use flat_tree::{
+  Arena, DenseTree, NodeIndex, NodeStore, NodeStoreMut, SparseTree,
+};
+
+fn double_leaves<S: NodeStoreMut<Value = u32>>(store: &mut S, leaves: usize) {
+  for leaf in 0..leaves {
+    if let Some(value) = store.node_mut(NodeIndex::leaf(leaf)) {
+      *value *= 2;
+    }
+  }
+}
+
+fn leaves<S: NodeStore<Value = u32> + ?Sized>(store: &S) -> Vec<u32> {
+  (0..4)
+    .filter_map(|leaf| store.node(NodeIndex::leaf(leaf)).cloned())
+    .collect()
+}
+
+#[test]
+fn node_store_impls_agree() {
+  let values: Vec<u32> = (0..7).collect();
+  let mut vec = values.clone();
+  let mut dense: DenseTree<u32> = values.iter().cloned().enumerate().collect();
+  let mut sparse: SparseTree<u32> =
+    values.iter().cloned().enumerate().collect();
+  let mut arena: Arena<u32> = values
+    .iter()
+    .enumerate()
+    .map(|(i, value)| (NodeIndex(i), *value))
+    .collect();
+
+  double_leaves(&mut vec, 4);
+  double_leaves(&mut dense, 4);
+  double_leaves(&mut sparse, 4);
+  double_leaves(&mut arena, 4);
+  double_leaves(&mut &mut vec[..], 2);
+
+  assert_eq!(leaves(&vec[..]), [0, 8, 8, 12]);
+  assert_eq!(leaves(&dense), [0, 4, 8, 12]);
+  assert_eq!(leaves(&sparse), [0, 4, 8, 12]);
+  assert_eq!(leaves(&arena), [0, 4, 8, 12]);
+  assert_eq!(leaves(&dense.snapshot()), [0, 4, 8, 12]);
+  assert!(!dense.contains_node(NodeIndex(7)));
+}
+
+#[test]
+fn index_by_node() {
+  let mut vec: Vec<u32> = (1..4).collect();
+  vec[NodeIndex(1)] = 5;
+  assert_eq!(vec[NodeIndex(1)], 5);
+  assert_eq!(vec[..][NodeIndex(2)], 3);
+
+  let mut dense = DenseTree::new();
+  dense.set(3, 'a');
+  dense[NodeIndex(3)] = 'b';
+  assert_eq!(dense[NodeIndex(3)], 'b');
+  assert_eq!(dense.snapshot()[NodeIndex(3)], 'b');
+
+  let mut sparse = SparseTree::new();
+  sparse.set(1 << 50, 1);
+  sparse[NodeIndex(1 << 50)] += 1;
+  assert_eq!(sparse[NodeIndex(1 << 50)], 2);
+
+  let mut arena = Arena::new();
+  arena.insert(NodeIndex(9), "x");
+  arena[NodeIndex(9)] = "y";
+  assert_eq!(arena[NodeIndex(9)], "y");
+}
+
+#[test]
+#[should_panic]
+fn index_missing_node() {
+  let dense: DenseTree<u8> = DenseTree::new();
+  let _ = dense[NodeIndex(0)];
+}
@@ -0,0 +1,274 @@
+extern crate flat_tree;
+
+use flat_tree::{
+  from_fenwick, from_level_order, from_postorder, from_preorder, from_utreexo,
+  from_veb, to_fenwick, to_level_order, to_postorder, to_preorder, to_utreexo,
+  to_veb, utreexo_rows,
+};
+
+#[test]
+fn level_order_round_trips() {
+  for height in 1..8 {
+    let leaves = 1 << (height - 1);
+    let nodes: Vec<_> = flat_tree::inorder(leaves).collect();
+    let mut numbers: Vec<_> = nodes
+      .iter()
+      .map(|&i| to_level_order(i, height).unwrap())
+      .collect();
+    for (&i, &k) in nodes.iter().zip(&numbers) {
+      assert_eq!(from_level_order(k, height), Some(i));
+      if let Some((left, right)) = flat_tree::children(i) {
+        assert_eq!(to_level_order(left, height), Some(2 * k));
+        assert_eq!(to_level_order(right, height), Some(2 * k + 1));
+      }
+    }
+    numbers.sort();
+    assert_eq!(numbers, (1..nodes.len() + 1).collect::<Vec<_>>());
+    assert_eq!(from_level_order(nodes.len() + 1, height), None);
+  }
+}
+
+#[test]
+fn level_order_out_of_tree() {
+  assert_eq!(from_level_order(0, 3), None);
+  assert_eq!(to_level_order(15, 3), None);
+  assert_eq!(to_level_order(11, 3), None);
+  assert_eq!(to_level_order(0, 0), None);
+}
+
+#[test]
+fn level_order_too_tall() {
+  assert_eq!(to_level_order(0, 100), None);
+  assert_eq!(to_level_order(flat_tree::index(62, 0), 64), Some(2));
+  assert_eq!(to_level_order((1 << 63) - 1, 64), None);
+  assert_eq!(from_level_order(2, 64), Some(flat_tree::index(62, 0)));
+  assert_eq!(from_level_order(1, 64), None);
+  assert_eq!(from_level_order(1, 65), None);
+  assert_eq!(from_level_order(1, 100), None);
+  assert_eq!(from_level_order(3, 65), None);
+}
+
+#[test]
+fn postorder_round_trips() {
+  for leaves in 0..70 {
+    let nodes: Vec<_> = flat_tree::postorder(leaves).collect();
+    for (p, &i) in nodes.iter().enumerate() {
+      assert_eq!(to_postorder(i, leaves), Some(p));
+      assert_eq!(from_postorder(p, leaves), Some(i));
+    }
+    assert_eq!(from_postorder(nodes.len(), leaves), None);
+  }
+}
+
+#[test]
+fn postorder_matches_tree_builder() {
+  let mut builder = flat_tree::TreeBuilder::new(|_, _: &(), _: &()| ());
+  let mut p = 0;
+  for leaf in 0..100 {
+    for (i, _) in builder.push(()) {
+      assert_eq!(to_postorder(i, leaf + 1), Some(p));
+      p += 1;
+    }
+  }
+}
+
+/// Pre-order over the left-balanced tree, built the way bao builds it.
+fn bao_preorder(start: usize, end: usize, nodes: &mut Vec<usize>) {
+  if end - start == 1 {
+    nodes.push(2 * start);
+    return;
+  }
+  let mut split = 1;
+  while split * 2 < end - start {
+    split *= 2;
+  }
+  nodes.push(2 * (start + split) - 1);
+  bao_preorder(start, start + split, nodes);
+  bao_preorder(start + split, end, nodes);
+}
+
+#[test]
+fn preorder_matches_bao() {
+  assert_eq!(to_preorder(0, 0), None);
+  assert_eq!(from_preorder(0, 0), None);
+  for leaves in 1..70 {
+    let mut nodes = Vec::new();
+    bao_preorder(0, leaves, &mut nodes);
+    assert_eq!(nodes.len(), 2 * leaves - 1);
+    for (p, &i) in nodes.iter().enumerate() {
+      assert_eq!(to_preorder(i, leaves), Some(p), "{} in {}", i, leaves);
+      assert_eq!(from_preorder(p, leaves), Some(i), "{} in {}", p, leaves);
+    }
+  }
+}
+
+#[test]
+fn preorder_at_the_end_of_the_range() {
+  // The largest tree whose root isn't deeper than the cursor allows.
+  let leaves = 1 << 62;
+  for &i in &[0, (1 << 62) - 1, (1 << 63) - 2] {
+    let p = to_preorder(i, leaves).unwrap();
+    assert_eq!(from_preorder(p, leaves), Some(i));
+  }
+  assert_eq!(to_preorder(1 << 63, leaves), None);
+
+  // The index after the last node doesn't fit in a `usize`.
+  assert_eq!(to_preorder(0, usize::MAX / 2 + 1), None);
+  assert_eq!(from_preorder(0, usize::MAX / 2 + 1), None);
+  assert_eq!(to_preorder(0, usize::MAX), None);
+}
+
+#[test]
+fn preorder_of_full_tree() {
+  let nodes: Vec<_> = flat_tree::preorder(16).collect();
+  for (p, &i) in nodes.iter().enumerate() {
+    assert_eq!(to_preorder(i, 16), Some(p));
+  }
+}
+
+#[test]
+fn fenwick_round_trips() {
+  for leaves in 1..130 {
+    let mut positions = Vec::new();
+    for i in flat_tree::inorder(leaves) {
+      let j = match to_fenwick(i) {
+        Some(j) => j,
+        None => {
+          assert_eq!(flat_tree::offset(i) % 2, 1);
+          continue;
+        }
+      };
+      assert_eq!(from_fenwick(j), Some(i));
+      let (left, right) = flat_tree::spans(i);
+      assert_eq!((right - left) / 2 + 1, j & j.wrapping_neg());
+      assert_eq!(right / 2, j - 1);
+      positions.push(j);
+    }
+    positions.sort();
+    assert_eq!(positions, (1..=leaves).collect::<Vec<_>>());
+  }
+}
+
+#[test]
+fn fenwick_at_the_end_of_the_range() {
+  assert_eq!(from_fenwick(usize::MAX), None);
+  assert_eq!(from_fenwick(usize::MAX - 2), None);
+  assert_eq!(from_fenwick(usize::MAX >> 1), Some(usize::MAX - 3));
+  assert_eq!(to_fenwick(usize::MAX - 3), Some(usize::MAX >> 1));
+  assert_eq!(from_fenwick(1 << 63), Some(usize::MAX >> 1));
+  assert_eq!(from_fenwick(3 << 62), None);
+}
+
+#[test]
+fn fenwick_prefix_query_visits_full_roots() {
+  for n in 1..130usize {
+    let mut visited = Vec::new();
+    let mut j = n;
+    while j > 0 {
+      visited.push(from_fenwick(j).unwrap());
+      j -= j & j.wrapping_neg();
+    }
+    visited.reverse();
+    let mut roots = Vec::new();
+    flat_tree::full_roots(2 * n, &mut roots);
+    assert_eq!(visited, roots);
+  }
+}
+
+#[test]
+fn veb_round_trips() {
+  for tree_height in 1..11 {
+    let leaves = 1 << (tree_height - 1);
+    for block_height in 1..6 {
+      let mut positions: Vec<_> = flat_tree::inorder(leaves)
+        .map(|i| {
+          let p = to_veb(i, tree_height, block_height).unwrap();
+          assert_eq!(from_veb(p, tree_height, block_height), Some(i));
+          p
+        })
+        .collect();
+      positions.sort();
+      assert_eq!(positions, (0..2 * leaves - 1).collect::<Vec<_>>());
+      assert_eq!(from_veb(2 * leaves - 1, tree_height, block_height), None);
+    }
+  }
+}
+
+#[test]
+fn veb_with_one_block_is_level_order() {
+  for i in flat_tree::inorder(16) {
+    assert_eq!(to_veb(i, 5, 5), to_level_order(i, 5).map(|k| k - 1));
+  }
+}
+
+#[test]
+fn veb_blocks_are_contiguous() {
+  // With 8 levels and blocks of 2 levels, every node is in the block of the
+  // 3 nodes under the node at an odd depth above it, or itself.
+  for i in flat_tree::inorder(128) {
+    let block_root = if flat_tree::depth(i) % 2 == 1 {
+      i
+    } else {
+      flat_tree::parent(i)
+    };
+    let first = to_veb(block_root, 8, 2).unwrap();
+    let p = to_veb(i, 8, 2).unwrap();
+    assert!(p >= first && p < first + 3);
+  }
+}
+
+#[test]
+fn veb_out_of_tree() {
+  assert_eq!(to_veb(31, 5, 2), None);
+  assert_eq!(to_veb(32, 5, 2), None);
+  assert_eq!(to_veb(0, 64, 2), None);
+}
+
+#[test]
+#[should_panic]
+fn veb_zero_block_height() {
+  to_veb(0, 3, 0);
+}
+
+/// Utreexo's parent of `pos` in a forest with `rows` rows.
+fn utreexo_parent(pos: usize, rows: usize) -> usize {
+  (pos >> 1) | (1 << rows)
+}
+
+#[test]
+fn utreexo_round_trips() {
+  for leaves in 1..130 {
+    let rows = utreexo_rows(leaves);
+    let mut positions = Vec::new();
+    for i in flat_tree::inorder(leaves) {
+      let pos = to_utreexo(i, leaves).unwrap();
+      assert_eq!(from_utreexo(pos, leaves), Some(i));
+      if flat_tree::depth(i) == 0 {
+        assert_eq!(pos, i / 2);
+      }
+      let parent = flat_tree::parent(i);
+      if let Some(parent_pos) = to_utreexo(parent, leaves) {
+        assert_eq!(utreexo_parent(pos, rows), parent_pos);
+      }
+      positions.push(pos);
+    }
+    positions.sort();
+    positions.dedup();
+    assert_eq!(positions.len(), flat_tree::inorder(leaves).count());
+    for pos in 0..(2 << rows) {
+      if !positions.contains(&pos) {
+        assert_eq!(from_utreexo(pos, leaves), None);
+      }
+    }
+  }
+}
+
+#[test]
+fn utreexo_rows_fit_leaves() {
+  assert_eq!(utreexo_rows(0), 0);
+  for leaves in 1..130 {
+    let rows = utreexo_rows(leaves);
+    assert!(1 << rows >= leaves);
+    assert!(rows == 0 || 1 << (rows - 1) < leaves);
+  }
+}
@@ -0,0 +1,40 @@
+extern crate flat_tree;
+
+use flat_tree::{DenseTree, NodeIndex, NodeStore, Overlay};
+
+fn base() -> DenseTree<&'static str> {
+  vec![(0, "a"), (2, "b"), (1, "ab")].into_iter().collect()
+}
+
+#[test]
+fn overlay_reads_pending_writes_first() {
+  let mut tree = base();
+  let mut overlay = Overlay::new(&mut tree);
+  assert_eq!(overlay.set(2, "c"), Some("b"));
+  assert_eq!(overlay.remove(1), Some("ab"));
+  assert_eq!(overlay.remove(1), None);
+  assert_eq!(overlay.set(9, "z"), None);
+  assert_eq!(overlay.pending(), 3);
+
+  assert_eq!(overlay.get(0), Some(&"a"));
+  assert_eq!(overlay.get(2), Some(&"c"));
+  assert!(!overlay.contains(1));
+  assert_eq!(overlay.node(NodeIndex(9)), Some(&"z"));
+  assert_eq!(overlay.base().get(2), Some(&"b"));
+  overlay.commit();
+
+  assert_eq!(tree.get(2), Some(&"c"));
+  assert_eq!(tree.get(1), None);
+  assert_eq!(tree.get(9), Some(&"z"));
+}
+
+#[test]
+fn overlay_rollback() {
+  let mut tree = base();
+  let mut overlay = Overlay::new(&mut tree);
+  overlay.set(0, "x");
+  overlay.remove(2);
+  overlay.set(20, "y");
+  overlay.rollback();
+  assert_eq!(tree, base());
+}
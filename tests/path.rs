@@ -0,0 +1,68 @@
+extern crate flat_tree;
+
+use flat_tree::{apply_ops, apply_path, Op, PathError};
+
+const OPS: [Op; 7] = [
+  Op::Parent,
+  Op::Sibling,
+  Op::Uncle,
+  Op::LeftChild,
+  Op::RightChild,
+  Op::LeftSpan,
+  Op::RightSpan,
+];
+
+#[test]
+fn matches_the_index_functions() {
+  for i in 0..1024 {
+    assert_eq!(Op::Parent.apply(i), Ok(flat_tree::parent(i)));
+    assert_eq!(Op::Sibling.apply(i), Ok(flat_tree::sibling(i)));
+    assert_eq!(Op::Uncle.apply(i), Ok(flat_tree::uncle(i)));
+    assert_eq!(Op::LeftSpan.apply(i), Ok(flat_tree::left_span(i)));
+    assert_eq!(Op::RightSpan.apply(i), Ok(flat_tree::right_span(i)));
+  }
+}
+
+#[test]
+fn too_deep() {
+  let max_depth = flat_tree::Iterator::MAX_DEPTH;
+  for &i in &[usize::MAX, (1 << 63) - 1] {
+    for &op in &OPS {
+      assert_eq!(op.apply(i), Err(PathError::OutOfRange(i)), "{}", op);
+    }
+  }
+  assert_eq!(
+    apply_path(usize::MAX, "sibling"),
+    Err(PathError::OutOfRange(usize::MAX))
+  );
+  assert_eq!(
+    apply_ops((1 << 63) - 1, &[Op::Parent]),
+    Err(PathError::OutOfRange((1 << 63) - 1))
+  );
+
+  // The deepest node has no parent or uncle, but every other move works.
+  let root = flat_tree::index(max_depth, 0);
+  assert_eq!(Op::Parent.apply(root), Err(PathError::OutOfRange(root)));
+  assert_eq!(Op::Uncle.apply(root), Err(PathError::OutOfRange(root)));
+  assert_eq!(apply_ops(root, &[Op::Sibling, Op::Sibling]), Ok(root));
+  assert_eq!(Op::LeftSpan.apply(root), Ok(0));
+  assert!(Op::LeftChild.apply(root).is_ok());
+  let parent = flat_tree::index(max_depth - 1, 0);
+  assert_eq!(Op::Parent.apply(parent), Ok(root));
+  assert_eq!(
+    PathError::OutOfRange(usize::MAX).to_string(),
+    format!("node {} is too deep to move from", usize::MAX)
+  );
+}
+
+#[test]
+fn end_of_the_range() {
+  let last = usize::MAX - 1;
+  let parent = flat_tree::parent(last);
+  assert_eq!(parent, usize::MAX - 2);
+  assert_eq!(Op::RightSpan.apply(parent), Ok(last));
+  assert_eq!(Op::LeftSpan.apply(parent), Ok(last - 2));
+  assert_eq!(apply_path(last, "sibling,parent,right_child"), Ok(last));
+  let root = flat_tree::index(flat_tree::Iterator::MAX_DEPTH, 1);
+  assert_eq!(Op::RightSpan.apply(root), Ok(last));
+}
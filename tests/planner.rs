@@ -0,0 +1,81 @@
+extern crate flat_tree;
+
+use flat_tree::{NodeSet, Planner, Request, Strategy, TreeIndex};
+
+fn tree(nodes: &[usize]) -> TreeIndex {
+  let mut tree = TreeIndex::new();
+  for node in nodes {
+    tree.set(*node);
+  }
+  tree
+}
+
+fn nodes(requests: &[Request]) -> Vec<usize> {
+  requests.iter().map(|request| request.node).collect()
+}
+
+#[test]
+fn prefix_first() {
+  let remote = tree(&[0, 2, 4, 6, 8, 12, 20]);
+  let requests = Planner::new().plan(&tree(&[]), &remote, &NodeSet::new());
+  assert_eq!(
+    requests,
+    [
+      Request {
+        node: 3,
+        priority: 0
+      },
+      Request {
+        node: 8,
+        priority: 4
+      },
+      Request {
+        node: 12,
+        priority: 6
+      },
+      Request {
+        node: 20,
+        priority: 10
+      },
+    ]
+  );
+}
+
+#[test]
+fn rarest_first() {
+  let remote = tree(&[0, 4, 8, 12]);
+  let peers = |node| match node {
+    12 => 1,
+    4 => 1,
+    _ => 2,
+  };
+  let requests = Planner::new().strategy(Strategy::RarestFirst(&peers)).plan(
+    &tree(&[]),
+    &remote,
+    &NodeSet::new(),
+  );
+  assert_eq!(nodes(&requests), [4, 12, 0, 8]);
+  assert_eq!(requests[0].priority, 1);
+  assert_eq!(requests[3].priority, 2);
+}
+
+#[test]
+fn max_outstanding() {
+  let remote = tree(&[0, 4, 8, 12]);
+  let planner = Planner::new().max_outstanding(2);
+  let none = NodeSet::new();
+  assert_eq!(nodes(&planner.plan(&tree(&[]), &remote, &none)), [0, 4]);
+
+  let in_flight: NodeSet = vec![0].into_iter().collect();
+  assert_eq!(nodes(&planner.plan(&tree(&[]), &remote, &in_flight)), [4]);
+
+  let in_flight: NodeSet = vec![0, 4, 100].into_iter().collect();
+  assert!(planner.plan(&tree(&[]), &remote, &in_flight).is_empty());
+}
+
+#[test]
+fn skips_local_nodes() {
+  let remote = tree(&[0, 2, 4, 6]);
+  let requests = Planner::new().plan(&tree(&[2, 4]), &remote, &NodeSet::new());
+  assert_eq!(nodes(&requests), [0, 6]);
+}
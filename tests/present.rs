@@ -0,0 +1,47 @@
+extern crate flat_tree;
+
+use flat_tree::TreeIndex;
+
+#[test]
+fn present_and_missing_leaves_partition() {
+  let mut tree = TreeIndex::new();
+  for leaf in &[0, 2, 6, 12, 14, 30] {
+    tree.set(*leaf);
+  }
+  let present: Vec<_> = tree.present_leaves().collect();
+  assert_eq!(present, [0, 2, 6, 12, 14, 30]);
+
+  let missing: Vec<_> = tree.missing_leaves().collect();
+  let expected: Vec<_> = (0..tree.len())
+    .step_by(2)
+    .filter(|leaf| !present.contains(leaf))
+    .collect();
+  assert_eq!(missing, expected);
+}
+
+#[test]
+fn present_leaves_ignore_parents() {
+  let mut tree = TreeIndex::new();
+  tree.set(3);
+  assert_eq!(tree.present_leaves().count(), 0);
+}
+
+#[test]
+fn leaves_range() {
+  let mut tree = TreeIndex::new();
+  for leaf in &[0, 2, 6, 12] {
+    tree.set(*leaf);
+  }
+  let present: Vec<_> = tree.present_leaves().range(1..12).collect();
+  assert_eq!(present, [2, 6]);
+  let missing: Vec<_> = tree.missing_leaves().range(4..20).collect();
+  assert_eq!(missing, [4, 8, 10, 14, 16, 18]);
+  assert_eq!(tree.missing_leaves().range(8..8).count(), 0);
+}
+
+#[test]
+fn leaves_of_empty_tree() {
+  let tree = TreeIndex::new();
+  assert_eq!(tree.present_leaves().next(), None);
+  assert_eq!(tree.missing_leaves().next(), None);
+}
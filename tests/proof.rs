@@ -0,0 +1,106 @@
+extern crate flat_tree;
+
+use flat_tree::{
+  audit_path, multiproof, needed_nodes, verify_proof, NodeSet, Verifier,
+  VerifyError,
+};
+
+#[test]
+fn multiproof_single_node_is_audit_path() {
+  for leaves in 1..20 {
+    for node in 0..2 * leaves {
+      let mut path: Vec<usize> = audit_path(node, leaves).collect();
+      path.sort_unstable();
+      assert_eq!(multiproof(&[node], leaves).as_slice(), &path[..]);
+    }
+  }
+}
+
+#[test]
+fn multiproof_shares_nodes() {
+  assert!(multiproof(&[], 8).is_empty());
+  assert!(multiproof(&[0, 2, 4, 6], 4).is_empty());
+  assert_eq!(multiproof(&[0, 2], 8).as_slice(), [5, 11]);
+  assert_eq!(multiproof(&[0, 8], 8).as_slice(), [2, 5, 10, 13]);
+  assert_eq!(multiproof(&[0, 1], 8).as_slice(), [2, 5, 11]);
+  assert_eq!(multiproof(&[0, 8], 5).as_slice(), [2, 5]);
+  assert_eq!(multiproof(&[4, 100], 4).as_slice(), [1, 6]);
+}
+
+#[test]
+fn needed_nodes_against_roots() {
+  let trusted: NodeSet = vec![7].into_iter().collect();
+  for leaf in 0..8 {
+    let path: Vec<usize> = audit_path(2 * leaf, 8).collect();
+    assert_eq!(needed_nodes(2 * leaf, &trusted), Some(path));
+  }
+  assert_eq!(needed_nodes(7, &trusted), Some(vec![]));
+  assert_eq!(needed_nodes(16, &trusted), None);
+}
+
+#[test]
+fn needed_nodes_against_subtrees() {
+  let trusted: NodeSet = vec![1, 11].into_iter().collect();
+  assert_eq!(needed_nodes(0, &trusted), Some(vec![2]));
+  assert_eq!(needed_nodes(4, &trusted), None);
+  assert_eq!(needed_nodes(12, &trusted), Some(vec![14, 9]));
+
+  let trusted: NodeSet = vec![2, 5, 7].into_iter().collect();
+  assert_eq!(needed_nodes(0, &trusted), Some(vec![11]));
+  assert_eq!(needed_nodes(1, &trusted), Some(vec![11]));
+
+  assert_eq!(needed_nodes(0, &NodeSet::new()), None);
+}
+
+#[test]
+fn verifier_tracks_positions() {
+  let combine = |parent: usize, left: &Vec<usize>, right: &Vec<usize>| {
+    let mut nodes = left.clone();
+    nodes.push(parent);
+    nodes.extend(right);
+    nodes
+  };
+  let mut verifier = Verifier::new(4, vec![4]);
+  assert_eq!(verifier.index(), 4);
+  assert_eq!(verifier.push(6, vec![6], combine), Ok(5));
+  assert_eq!(verifier.push(1, vec![0, 1, 2], combine), Ok(3));
+  assert_eq!(verifier.value(), &[0, 1, 2, 3, 4, 5, 6]);
+  assert_eq!(
+    verifier.push(3, vec![], combine),
+    Err(VerifyError::NotSibling {
+      expected: 11,
+      got: 3
+    })
+  );
+  assert_eq!(verifier.index(), 3);
+  assert_eq!(verifier.finish(3).unwrap().len(), 7);
+}
+
+#[test]
+fn verify_audit_paths() {
+  let sum = |_: usize, left: &usize, right: &usize| left + right;
+  for leaves in 1..17 {
+    let mut roots = Vec::new();
+    flat_tree::full_roots(2 * leaves, &mut roots);
+    for leaf in 0..leaves {
+      let node = 2 * leaf;
+      let root = *roots
+        .iter()
+        .find(|&&root| flat_tree::spans(root).1 >= node)
+        .unwrap();
+      let proof =
+        audit_path(node, leaves).map(|n| (n, flat_tree::count(n) / 2 + 1));
+      let total = verify_proof(node, 1, proof, root, sum).unwrap();
+      assert_eq!(total, flat_tree::count(root) / 2 + 1);
+    }
+  }
+}
+
+#[test]
+fn verify_error_display() {
+  let error = VerifyError::NotSibling {
+    expected: 2,
+    got: 4,
+  };
+  assert_eq!(error.to_string(), "expected sibling 2, got node 4");
+}
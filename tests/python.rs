@@ -0,0 +1,106 @@
+#![cfg(feature = "python")]
+
+extern crate flat_tree;
+extern crate pyo3;
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::ffi::CString;
+
+fn run(code: &str) {
+  Python::initialize();
+  Python::attach(|py| {
+    let module = pyo3::wrap_pymodule!(flat_tree::python::flat_tree)(py);
+    let globals = PyDict::new(py);
+    globals.set_item("flat_tree", module).unwrap();
+    let code = CString::new(code).unwrap();
+    if let Err(err) = py.run(&code, Some(&globals), None) {
+      err.print(py);
+      panic!("python code failed");
+    }
+  });
+}
+
+#[test]
+fn index_functions() {
+  run(
+    r#"
+assert flat_tree.index(1, 1) == 5
+assert flat_tree.depth(5) == 1
+assert flat_tree.offset(5) == 1
+assert flat_tree.parent(0) == 1
+assert flat_tree.sibling(0) == 2
+assert flat_tree.uncle(0) == 5
+assert flat_tree.children(0) is None
+assert flat_tree.children(3) == (1, 5)
+assert flat_tree.left_span(3) == 0
+assert flat_tree.right_span(3) == 6
+assert flat_tree.spans(3) == (0, 6)
+assert flat_tree.count(3) == 7
+assert flat_tree.full_roots(14) == [3, 9, 12]
+try:
+  flat_tree.full_roots(1)
+  assert False
+except ValueError:
+  pass
+"#,
+  );
+}
+
+#[test]
+fn batch_functions() {
+  run(
+    r#"
+nodes = list(range(64))
+assert flat_tree.parents(nodes) == [flat_tree.parent(i) for i in nodes]
+assert flat_tree.siblings(nodes) == [flat_tree.sibling(i) for i in nodes]
+assert flat_tree.depths(nodes) == [flat_tree.depth(i) for i in nodes]
+assert flat_tree.offsets(nodes) == [flat_tree.offset(i) for i in nodes]
+assert flat_tree.left_spans(nodes) == [flat_tree.left_span(i) for i in nodes]
+assert flat_tree.right_spans(nodes) == [flat_tree.right_span(i) for i in nodes]
+pairs = list(zip(flat_tree.depths(nodes), flat_tree.offsets(nodes)))
+assert flat_tree.indices(pairs) == nodes
+"#,
+  );
+}
+
+#[test]
+fn iterator() {
+  run(
+    r#"
+it = flat_tree.Iterator(0)
+assert repr(it) == "Iterator(0)"
+assert it.next() == 2
+assert it.prev() == 0
+assert it.prev() is None
+assert it.parent() == 1
+assert it.depth() == 1
+assert it.factor() == 4
+assert it.right_child() == 2
+assert it.is_right()
+it.seek(3)
+assert it.contains(6)
+assert it.left_child() == 1
+assert it.left_child() == 0
+assert it.left_child() is None
+assert flat_tree.Iterator().index() == 0
+"#,
+  );
+}
+
+#[test]
+fn tree_index() {
+  run(
+    r#"
+tree = flat_tree.TreeIndex()
+assert len(tree) == 0
+assert tree.set_many([0, 2, 2]) == 2
+assert tree.get(1)
+assert tree.get_many([0, 1, 4]) == [True, True, False]
+assert tree.blocks() == 2
+assert tree.roots() == [1]
+assert tree.proof(4) is None
+assert tree.proof(0) == ([2], 4)
+"#,
+  );
+}
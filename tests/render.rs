@@ -0,0 +1,161 @@
+extern crate flat_tree;
+
+use flat_tree::{
+  render_ascii, render_unicode, Highlights, NodeSet, Orientation, RenderOptions,
+};
+
+#[test]
+fn ascii_matches_the_readme() {
+  assert_eq!(
+    render_ascii(4),
+    concat!("      3\n", "  1       5\n", "0   2   4   6\n")
+  );
+}
+
+#[test]
+fn ascii_forests() {
+  assert_eq!(render_ascii(1), "0\n");
+  assert_eq!(
+    render_ascii(7),
+    concat!(
+      "         3\n",
+      "   1           5           9\n",
+      "0     2     4     6     8     10    12\n",
+    )
+  );
+}
+
+#[test]
+fn ascii_parents_sit_between_their_children() {
+  let lines: Vec<String> = render_ascii(64).lines().map(String::from).collect();
+  assert_eq!(lines.len(), 7);
+  let column = |i: usize| {
+    let line = &lines[6 - flat_tree::depth(i)];
+    let text = format!(" {} ", i);
+    format!(" {} ", line).find(&text).unwrap()
+  };
+  for i in (1..127).step_by(2) {
+    let (left, right) = flat_tree::children(i).unwrap();
+    assert!(column(left) < column(i) && column(i) < column(right));
+  }
+}
+
+#[test]
+fn unicode_top_down() {
+  assert_eq!(render_unicode(0, RenderOptions::new()), "");
+  assert_eq!(render_unicode(1, RenderOptions::new()), "0\n");
+  assert_eq!(
+    render_unicode(7, RenderOptions::new()),
+    concat!(
+      "   ┌─────3─────┐\n",
+      "┌──1──┐     ┌──5──┐     ┌──9──┐\n",
+      "0     2     4     6     8     10    12\n",
+    )
+  );
+}
+
+#[test]
+fn unicode_top_down_elides_deep_subtrees() {
+  assert_eq!(
+    render_unicode(7, RenderOptions::new().min_depth(1)),
+    concat!("┌──3──┐\n", "1     5     9     12\n", "…     …     …\n")
+  );
+  assert_eq!(
+    render_unicode(7, RenderOptions::new().min_depth(100)),
+    concat!("3     9     12\n", "…     …\n")
+  );
+}
+
+#[test]
+fn unicode_top_down_fits_max_width() {
+  for &max_width in &[1, 20, 40, 80, 120] {
+    let rendered =
+      render_unicode(1000, RenderOptions::new().max_width(max_width));
+    let longest = rendered.lines().map(|line| line.chars().count()).max();
+    let roots = render_unicode(1000, RenderOptions::new().min_depth(64));
+    assert!(longest <= Some(max_width) || rendered == roots);
+  }
+  assert_eq!(
+    render_unicode(16, RenderOptions::new().max_width(21)),
+    concat!(
+      "   ┌─────15────┐\n",
+      "┌──7──┐     ┌──23─┐\n",
+      "3     11    19    27\n",
+      "…     …     …     …\n"
+    )
+  );
+}
+
+#[test]
+fn unicode_left_right() {
+  let opts = RenderOptions::new().orientation(Orientation::LeftRight);
+  assert_eq!(
+    render_unicode(5, opts),
+    concat!(
+      "3\n",
+      "├── 1\n",
+      "│   ├── 0\n",
+      "│   └── 2\n",
+      "└── 5\n",
+      "    ├── 4\n",
+      "    └── 6\n",
+      "8\n",
+    )
+  );
+  assert_eq!(
+    render_unicode(7, opts.min_depth(1)),
+    concat!("3\n", "├── 1 …\n", "└── 5 …\n", "9 …\n", "12\n")
+  );
+  assert_eq!(
+    render_unicode(4, opts.max_width(7)),
+    concat!(
+      "3\n",
+      "├── 1\n",
+      "│   ├─…\n",
+      "│   └─…\n",
+      "└── 5\n",
+      "    ├─…\n",
+      "    └─…\n",
+    )
+  );
+}
+
+#[test]
+fn unicode_highlights() {
+  let have: NodeSet = vec![0, 1].into_iter().collect();
+  let want: NodeSet = vec![1, 4].into_iter().collect();
+  let highlights = Highlights::new()
+    .set("have", &have)
+    .set("want", &want)
+    .annotate(|i| {
+      if i == 5 {
+        Some("proof".to_string())
+      } else {
+        None
+      }
+    });
+  let opts = RenderOptions::new().highlights(&highlights);
+  assert_eq!(
+    render_unicode(4, opts),
+    concat!(
+      "    ┌───────3───────┐\n",
+      "┌───1*+─┐       ┌───5───┐\n",
+      "0*      2       4+      6\n",
+      "\n",
+      "5: proof\n",
+      "* have\n",
+      "+ want\n",
+    )
+  );
+  assert_eq!(
+    render_unicode(4, opts.orientation(Orientation::LeftRight).min_depth(1)),
+    concat!(
+      "3\n",
+      "├── 1*+ …\n",
+      "└── 5 …  proof\n",
+      "\n",
+      "* have\n",
+      "+ want\n"
+    )
+  );
+}
@@ -0,0 +1,94 @@
+extern crate flat_tree;
+
+use flat_tree::{rle, DecodeError, TreeIndex};
+
+#[test]
+fn encode() {
+  assert!(rle::encode(&[]).is_empty());
+  assert_eq!(rle::encode(&[0]), [0b101]);
+  assert_eq!(rle::encode(&[0xff, 0xff]), [0b1011]);
+  assert_eq!(rle::encode(&[1, 2, 3]), [0b110, 1, 2, 3]);
+  assert_eq!(
+    rle::encode(&[1, 0, 0, 0, 0, 2]),
+    [0b10, 1, 0b10001, 0b10, 2]
+  );
+  assert_eq!(rle::encode(&[1, 0xff, 0xff, 2]), [0b1000, 1, 0xff, 0xff, 2]);
+}
+
+#[test]
+fn decode() {
+  assert_eq!(rle::decode(&[]), Ok(vec![]));
+  assert_eq!(
+    rle::decode(&[0b1001, 0b110, 1, 2, 3]),
+    Ok(vec![0, 0, 1, 2, 3])
+  );
+  assert_eq!(rle::decode(&[0b1011, 0b1001]), Ok(vec![0xff, 0xff, 0, 0]));
+  assert_eq!(rle::decode(&[0x80]), Err(DecodeError::Truncated));
+  assert_eq!(rle::decode(&[0b110, 1]), Err(DecodeError::Truncated));
+  assert_eq!(rle::decode(&[0x80; 11]), Err(DecodeError::Overflow));
+}
+
+#[test]
+fn decode_rejects_bits_past_the_end() {
+  // The 10th byte of a varint only has room for the top bit of a u64.
+  let mut varint = vec![0x80; 9];
+  varint.push(0x01);
+  assert_eq!(rle::decode(&varint), Err(DecodeError::Truncated));
+  varint.pop();
+  varint.push(0x02);
+  assert_eq!(rle::decode(&varint), Err(DecodeError::Overflow));
+
+  // An empty run, unless the dropped bit is rejected.
+  let mut varint = vec![0x81];
+  varint.extend_from_slice(&[0x80; 8]);
+  varint.push(0x02);
+  assert_eq!(rle::decode(&varint), Err(DecodeError::Overflow));
+}
+
+#[test]
+fn decode_rejects_huge_runs() {
+  assert_eq!(
+    rle::decode(&[0xfd, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x3f]),
+    Err(DecodeError::Overflow)
+  );
+  // A run of `MAX_DECODED_LEN + 1` zero bytes.
+  let run = [0x85, 0x80, 0x80, 0x80, 0x10];
+  assert_eq!(rle::decode(&run), Err(DecodeError::Overflow));
+  assert_eq!(
+    rle::decode_max(&[0b110, 1, 2, 3], 2),
+    Err(DecodeError::Overflow)
+  );
+  assert_eq!(rle::decode_max(&[0b110, 1, 2, 3], 3), Ok(vec![1, 2, 3]));
+
+  let mut bytes = b"FTIX\x01\x01".to_vec();
+  bytes.extend_from_slice(&8u64.to_le_bytes());
+  bytes.extend_from_slice(&[0xfd, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x3f]);
+  assert_eq!(TreeIndex::from_bytes(&bytes), Err(DecodeError::Overflow));
+}
+
+#[test]
+fn round_trip() {
+  let mut bits = vec![0u8; 1000];
+  for (i, byte) in bits.iter_mut().enumerate() {
+    *byte = match i % 100 {
+      0..=29 => 0xff,
+      30..=39 => (i % 7) as u8,
+      _ => 0,
+    };
+  }
+  let encoded = rle::encode(&bits);
+  assert!(encoded.len() < 200);
+  assert_eq!(rle::decode(&encoded), Ok(bits));
+}
+
+#[test]
+fn tree_index_compressed_bytes() {
+  let mut tree = TreeIndex::new();
+  for leaf in (0..500).chain(600..700) {
+    tree.set(leaf * 2);
+  }
+  let bytes = tree.to_compressed_bytes();
+  assert_eq!(bytes[5], 1);
+  assert!(bytes.len() < tree.to_bytes().len() / 2);
+  assert_eq!(TreeIndex::from_bytes(&bytes), Ok(tree));
+}
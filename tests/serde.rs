@@ -0,0 +1,83 @@
+#![cfg(feature = "serde")]
+
+extern crate flat_tree;
+extern crate serde_json;
+
+#[test]
+fn iterator_round_trip() {
+  let mut iterator = flat_tree::Iterator::new(0);
+  iterator.parent();
+  iterator.parent();
+  iterator.next();
+
+  let json = serde_json::to_string(&iterator).unwrap();
+  assert_eq!(json, r#"{"index":11,"offset":1,"factor":8}"#);
+  let restored: flat_tree::Iterator = serde_json::from_str(&json).unwrap();
+  assert_eq!(restored, iterator);
+}
+
+#[test]
+fn iterator_rejects_invalid_state() {
+  let json = r#"{"index":11,"offset":2,"factor":8}"#;
+  assert!(serde_json::from_str::<flat_tree::Iterator>(json).is_err());
+  let json = r#"{"index":11,"offset":1,"factor":4}"#;
+  assert!(serde_json::from_str::<flat_tree::Iterator>(json).is_err());
+}
+
+#[test]
+fn node_index_is_a_number() {
+  let json = serde_json::to_string(&flat_tree::NodeIndex(5)).unwrap();
+  assert_eq!(json, "5");
+  let node: flat_tree::NodeIndex = serde_json::from_str(&json).unwrap();
+  assert_eq!(node, flat_tree::NodeIndex(5));
+}
+
+#[test]
+fn coords_round_trip() {
+  let coords = flat_tree::Coords::new(23);
+  let json = serde_json::to_string(&coords).unwrap();
+  assert_eq!(json, r#"{"index":23,"depth":3,"offset":1}"#);
+  let restored: flat_tree::Coords = serde_json::from_str(&json).unwrap();
+  assert_eq!(restored, coords);
+}
+
+#[test]
+fn coords_rejects_invalid_state() {
+  let json = r#"{"index":23,"depth":2,"offset":1}"#;
+  assert!(serde_json::from_str::<flat_tree::Coords>(json).is_err());
+  let json = r#"{"index":23,"depth":3,"offset":0}"#;
+  assert!(serde_json::from_str::<flat_tree::Coords>(json).is_err());
+  let json = format!(r#"{{"index":{},"depth":64,"offset":0}}"#, usize::MAX);
+  assert!(serde_json::from_str::<flat_tree::Coords>(&json).is_err());
+}
+
+#[test]
+fn node_set_is_sorted() {
+  let set: flat_tree::NodeSet = vec![9, 0, 5].into_iter().collect();
+  let json = serde_json::to_string(&set).unwrap();
+  assert_eq!(json, "[0,5,9]");
+  let restored: flat_tree::NodeSet = serde_json::from_str("[9,5,0,5]").unwrap();
+  assert_eq!(restored, set);
+}
+
+#[test]
+fn frontier_is_its_leaves() {
+  let frontier = flat_tree::Frontier::with_leaves(7);
+  let json = serde_json::to_string(&frontier).unwrap();
+  assert_eq!(json, r#"{"leaves":7}"#);
+  let restored: flat_tree::Frontier = serde_json::from_str(&json).unwrap();
+  assert_eq!(restored, frontier);
+  assert_eq!(restored.roots(), [3, 9, 12]);
+}
+
+#[test]
+fn tree_index_is_its_bitfield() {
+  let mut tree = flat_tree::TreeIndex::new();
+  tree.set(0);
+  tree.set(2);
+  let json = serde_json::to_string(&tree).unwrap();
+  assert_eq!(json, "[224]");
+  let restored: flat_tree::TreeIndex = serde_json::from_str(&json).unwrap();
+  assert_eq!(restored, tree);
+  assert!(restored.get(1));
+}
@@ -0,0 +1,54 @@
+extern crate flat_tree;
+
+use flat_tree::{DenseTree, Order, SparseTree};
+
+#[test]
+fn sparse_tree_get_set() {
+  let mut tree = SparseTree::new();
+  assert!(tree.is_empty());
+  assert_eq!(tree.set(usize::MAX - 1, 'z'), None);
+  assert_eq!(tree.set(3, 'd'), None);
+  assert_eq!(tree.set(3, 'D'), Some('d'));
+  assert_eq!(tree.len(), 2);
+  assert!(tree.contains(3));
+
+  *tree.get_mut(3).unwrap() = 'x';
+  assert_eq!(tree.get(3), Some(&'x'));
+  assert_eq!(tree.remove(3), Some('x'));
+  assert_eq!(tree.remove(3), None);
+  assert_eq!(tree.take(), [(usize::MAX - 1, 'z')]);
+  assert!(tree.is_empty());
+}
+
+#[test]
+fn sparse_tree_matches_dense_tree() {
+  let values: Vec<(usize, usize)> = (0..63)
+    .filter(|i| i % 3 != 1)
+    .map(|i| (i, i * 10))
+    .collect();
+  let dense: DenseTree<_> = values.iter().cloned().collect();
+  let sparse: SparseTree<_> = values.iter().cloned().collect();
+
+  assert!(dense.iter().eq(sparse.iter()));
+  let orders = [
+    Order::InOrder,
+    Order::PreOrder,
+    Order::PostOrder,
+    Order::LevelOrder,
+  ];
+  for root in &[1, 3, 11, 15, 23, 31] {
+    for order in &orders {
+      assert!(
+        dense
+          .subtree(*root, *order)
+          .eq(sparse.subtree(*root, *order)),
+        "root {} in {:?}",
+        root,
+        order
+      );
+    }
+  }
+  for i in 0..70 {
+    assert!(dense.ancestors(i).eq(sparse.ancestors(i)), "node {}", i);
+  }
+}
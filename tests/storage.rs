@@ -0,0 +1,105 @@
+extern crate flat_tree;
+
+use flat_tree::{
+  assemble_proof, MissingNode, NodeStorage, Proof, ProofOptions, TreeIndex,
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::task::{Context, Poll, Waker};
+
+/// Storage whose lookups are pending once before resolving.
+struct SlowStorage {
+  nodes: HashMap<usize, String>,
+}
+
+struct SlowGet<'a> {
+  storage: &'a SlowStorage,
+  index: usize,
+  polled: bool,
+}
+
+impl<'a> Future for SlowGet<'a> {
+  type Output = Option<String>;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+    if !self.polled {
+      self.polled = true;
+      cx.waker().wake_by_ref();
+      return Poll::Pending;
+    }
+    Poll::Ready(self.storage.nodes.get(&self.index).cloned())
+  }
+}
+
+impl NodeStorage for SlowStorage {
+  type Node = String;
+  type Get<'a> = SlowGet<'a>;
+
+  fn get(&self, index: usize) -> Self::Get<'_> {
+    SlowGet {
+      storage: self,
+      index,
+      polled: false,
+    }
+  }
+}
+
+fn block_on<F: Future>(future: F) -> (F::Output, usize) {
+  let mut future = pin!(future);
+  let mut cx = Context::from_waker(Waker::noop());
+  let mut polls = 1;
+  loop {
+    if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+      return (output, polls);
+    }
+    polls += 1;
+  }
+}
+
+fn storage(nodes: &[usize]) -> SlowStorage {
+  SlowStorage {
+    nodes: nodes.iter().map(|&i| (i, format!("node {}", i))).collect(),
+  }
+}
+
+/// A tree with `leaves` leaves, all of them present.
+fn tree(leaves: usize) -> TreeIndex {
+  let mut tree = TreeIndex::new();
+  for leaf in 0..leaves {
+    tree.set(leaf * 2);
+  }
+  tree
+}
+
+#[test]
+fn assembles_planned_proof() {
+  let storage = storage(&(0..20).collect::<Vec<_>>());
+  let proof = tree(6).proof(4, ProofOptions::new()).unwrap();
+  assert_eq!(proof.nodes, [6, 1, 9]);
+  let (nodes, polls) = block_on(assemble_proof(&proof, &storage));
+  let expected: Vec<_> = proof
+    .nodes
+    .iter()
+    .map(|&i| (i, format!("node {}", i)))
+    .collect();
+  assert_eq!(nodes, Ok(expected));
+  assert_eq!(polls, 4);
+
+  let proof = Proof {
+    nodes: vec![],
+    verified_by: 0,
+  };
+  let (nodes, polls) = block_on(assemble_proof(&proof, &storage));
+  assert_eq!(nodes, Ok(vec![]));
+  assert_eq!(polls, 1);
+}
+
+#[test]
+fn missing_node() {
+  let storage = storage(&[2, 11]);
+  let proof = tree(8).proof(0, ProofOptions::new()).unwrap();
+  let (proof, _) = block_on(assemble_proof(&proof, &storage));
+  assert_eq!(proof, Err(MissingNode { index: 5 }));
+  assert_eq!(MissingNode { index: 5 }.to_string(), "node 5 isn't stored");
+}
@@ -0,0 +1,152 @@
+//! Load the fixtures written by `tests/fixtures/js/generate.js` from the
+//! reference JavaScript implementation.
+//!
+//! A fixture is a JSON object with:
+//!
+//! - `package`: the name and version of the packages that generated it,
+//!   separated by spaces.
+//! - `functions`: calls of the index functions, each with the camel case
+//!   `name` of the function, its `args`, and either its `result` or
+//!   `"throws": true`.
+//! - `iterators`: an iterator `start` index and the `steps` taken from it,
+//!   each with the method called as `op`, its `result`, and the `index`,
+//!   `offset` and `factor` of the iterator after the call.
+
+use serde_json::{self, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The fixtures for one version of the JavaScript package.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fixture {
+  pub package: String,
+  pub functions: Vec<FunctionCase>,
+  pub iterators: Vec<IteratorCase>,
+}
+
+/// A call of an index function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionCase {
+  pub name: String,
+  pub args: Vec<usize>,
+  /// The returned value, or `None` if the call threw.
+  pub result: Option<Value>,
+}
+
+/// A sequence of steps taken by an iterator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IteratorCase {
+  pub start: usize,
+  pub steps: Vec<Step>,
+}
+
+/// A method called on an iterator, and the iterator's state after it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+  pub op: String,
+  pub result: Value,
+  pub index: usize,
+  pub offset: usize,
+  pub factor: usize,
+}
+
+/// The directory holding the fixtures.
+pub fn dir() -> PathBuf {
+  Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/js")
+}
+
+/// Load every `.json` fixture in `dir()`, sorted by path.
+pub fn load_all() -> Vec<(PathBuf, Fixture)> {
+  let mut paths: Vec<_> = fs::read_dir(dir())
+    .unwrap()
+    .map(|entry| entry.unwrap().path())
+    .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+    .collect();
+  paths.sort();
+  paths
+    .into_iter()
+    .map(|path| {
+      let json = fs::read_to_string(&path).unwrap();
+      let fixture = parse(&json)
+        .unwrap_or_else(|err| panic!("{}: {}", path.display(), err));
+      (path, fixture)
+    })
+    .collect()
+}
+
+/// Parse a fixture.
+pub fn parse(json: &str) -> Result<Fixture, String> {
+  let value: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+  let functions = array(&value, "functions")?
+    .iter()
+    .map(|case| {
+      let args = array(case, "args")?
+        .iter()
+        .map(index)
+        .collect::<Result<_, _>>()?;
+      let throws = case.get("throws") == Some(&Value::Bool(true));
+      Ok(FunctionCase {
+        name: string(case, "name")?,
+        args,
+        result: if throws {
+          None
+        } else {
+          Some(case.get("result").cloned().unwrap_or(Value::Null))
+        },
+      })
+    })
+    .collect::<Result<_, String>>()?;
+  let iterators = array(&value, "iterators")?
+    .iter()
+    .map(|case| {
+      let steps = array(case, "steps")?
+        .iter()
+        .map(|step| {
+          Ok(Step {
+            op: string(step, "op")?,
+            result: step.get("result").cloned().unwrap_or(Value::Null),
+            index: field_index(step, "index")?,
+            offset: field_index(step, "offset")?,
+            factor: field_index(step, "factor")?,
+          })
+        })
+        .collect::<Result<_, String>>()?;
+      Ok(IteratorCase {
+        start: field_index(case, "start")?,
+        steps,
+      })
+    })
+    .collect::<Result<_, String>>()?;
+  Ok(Fixture {
+    package: string(&value, "package")?,
+    functions,
+    iterators,
+  })
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> Result<&'a Vec<Value>, String> {
+  value
+    .get(key)
+    .and_then(Value::as_array)
+    .ok_or_else(|| format!("missing array {:?}", key))
+}
+
+fn string(value: &Value, key: &str) -> Result<String, String> {
+  value
+    .get(key)
+    .and_then(Value::as_str)
+    .map(str::to_string)
+    .ok_or_else(|| format!("missing string {:?}", key))
+}
+
+fn field_index(value: &Value, key: &str) -> Result<usize, String> {
+  index(value.get(key).unwrap_or(&Value::Null))
+    .map_err(|err| format!("{:?}: {}", key, err))
+}
+
+fn index(value: &Value) -> Result<usize, String> {
+  value
+    .as_u64()
+    .map(|i| i as usize)
+    .ok_or_else(|| format!("not an index: {}", value))
+}
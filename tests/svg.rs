@@ -0,0 +1,99 @@
+#![cfg(feature = "svg")]
+
+extern crate flat_tree;
+
+use flat_tree::{to_svg, Highlights, NodeSet, SvgOptions, TreeIndex};
+
+fn circles(svg: &str) -> Vec<&str> {
+  svg
+    .lines()
+    .filter(|line| line.starts_with("<circle"))
+    .collect()
+}
+
+#[test]
+fn draws_every_node() {
+  let svg = to_svg(&TreeIndex::new(), 4, SvgOptions::new());
+  assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" "#));
+  assert!(svg.ends_with("</svg>\n"));
+  assert_eq!(circles(&svg).len(), 7);
+  assert_eq!(svg.matches("<line ").count(), 6);
+  for i in 0..7 {
+    assert!(svg.contains(&format!(r#"dy="0.35em">{}</text>"#, i)));
+  }
+  assert_eq!(svg.matches("<text ").count(), 7);
+}
+
+#[test]
+fn colors_node_states() {
+  let mut tree = TreeIndex::new();
+  tree.set(0);
+  tree.set(2);
+  let opts = SvgOptions::new()
+    .present("green")
+    .missing("white")
+    .root("red")
+    .stroke("gray");
+  let svg = to_svg(&tree, 3, opts);
+  let circles = circles(&svg);
+  assert_eq!(circles.len(), 4);
+
+  // Nodes 0, 1 and 2 are present, 4 isn't. The roots are 1 and 4.
+  let fills: Vec<_> = circles
+    .iter()
+    .map(|c| c.contains(r#"fill="green""#))
+    .collect();
+  assert_eq!(fills, [true, true, true, false]);
+  let roots: Vec<_> = circles
+    .iter()
+    .map(|c| c.contains(r#"stroke="red" stroke-width="2""#))
+    .collect();
+  assert_eq!(roots, [false, true, false, true]);
+}
+
+#[test]
+fn escapes_colors() {
+  let opts = SvgOptions::new().present(r#"a"<&"#);
+  let mut tree = TreeIndex::new();
+  tree.set(0);
+  let svg = to_svg(&tree, 1, opts);
+  assert!(svg.contains(r#"fill="a&quot;&lt;&amp;""#));
+}
+
+#[test]
+fn empty_tree() {
+  let svg = to_svg(&TreeIndex::new(), 0, SvgOptions::new());
+  assert!(circles(&svg).is_empty());
+  assert!(svg.ends_with("</svg>\n"));
+}
+
+#[test]
+fn rings_highlighted_nodes() {
+  let have: NodeSet = vec![0, 1].into_iter().collect();
+  let want: NodeSet = vec![1].into_iter().collect();
+  let highlights = Highlights::new()
+    .set("have", &have)
+    .set("want <", &want)
+    .annotate(|i| {
+      if i == 2 {
+        Some("missing".to_string())
+      } else {
+        None
+      }
+    });
+  let svg = to_svg(
+    &TreeIndex::new(),
+    2,
+    SvgOptions::new().highlights(&highlights),
+  );
+  let rings: Vec<_> = circles(&svg)
+    .into_iter()
+    .filter(|c| c.contains(r#"fill="none""#))
+    .collect();
+  assert_eq!(rings.len(), 3);
+  assert_eq!(rings.iter().filter(|c| c.contains("#1f77b4")).count(), 2);
+  assert_eq!(rings.iter().filter(|c| c.contains("#ff7f0e")).count(), 1);
+  assert!(svg.contains("><title>missing</title></circle>\n"));
+  assert!(svg.contains(r##"fill="#1f77b4">have</text>"##));
+  assert!(svg.contains(r##"fill="#ff7f0e">want &lt;</text>"##));
+}
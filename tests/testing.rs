@@ -0,0 +1,58 @@
+#![cfg(feature = "proptest")]
+
+extern crate flat_tree;
+#[macro_use]
+extern crate proptest;
+
+use flat_tree::testing;
+
+proptest! {
+  #[test]
+  fn depth_and_offset_fit((depth, offset) in testing::depth_and_offset()) {
+    prop_assert!(depth <= testing::MAX_DEPTH);
+    let i = flat_tree::index(depth, offset);
+    prop_assert_eq!(flat_tree::depth(i), depth);
+    prop_assert_eq!(flat_tree::offset(i), offset);
+  }
+
+  #[test]
+  fn every_node_is_consistent(i in testing::node()) {
+    testing::assert_node(i);
+  }
+
+  #[test]
+  fn node_is_in_its_tree((leaves, i) in testing::tree_and_node(100)) {
+    prop_assert!(flat_tree::spans(i).1 < 2 * leaves);
+  }
+
+  #[test]
+  fn node_set_is_in_its_tree(set in testing::node_set(20, 10)) {
+    prop_assert!(set.len() <= 10);
+    for i in &set {
+      prop_assert!(flat_tree::spans(i).1 < 40);
+    }
+  }
+
+  #[test]
+  fn leaf_range_is_in_its_tree(range in testing::leaf_range(20)) {
+    prop_assert!(range.start <= range.end && range.end <= 20);
+  }
+
+  #[test]
+  fn roots_cover_leaves(leaves in testing::tree(1000)) {
+    testing::assert_roots(leaves);
+  }
+}
+
+#[test]
+fn empty_tree_has_no_node_set() {
+  use proptest::strategy::{Strategy, ValueTree};
+  use proptest::test_runner::TestRunner;
+
+  let mut runner = TestRunner::default();
+  let set = testing::node_set(0, 10)
+    .new_tree(&mut runner)
+    .unwrap()
+    .current();
+  assert!(set.is_empty());
+}
@@ -0,0 +1,82 @@
+extern crate flat_tree;
+
+use flat_tree::Tile;
+
+#[test]
+fn locate_round_trips() {
+  for height in 1..5 {
+    for i in flat_tree::inorder(64) {
+      match Tile::locate(i, height) {
+        Some((tile, offset)) => {
+          assert_eq!(flat_tree::depth(i) % height, 0);
+          assert_eq!(tile.height, height);
+          assert_eq!(tile.node(offset), i);
+        }
+        None => assert_ne!(flat_tree::depth(i) % height, 0),
+      }
+    }
+  }
+}
+
+#[test]
+fn covering_spans_the_node() {
+  for height in 1..5 {
+    for i in flat_tree::inorder(64) {
+      let (tile, offsets) = Tile::covering(i, height);
+      let first = tile.node(offsets.start);
+      let last = tile.node(offsets.end - 1);
+      assert_eq!(flat_tree::spans(first).0, flat_tree::spans(i).0);
+      assert_eq!(flat_tree::spans(last).1, flat_tree::spans(i).1);
+      assert!(flat_tree::depth(i) - flat_tree::depth(first) < height);
+    }
+  }
+}
+
+#[test]
+fn tile_nodes() {
+  let tile = Tile {
+    height: 2,
+    level: 1,
+    index: 0,
+  };
+  assert_eq!(tile.nodes().collect::<Vec<_>>(), [3, 11, 19, 27]);
+  for node in tile.nodes() {
+    let (left, _) = flat_tree::children(node).unwrap();
+    let (below, offsets) = Tile::covering(left, 2);
+    assert_eq!(below.parent(), tile);
+    assert_eq!(offsets.len(), 2);
+  }
+}
+
+#[test]
+fn width_counts_complete_nodes() {
+  for height in 1..4 {
+    for leaves in 0..40 {
+      for level in 0..4 {
+        for index in 0..4 {
+          let tile = Tile {
+            height,
+            level,
+            index,
+          };
+          let complete = tile
+            .nodes()
+            .filter(|&i| flat_tree::spans(i).1 < 2 * leaves)
+            .count();
+          assert_eq!(tile.width(leaves), complete);
+        }
+      }
+    }
+  }
+}
+
+#[test]
+#[should_panic]
+fn node_past_the_end() {
+  let tile = Tile {
+    height: 2,
+    level: 0,
+    index: 0,
+  };
+  tile.node(4);
+}
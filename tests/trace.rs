@@ -0,0 +1,78 @@
+extern crate flat_tree;
+
+use flat_tree::{Cursor, Move, Orientation, RenderOptions, Step, Trace};
+
+#[test]
+fn frames_follow_the_cursor() {
+  let mut cursor = Cursor::new(3);
+  cursor.left_child();
+  cursor.sibling();
+  cursor.undo();
+  cursor.right_child();
+  let trace = Trace::from(&cursor);
+  assert_eq!(trace.path(), [3, 1, 2]);
+
+  let opts = RenderOptions::new().orientation(Orientation::LeftRight);
+  let frames = trace.frames(4, opts);
+  assert_eq!(frames.len(), 3);
+  assert_eq!(
+    frames[2],
+    concat!(
+      "frame 2: 1 -> 2 (right_child)\n",
+      "3+\n",
+      "├── 1+\n",
+      "│   ├── 0\n",
+      "│   └── 2*\n",
+      "└── 5\n",
+      "    ├── 4\n",
+      "    └── 6\n",
+      "\n",
+      "* here\n",
+      "+ visited\n",
+    )
+  );
+  assert!(frames[0].starts_with("frame 0: start at 3\n3*\n"));
+}
+
+#[test]
+fn walk_records_moves() {
+  let (trace, found) = Trace::walk(7, 8, |node, ctx| {
+    if node == 9 {
+      Step::Stop
+    } else if ctx.depth == 1 {
+      Step::SkipSubtree
+    } else {
+      Step::Descend
+    }
+  });
+  assert_eq!(found, Some(9));
+  assert_eq!(trace.path(), [7, 3, 1, 5, 11, 9]);
+  let ops: Vec<Move> = trace.steps().iter().map(|step| step.op).collect();
+  assert_eq!(
+    ops,
+    [
+      Move::LeftChild,
+      Move::LeftChild,
+      Move::Sibling,
+      Move::Seek,
+      Move::LeftChild
+    ]
+  );
+
+  let (trace, found) = Trace::walk(7, 3, |_, _| Step::Descend);
+  assert_eq!(found, None);
+  assert_eq!(trace.path(), [7]);
+}
+
+#[cfg(feature = "svg")]
+#[test]
+fn svg_frames() {
+  let mut trace = Trace::new(1);
+  trace.push(Move::RightChild, 2);
+  let frames = trace.svg_frames(2, flat_tree::SvgOptions::new());
+  assert_eq!(frames.len(), 2);
+  assert!(
+    frames[1].contains(">\n<title>frame 1: 1 -> 2 (right_child)</title>\n")
+  );
+  assert!(frames[1].ends_with("</svg>\n"));
+}
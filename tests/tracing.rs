@@ -0,0 +1,128 @@
+#![cfg(feature = "tracing")]
+
+extern crate flat_tree;
+extern crate tracing;
+
+use flat_tree::{multiproof, NodeSet, Planner, ProofOptions, TreeIndex};
+use std::fmt::{self, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Records every span and event as a line of text.
+#[derive(Clone, Default)]
+struct Recorder {
+  lines: Arc<Mutex<Vec<String>>>,
+  next_id: Arc<AtomicU64>,
+}
+
+struct Fields(String);
+
+impl Visit for Fields {
+  fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+    write!(self.0, " {}={:?}", field.name(), value).unwrap();
+  }
+}
+
+impl Recorder {
+  fn push(&self, prefix: &str, name: &str, fields: Fields) {
+    let line = format!("{} {}{}", prefix, name, fields.0);
+    self.lines.lock().unwrap().push(line);
+  }
+
+  fn lines(&self) -> Vec<String> {
+    self.lines.lock().unwrap().clone()
+  }
+}
+
+impl Subscriber for Recorder {
+  fn enabled(&self, _: &Metadata) -> bool {
+    true
+  }
+
+  fn new_span(&self, span: &Attributes) -> Id {
+    let mut fields = Fields(String::new());
+    span.record(&mut fields);
+    self.push("span", span.metadata().name(), fields);
+    Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+  }
+
+  fn record(&self, _: &Id, values: &Record) {
+    let mut fields = Fields(String::new());
+    values.record(&mut fields);
+    self.push("record", "", fields);
+  }
+
+  fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+  fn event(&self, event: &Event) {
+    let mut fields = Fields(String::new());
+    event.record(&mut fields);
+    self.push("event", "", fields);
+  }
+
+  fn enter(&self, _: &Id) {}
+
+  fn exit(&self, _: &Id) {}
+}
+
+fn record<F: FnOnce()>(f: F) -> Vec<String> {
+  let recorder = Recorder::default();
+  tracing::subscriber::with_default(recorder.clone(), f);
+  recorder.lines()
+}
+
+#[test]
+fn set_records_completed_parents() {
+  let mut tree = TreeIndex::new();
+  tree.set(0);
+  let lines = record(|| {
+    tree.set(2);
+  });
+  assert_eq!(
+    lines,
+    [
+      "span set index=2",
+      "event  message=completed parent parent=1",
+      "event  return=true",
+    ]
+  );
+}
+
+#[test]
+fn proof_and_planning() {
+  let mut tree = TreeIndex::new();
+  for leaf in &[0, 2, 4] {
+    tree.set(*leaf);
+  }
+  let lines = record(|| {
+    tree.proof(0, ProofOptions::new());
+  });
+  assert_eq!(lines[0], "span proof index=0 digest=0 hash=false");
+  assert!(lines[1].contains("nodes: [2, 4]"));
+
+  let local = TreeIndex::new();
+  let lines = record(|| {
+    Planner::new()
+      .max_outstanding(4)
+      .plan(&local, &tree, &NodeSet::new());
+  });
+  assert_eq!(
+    lines,
+    [
+      "span plan strategy=PrefixFirst max_outstanding=4 in_flight=0",
+      "span want local_len=0 remote_len=8",
+      "event  message=wanted nodes nodes=[1, 4]",
+      "event  message=planned request node=1 priority=0",
+      "event  message=planned request node=4 priority=2",
+    ]
+  );
+
+  let lines = record(|| {
+    multiproof(&[0], 4);
+  });
+  assert_eq!(lines[0], "span multiproof nodes=[0] leaves=4");
+  assert_eq!(lines[1], "event  message=proof nodes proof=[2, 5]");
+}
@@ -0,0 +1,272 @@
+extern crate flat_tree;
+
+use flat_tree::{nodes, Order};
+
+#[test]
+fn nodes_empty_tree() {
+  assert_eq!(nodes(0, Order::InOrder).count(), 0);
+  assert_eq!(nodes(0, Order::PreOrder).count(), 0);
+  assert_eq!(nodes(0, Order::PostOrder).count(), 0);
+}
+
+#[test]
+fn nodes_incomplete_tree() {
+  let all: Vec<usize> = nodes(5, Order::InOrder).collect();
+  assert_eq!(all, [0, 1, 2, 3, 4, 5, 6, 8]);
+  let all: Vec<usize> = nodes(5, Order::PreOrder).collect();
+  assert_eq!(all, [3, 1, 0, 2, 5, 4, 6, 8]);
+  let all: Vec<usize> = nodes(5, Order::PostOrder).collect();
+  assert_eq!(all, [0, 2, 1, 4, 6, 5, 3, 8]);
+}
+
+#[test]
+#[should_panic(expected = "more nodes than fit in a usize")]
+fn nodes_too_many_leaves() {
+  nodes(usize::MAX / 2 + 1, Order::InOrder);
+}
+
+#[test]
+#[should_panic(expected = "more nodes than fit in a usize")]
+fn parents_by_level_too_many_leaves() {
+  flat_tree::parents_by_level(usize::MAX / 2 + 1);
+}
+
+#[test]
+fn nodes_orders_agree() {
+  for leaves in 0..64 {
+    let mut in_order: Vec<usize> = nodes(leaves, Order::InOrder).collect();
+    let mut pre_order: Vec<usize> = nodes(leaves, Order::PreOrder).collect();
+    let mut post_order: Vec<usize> = nodes(leaves, Order::PostOrder).collect();
+    let mut level_order: Vec<usize> =
+      nodes(leaves, Order::LevelOrder).collect();
+    assert_eq!(in_order.len(), nodes(leaves, Order::InOrder).len());
+    in_order.sort();
+    pre_order.sort();
+    post_order.sort();
+    level_order.sort();
+    assert_eq!(in_order, pre_order);
+    assert_eq!(in_order, post_order);
+    assert_eq!(in_order, level_order);
+  }
+}
+
+#[test]
+fn nodes_with_coords() {
+  for order in [Order::InOrder, Order::PreOrder, Order::PostOrder].iter() {
+    for coords in nodes(37, *order).with_coords() {
+      assert_eq!(coords.depth, flat_tree::depth(coords.index));
+      assert_eq!(coords.offset, flat_tree::offset(coords.index));
+    }
+  }
+}
+
+#[test]
+fn coords_at_the_top_of_the_range() {
+  let max_depth = flat_tree::Iterator::MAX_DEPTH;
+  let deepest = (1 << max_depth) - 1;
+  let coords = flat_tree::Coords::new(deepest);
+  assert_eq!((coords.depth, coords.offset), (max_depth, 0));
+  let coords = flat_tree::Coords::new(flat_tree::index(max_depth, 1));
+  assert_eq!((coords.depth, coords.offset), (max_depth, 1));
+  assert_eq!(flat_tree::Coords::try_new(usize::MAX), None);
+  assert_eq!(flat_tree::Coords::try_new(usize::MAX >> 1), None);
+  assert!(flat_tree::Coords::try_new(deepest).is_some());
+}
+
+#[test]
+#[should_panic]
+fn coords_too_deep() {
+  flat_tree::Coords::new(usize::MAX);
+}
+
+#[test]
+fn ancestors_unbounded() {
+  let mut expected = Vec::new();
+  let mut i = 10;
+  while flat_tree::depth(i) < flat_tree::Iterator::MAX_DEPTH {
+    i = flat_tree::parent(i);
+    expected.push(i);
+  }
+  let ancestors = flat_tree::ancestors(10);
+  assert_eq!(ancestors.len(), expected.len());
+  assert_eq!(ancestors.collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn ancestors_bounded() {
+  for leaves in 1..40 {
+    for leaf in 0..leaves {
+      let ancestors: Vec<usize> =
+        flat_tree::ancestors(2 * leaf).within(leaves).collect();
+      assert_eq!(
+        ancestors.len(),
+        flat_tree::ancestors(2 * leaf).within(leaves).len()
+      );
+      let mut roots = Vec::new();
+      flat_tree::full_roots(2 * leaves, &mut roots);
+      let top = *ancestors.last().unwrap_or(&(2 * leaf));
+      assert!(roots.contains(&top));
+    }
+  }
+  assert_eq!(flat_tree::ancestors(12).within(6).count(), 0);
+  assert_eq!(flat_tree::ancestors(0).within(8).to_depth(1).len(), 1);
+}
+
+#[test]
+fn descendants() {
+  for i in 0..128 {
+    let (left, right) = flat_tree::spans(i);
+    let root_depth = flat_tree::depth(i);
+    for to_depth in 0..root_depth + 2 {
+      let expected: Vec<usize> = (left..=right)
+        .filter(|&j| j != i && flat_tree::depth(j) >= to_depth)
+        .collect();
+      let descendants = flat_tree::descendants(i).to_depth(to_depth);
+      assert_eq!(descendants.len(), expected.len());
+      assert_eq!(descendants.collect::<Vec<_>>(), expected);
+      let mut reversed: Vec<usize> = descendants.rev().collect();
+      reversed.reverse();
+      assert_eq!(reversed, expected);
+    }
+  }
+}
+
+#[test]
+fn preorder_subtree() {
+  for root in 0..128 {
+    let preorder: Vec<usize> = flat_tree::preorder_subtree(root).collect();
+    assert_eq!(preorder[0], root);
+    assert_eq!(preorder.len(), flat_tree::count(root));
+    for (position, &i) in preorder.iter().enumerate().skip(1) {
+      let parent = flat_tree::parent(i);
+      assert!(preorder[..position].contains(&parent));
+    }
+    let mut in_order: Vec<usize> =
+      flat_tree::subtree_nodes(root, Order::InOrder).collect();
+    let mut sorted = preorder.clone();
+    sorted.sort();
+    assert_eq!(in_order, sorted);
+    in_order.retain(|&i| i != root);
+    assert_eq!(in_order, flat_tree::descendants(root).collect::<Vec<_>>());
+  }
+}
+
+#[test]
+fn postorder() {
+  for leaves in 0..64 {
+    let postorder: Vec<usize> = flat_tree::postorder(leaves).collect();
+    for (position, &i) in postorder.iter().enumerate() {
+      if let Some((left, right)) = flat_tree::children(i) {
+        assert!(postorder[..position].contains(&left));
+        assert!(postorder[..position].contains(&right));
+      }
+    }
+    let mut computable = Vec::new();
+    for leaf in 0..leaves {
+      let mut i = 2 * leaf;
+      computable.push(i);
+      while flat_tree::sibling(i) < i {
+        i = flat_tree::parent(i);
+        computable.push(i);
+      }
+    }
+    assert_eq!(postorder, computable);
+  }
+}
+
+#[test]
+fn inorder() {
+  for leaves in 0..64 {
+    let expected: Vec<usize> = (0..2 * leaves)
+      .filter(|&i| flat_tree::right_span(i) < 2 * leaves)
+      .collect();
+    let inorder = flat_tree::inorder(leaves);
+    assert_eq!(inorder.len(), expected.len());
+    assert_eq!(inorder.collect::<Vec<_>>(), expected);
+  }
+}
+
+#[test]
+fn levelorder() {
+  for leaves in 0..64 {
+    let levelorder: Vec<usize> = flat_tree::levelorder(leaves).collect();
+    let depths: Vec<usize> =
+      levelorder.iter().map(|&i| flat_tree::depth(i)).collect();
+    let mut sorted = depths.clone();
+    sorted.sort_by(|a, b| b.cmp(a));
+    assert_eq!(depths, sorted);
+  }
+  for root in 0..128 {
+    let mut levelorder: Vec<usize> =
+      flat_tree::levelorder_subtree(root).collect();
+    assert_eq!(levelorder[0], root);
+    levelorder.sort();
+    assert_eq!(
+      levelorder,
+      flat_tree::inorder_subtree(root).collect::<Vec<_>>()
+    );
+  }
+}
+
+#[test]
+fn path_to_ancestor() {
+  for node in 0..64 {
+    for ancestor in 0..128 {
+      let (left, right) = flat_tree::spans(ancestor);
+      match flat_tree::path_to_ancestor(node, ancestor) {
+        Ok(path) => {
+          assert!(left <= node && node <= right);
+          let path: Vec<usize> = path.collect();
+          assert_eq!(path.last(), Some(&ancestor).filter(|_| node != ancestor));
+          let mut i = node;
+          for &j in &path {
+            assert_eq!(flat_tree::parent(i), j);
+            i = j;
+          }
+        }
+        Err(err) => {
+          assert!(node < left || node > right);
+          assert_eq!(err.node, node);
+          assert_eq!(err.ancestor, ancestor);
+        }
+      }
+    }
+  }
+}
+
+#[test]
+fn path_from_root() {
+  for leaves in 0..40 {
+    for target in 0..2 * leaves + 4 {
+      let mut expected: Vec<usize> =
+        flat_tree::ancestors(target).within(leaves).collect();
+      expected.reverse();
+      if flat_tree::right_span(target) < 2 * leaves {
+        expected.push(target);
+      }
+      let path = flat_tree::path_from_root(target, leaves);
+      assert_eq!(path.len(), expected.len());
+      assert_eq!(path.collect::<Vec<_>>(), expected);
+    }
+  }
+}
+
+#[test]
+fn parents_by_level() {
+  for leaves in 0..100 {
+    let mut built: Vec<usize> = (0..leaves).map(|i| 2 * i).collect();
+    let parents = flat_tree::parents_by_level(leaves);
+    let len = parents.len();
+    let mut previous_depth = 1;
+    for (parent, left, right) in parents {
+      assert_eq!(flat_tree::children(parent), Some((left, right)));
+      assert!(built.contains(&left) && built.contains(&right));
+      assert!(flat_tree::depth(parent) >= previous_depth);
+      previous_depth = flat_tree::depth(parent);
+      built.push(parent);
+    }
+    assert_eq!(built.len() - leaves, len);
+    built.sort();
+    assert_eq!(built, flat_tree::inorder(leaves).collect::<Vec<_>>());
+  }
+}
@@ -0,0 +1,47 @@
+extern crate flat_tree;
+
+use flat_tree::TreeBuilder;
+
+fn concat(parent: usize, left: &String, right: &String) -> String {
+  format!("{}({},{})", parent, left, right)
+}
+
+#[test]
+fn tree_builder_emits_every_node_once() {
+  let mut builder = TreeBuilder::new(concat);
+  let mut emitted = Vec::new();
+  for leaf in 0..16 {
+    emitted.extend(builder.push(leaf.to_string()));
+  }
+  assert_eq!(builder.leaves(), 16);
+
+  let mut indices: Vec<_> = emitted.iter().map(|node| node.0).collect();
+  indices.sort();
+  assert_eq!(indices, (0..31).collect::<Vec<_>>());
+
+  let postorder: Vec<_> = flat_tree::postorder(16).collect();
+  let order: Vec<_> = emitted.iter().map(|node| node.0).collect();
+  assert_eq!(order, postorder);
+}
+
+#[test]
+fn tree_builder_parent_values() {
+  let mut builder = TreeBuilder::new(concat);
+  assert!(builder.is_empty());
+  for leaf in &["a", "b", "c"] {
+    builder.push(leaf.to_string());
+  }
+  let roots: Vec<_> = builder.roots().collect();
+  assert_eq!(roots, [(1, &"1(a,b)".to_string()), (4, &"c".to_string())]);
+
+  let nodes = builder.push("d".to_string());
+  assert_eq!(
+    nodes,
+    [
+      (6, "d".to_string()),
+      (5, "5(c,d)".to_string()),
+      (3, "3(1(a,b),5(c,d))".to_string()),
+    ]
+  );
+  assert_eq!(builder.frontier().roots(), [3]);
+}
@@ -0,0 +1,216 @@
+extern crate flat_tree;
+
+use flat_tree::{Proof, ProofOptions, TreeIndex};
+
+#[test]
+fn set_and_get() {
+  let mut tree = TreeIndex::new();
+  assert!(tree.is_empty());
+  assert!(!tree.get(0));
+  assert!(tree.set(0));
+  assert!(tree.get(0));
+  assert!(!tree.get(1));
+  assert!(!tree.set(0));
+  assert!(!tree.get(1000));
+}
+
+#[test]
+fn set_maintains_parents() {
+  let mut tree = TreeIndex::new();
+  tree.set(0);
+  tree.set(2);
+  assert!(tree.get(1));
+  assert!(!tree.get(3));
+  tree.set(6);
+  assert!(!tree.get(5));
+  tree.set(4);
+  assert!(tree.get(5));
+  assert!(tree.get(3));
+  assert!(!tree.get(7));
+  assert!(tree.len() >= 8);
+}
+
+#[test]
+fn set_parent_directly() {
+  let mut tree = TreeIndex::new();
+  tree.set(1);
+  tree.set(5);
+  assert!(tree.get(3));
+  assert!(!tree.get(0));
+  assert!(!tree.get(4));
+}
+
+#[test]
+fn proof_without_remote() {
+  let mut tree = TreeIndex::new();
+  assert_eq!(tree.proof(0, ProofOptions::new()), None);
+
+  tree.set(0);
+  assert_eq!(tree.proof(0, ProofOptions::new()), Some(proof(&[], 2)));
+  tree.set(2);
+  assert_eq!(tree.proof(0, ProofOptions::new()), Some(proof(&[2], 4)));
+  tree.set(5);
+  assert_eq!(tree.proof(0, ProofOptions::new()), Some(proof(&[2, 5], 8)));
+  tree.set(8);
+  assert_eq!(
+    tree.proof(0, ProofOptions::new()),
+    Some(proof(&[2, 5, 8], 10))
+  );
+  assert_eq!(
+    tree.proof(0, ProofOptions::new().hash(true)),
+    Some(proof(&[0, 2, 5, 8], 10))
+  );
+}
+
+#[test]
+fn proof_with_remote() {
+  let mut tree = TreeIndex::new();
+  for leaf in &[0, 2, 4, 6, 8] {
+    tree.set(*leaf);
+  }
+
+  let mut remote = TreeIndex::new();
+  remote.set(5);
+  assert_eq!(
+    tree.proof(0, ProofOptions::new().remote(&remote)),
+    Some(proof(&[2, 8], 10))
+  );
+
+  remote.set(3);
+  assert_eq!(
+    tree.proof(0, ProofOptions::new().remote(&remote)),
+    Some(proof(&[2], 0))
+  );
+}
+
+#[test]
+fn verified_by() {
+  let mut tree = TreeIndex::new();
+  assert_eq!(tree.verified_by(0), 0);
+  tree.set(0);
+  assert_eq!(tree.verified_by(0), 2);
+  tree.set(2);
+  assert_eq!(tree.verified_by(0), 4);
+  tree.set(14);
+  tree.set(16);
+  assert_eq!(tree.verified_by(0), 4);
+  assert_eq!(tree.verified_by(14), 16);
+  tree.set(5);
+  assert_eq!(tree.verified_by(0), 8);
+  tree.set(11);
+  assert_eq!(tree.verified_by(0), 18);
+}
+
+fn proof(nodes: &[usize], verified_by: usize) -> Proof {
+  Proof {
+    nodes: nodes.to_vec(),
+    verified_by,
+  }
+}
+
+#[test]
+fn digest() {
+  let tree = TreeIndex::new();
+  assert_eq!(tree.digest(0), 0b0);
+
+  let mut tree = TreeIndex::new();
+  tree.set(0);
+  assert_eq!(tree.digest(0), 0b1);
+
+  let mut tree = TreeIndex::new();
+  tree.set(1);
+  assert_eq!(tree.digest(0), 0b101);
+
+  let mut tree = TreeIndex::new();
+  tree.set(2);
+  assert_eq!(tree.digest(0), 0b10);
+
+  let mut tree = TreeIndex::new();
+  tree.set(1);
+  tree.set(2);
+  assert_eq!(tree.digest(0), 0b1);
+
+  let mut tree = TreeIndex::new();
+  tree.set(3);
+  tree.set(2);
+  assert_eq!(tree.digest(0), 0b1011);
+
+  let mut tree = TreeIndex::new();
+  tree.set(5);
+  assert_eq!(tree.digest(1), 0b10);
+}
+
+#[test]
+fn proof_with_digest() {
+  let mut tree = TreeIndex::new();
+  for leaf in &[0, 2, 4, 6, 8] {
+    tree.set(*leaf);
+  }
+
+  assert_eq!(
+    tree.proof(0, ProofOptions::new().digest(0b1)),
+    Some(proof(&[], 0))
+  );
+  assert_eq!(
+    tree.proof(0, ProofOptions::new().digest(0b1).hash(true)),
+    Some(proof(&[0], 0))
+  );
+  assert_eq!(
+    tree.proof(0, ProofOptions::new().digest(0b10)),
+    Some(proof(&[5, 8], 10))
+  );
+  assert_eq!(
+    tree.proof(0, ProofOptions::new().digest(0b1001)),
+    Some(proof(&[2, 5], 0))
+  );
+}
+
+#[test]
+fn digest_round_trip() {
+  let mut tree = TreeIndex::new();
+  for leaf in &[0, 2, 4, 6, 8] {
+    tree.set(*leaf);
+  }
+  let mut local = TreeIndex::new();
+  local.set(2);
+  local.set(5);
+
+  let digest = local.digest(0);
+  assert_eq!(digest, 0b110);
+  let mut remote = TreeIndex::new();
+  tree.decode_digest(0, digest, &mut remote);
+  assert!(remote.get(2));
+  assert!(remote.get(5));
+  assert_eq!(
+    tree.proof(0, ProofOptions::new().digest(digest)),
+    Some(proof(&[8], 10))
+  );
+}
+
+#[test]
+fn blocks_and_roots() {
+  let mut tree = TreeIndex::new();
+  assert_eq!(tree.blocks(), 0);
+  assert!(tree.roots().is_empty());
+
+  tree.set(0);
+  assert_eq!(tree.blocks(), 1);
+  assert_eq!(tree.roots(), [0]);
+  tree.set(2);
+  assert_eq!(tree.blocks(), 2);
+  assert_eq!(tree.roots(), [1]);
+  tree.set(6);
+  assert_eq!(tree.blocks(), 2);
+  tree.set(4);
+  assert_eq!(tree.blocks(), 4);
+  assert_eq!(tree.roots(), [3]);
+  tree.set(8);
+  assert_eq!(tree.blocks(), 5);
+  assert_eq!(tree.roots(), [3, 8]);
+
+  let mut tree = TreeIndex::new();
+  tree.set(2);
+  assert_eq!(tree.blocks(), 0);
+  tree.set(3);
+  assert_eq!(tree.blocks(), 4);
+}
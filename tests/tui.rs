@@ -0,0 +1,55 @@
+#![cfg(feature = "tui")]
+
+use std::env;
+use std::fs;
+use std::process::Command;
+
+fn frame(args: &[&str]) -> Vec<String> {
+  let output = Command::new(env!("CARGO_BIN_EXE_flat-tree-tui"))
+    .arg("--once")
+    .args(args)
+    .output()
+    .unwrap();
+  assert!(output.status.success());
+  let stdout = String::from_utf8(output.stdout).unwrap();
+  stdout.lines().map(str::to_string).collect()
+}
+
+#[test]
+fn draws_the_tree() {
+  assert_eq!(
+    frame(&["--leaves", "4", "--at", "1"]),
+    [
+      "              3",
+      "    [1]               5",
+      "  0       2       4       6",
+      "",
+      "index 1  depth 1  offset 0  spans 0..=2  marked no",
+    ]
+  );
+}
+
+#[test]
+fn marks_nodes() {
+  let lines = frame(&["--leaves", "3", "--nodes", "0,1"]);
+  assert_eq!(lines[0], "     1*");
+  assert_eq!(lines[1], "[0]*      2       4");
+  assert!(lines[3].ends_with("marked yes"));
+
+  let path = env::temp_dir().join("flat-tree-tui-bitfield");
+  fs::write(&path, [0b1110_0000]).unwrap();
+  let lines = frame(&["--leaves", "2", "--bitfield", path.to_str().unwrap()]);
+  fs::remove_file(&path).unwrap();
+  assert_eq!(lines[0], "     1*");
+  assert_eq!(lines[1], "[0]*     2*");
+}
+
+#[test]
+fn rejects_nodes_outside_the_tree() {
+  let status = Command::new(env!("CARGO_BIN_EXE_flat-tree-tui"))
+    .args(["--once", "--leaves", "3", "--at", "5"])
+    .output()
+    .unwrap()
+    .status;
+  assert_eq!(status.code(), Some(2));
+}
@@ -0,0 +1,72 @@
+extern crate flat_tree;
+
+use flat_tree::{walk, Step};
+
+#[test]
+fn walk_matches_preorder() {
+  for root in 0..64 {
+    let mut visited = Vec::new();
+    let stopped = walk(root, 64, |node, ctx| {
+      assert_eq!(ctx.depth, flat_tree::depth(node));
+      assert_eq!(ctx.offset, flat_tree::offset(node));
+      assert_eq!(ctx.level, flat_tree::depth(root) - ctx.depth);
+      assert_eq!(ctx.is_leaf(), ctx.depth == 0);
+      visited.push(node);
+      Step::Descend
+    });
+    assert_eq!(stopped, None);
+    assert_eq!(
+      visited,
+      flat_tree::preorder_subtree(root).collect::<Vec<_>>()
+    );
+  }
+}
+
+#[test]
+fn walk_stop() {
+  let mut visited = Vec::new();
+  let stopped = walk(15, 16, |node, _| {
+    visited.push(node);
+    if node == 2 {
+      Step::Stop
+    } else {
+      Step::Descend
+    }
+  });
+  assert_eq!(stopped, Some(2));
+  assert_eq!(visited, [15, 7, 3, 1, 0, 2]);
+}
+
+#[test]
+fn walk_skip() {
+  let mut leaves = Vec::new();
+  walk(7, 8, |node, ctx| {
+    if ctx.is_leaf() {
+      leaves.push(node);
+    }
+    if node == 3 {
+      Step::SkipSubtree
+    } else {
+      Step::Descend
+    }
+  });
+  assert_eq!(leaves, [8, 10, 12, 14]);
+}
+
+#[test]
+fn fold_subtree() {
+  for root in 0..128 {
+    let count = flat_tree::fold_subtree(root, |_| 1, |_, l, r| l + r + 1);
+    assert_eq!(count, flat_tree::count(root));
+
+    let folded = flat_tree::fold_subtree(
+      root,
+      |leaf| leaf,
+      |parent, left, right| {
+        assert_eq!(flat_tree::children(parent), Some((left, right)));
+        parent
+      },
+    );
+    assert_eq!(folded, root);
+  }
+}
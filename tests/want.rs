@@ -0,0 +1,41 @@
+extern crate flat_tree;
+
+use flat_tree::TreeIndex;
+
+fn tree(nodes: &[usize]) -> TreeIndex {
+  let mut tree = TreeIndex::new();
+  for node in nodes {
+    tree.set(*node);
+  }
+  tree
+}
+
+#[test]
+fn want_nothing() {
+  assert!(tree(&[]).want(&tree(&[])).is_empty());
+  assert!(tree(&[0, 2]).want(&tree(&[0, 2])).is_empty());
+  assert!(tree(&[0, 2, 4]).want(&tree(&[2])).is_empty());
+}
+
+#[test]
+fn want_collapses_missing_subtrees() {
+  let remote = tree(&[0, 2, 4, 6, 8, 10, 12, 14]);
+  assert_eq!(tree(&[]).want(&remote).as_slice(), [7]);
+  assert_eq!(tree(&[0]).want(&remote).as_slice(), [2, 5, 11]);
+  assert_eq!(tree(&[6, 8]).want(&remote).as_slice(), [1, 4, 10, 13]);
+  assert_eq!(tree(&[3]).want(&remote).as_slice(), [11]);
+}
+
+#[test]
+fn want_sparse_remote() {
+  let remote = tree(&[2, 8, 10, 40]);
+  assert_eq!(tree(&[]).want(&remote).as_slice(), [2, 9, 40]);
+  assert_eq!(tree(&[10]).want(&remote).as_slice(), [2, 8, 40]);
+}
+
+#[test]
+fn want_remote_parent_without_children() {
+  let remote = tree(&[5]);
+  assert_eq!(tree(&[]).want(&remote).as_slice(), [5]);
+  assert_eq!(tree(&[4]).want(&remote).as_slice(), [6]);
+}
@@ -0,0 +1,77 @@
+extern crate flat_tree;
+
+use flat_tree::{Planner, Request, TreeIndex, WantQueue};
+
+#[test]
+fn want_queue_pops_by_priority() {
+  let mut queue = WantQueue::new();
+  assert!(queue.is_empty());
+  queue.insert(8, 3);
+  queue.insert(0, 3);
+  queue.insert(20, 1);
+  assert_eq!(
+    queue.peek(),
+    Some(Request {
+      node: 20,
+      priority: 1
+    })
+  );
+
+  let nodes: Vec<_> = std::iter::from_fn(|| queue.pop())
+    .map(|request| request.node)
+    .collect();
+  assert_eq!(nodes, [20, 0, 8]);
+  assert!(queue.is_empty());
+}
+
+#[test]
+fn want_queue_supersedes_descendants() {
+  let mut queue = WantQueue::new();
+  for leaf in (0..8).step_by(2) {
+    assert!(queue.insert(leaf, 10 - leaf));
+  }
+  assert!(queue.insert(16, 0));
+  assert!(queue.insert(5, 20));
+  assert_eq!(queue.len(), 4);
+  assert_eq!(queue.priority(5), Some(4));
+
+  assert!(queue.insert(7, 9));
+  assert_eq!(queue.len(), 2);
+  assert_eq!(queue.priority(7), Some(4));
+  assert!(!queue.contains(6));
+  assert!(queue.covers(6));
+  assert!(!queue.insert(6, 0));
+  assert!(!queue.insert(7, 0));
+
+  assert!(queue.insert(15, 9));
+  assert_eq!(queue.len(), 1);
+  assert_eq!(
+    queue.pop(),
+    Some(Request {
+      node: 15,
+      priority: 0
+    })
+  );
+}
+
+#[test]
+fn want_queue_remove() {
+  let mut queue = WantQueue::new();
+  queue.insert(3, 2);
+  assert_eq!(queue.remove(1), None);
+  assert_eq!(queue.remove(3), Some(2));
+  assert_eq!(queue.pop(), None);
+}
+
+#[test]
+fn want_queue_from_planner() {
+  let local = TreeIndex::new();
+  let mut remote = TreeIndex::new();
+  for leaf in &[0, 2, 4, 8] {
+    remote.set(*leaf);
+  }
+  let mut queue = WantQueue::new();
+  queue.extend(Planner::new().plan(&local, &remote, &Default::default()));
+  assert_eq!(queue.len(), 3);
+  assert_eq!(queue.pop().map(|request| request.node), Some(1));
+}
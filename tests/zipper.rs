@@ -0,0 +1,49 @@
+extern crate flat_tree;
+
+use flat_tree::Zipper;
+
+#[test]
+fn zipper_bounds() {
+  let mut nodes: Vec<usize> = (0..5).collect();
+  let mut zipper = Zipper::new(&mut nodes, 4);
+  assert_eq!(*zipper.get(), 4);
+  assert_eq!(zipper.go_up(), None);
+  assert_eq!(zipper.go_sibling(), None);
+  let mut zipper = Zipper::new(zipper.into_inner(), 2);
+  assert_eq!(zipper.go_sibling(), Some(0));
+  assert_eq!(zipper.go_up(), Some(1));
+  assert_eq!(zipper.go_up(), Some(3));
+  assert_eq!(zipper.go_up(), None);
+  assert_eq!(zipper.go_right(), None);
+  assert_eq!(zipper.go_left(), Some(1));
+  assert_eq!(zipper.go_right(), Some(2));
+  assert_eq!(zipper.go_right(), None);
+  assert_eq!(zipper.go_left(), None);
+  assert_eq!(zipper.index(), 2);
+}
+
+#[test]
+fn zipper_rewrite_path() {
+  let mut nodes = vec![String::new(); 15];
+  {
+    let mut zipper = Zipper::new(&mut nodes, 10);
+    zipper.set("leaf".to_string());
+    while zipper.go_up().is_some() {
+      let index = zipper.index();
+      assert_eq!(zipper.set(index.to_string()), "");
+    }
+    assert_eq!(zipper.index(), 7);
+  }
+  assert_eq!(nodes[10], "leaf");
+  assert_eq!(nodes[9], "9");
+  assert_eq!(nodes[11], "11");
+  assert_eq!(nodes[7], "7");
+  assert_eq!(nodes[3], "");
+}
+
+#[test]
+#[should_panic]
+fn zipper_out_of_bounds() {
+  let mut nodes = vec![0; 3];
+  Zipper::new(&mut nodes, 3);
+}